@@ -1,6 +1,21 @@
 import mixpanel from "https://cdn.mxpnl.com/libs/mixpanel-js/dist/mixpanel.module.js";
 
-mixpanel.init("609abef3172b5fc64554f5ac6c77414d", {autocapture: true,  track_pageview: true,  debug: false, persistence: 'localStorage'});
+// keep in sync with `utils::consent`
+function analyticsConsented() {
+  if (navigator.doNotTrack === "1" || navigator.globalPrivacyControl === true) {
+    return false;
+  }
+  const cookie = document.cookie
+    .split("; ")
+    .find((c) => c.startsWith("privacy-consent="));
+  // essential only until the user opts in
+  if (!cookie) {
+    return false;
+  }
+  return cookie.split("=")[1].split(".").includes("analytics");
+}
+
+mixpanel.init("609abef3172b5fc64554f5ac6c77414d", {autocapture: true,  track_pageview: true,  debug: false, persistence: 'localStorage', opt_out_tracking_by_default: !analyticsConsented()});
 
 window.mixpanel = mixpanel;
 
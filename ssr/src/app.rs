@@ -7,31 +7,39 @@ use leptos_router::hooks::use_location;
 use leptos_router::{components::*, path, MatchNestedRoutes};
 use page::about_us::AboutUs;
 use page::leaderboard::Leaderboard;
-use page::post_view::PostDetailsCacheCtx;
+use page::post_view::{bet::config::BetConfigCtx, PostDetailsCacheCtx};
 use page::root::YralRootPage;
 use page::terms_android::TermsAndroid;
 use page::terms_ios::TermsIos;
 use page::{
+    admin::{audit::AuditLog, console::AdminConsole, moderation::ModerationQueue},
     err::ServerErrorPage,
     logout::Logout,
     menu::Menu,
-    post_view::{single_post::SinglePost, PostView, PostViewCtx},
+    post_view::{
+        following::FollowingFeed, single_post::SinglePost, tags::TagFeed, PostView, PostViewCtx,
+    },
     privacy::PrivacyPolicy,
     profile::{
         profile_post::ProfilePost, LoggedInUserProfileView, ProfilePostsContext, ProfileView,
     },
     refer_earn::ReferEarn,
-    settings::Settings,
+    settings::{
+        AccountDeletionStatus, BetLimitsSettings, DataExport, PrivacySettings, ProfileSettings,
+        Settings,
+    },
     terms::TermsOfService,
     token::{info::TokenInfo, transfer::TokenTransfer},
-    upload::UploadPostPage,
+    upload::{MyUploads, UploadPostPage},
     wallet::Wallet,
 };
 use page::{hon, pumpdump};
 use state::app_state::AppState;
 use state::app_type::AppType;
+use state::balance_store::BalanceStore;
 use state::hn_bet_state::HnBetState;
 use state::{audio_state::AudioState, content_seed_client::ContentSeedClient};
+use utils::consent::ConsentCtx;
 use utils::event_streaming::events::HistoryCtx;
 use utils::event_streaming::EventHistory;
 use utils::types::PostParams;
@@ -111,6 +119,9 @@ pub fn App() -> impl IntoView {
     provide_context(history_ctx.clone());
 
     let _ = HnBetState::init();
+    let _ = BalanceStore::init();
+    let _ = BetConfigCtx::init();
+    let _ = ConsentCtx::init();
 
     let current_post_params = RwSignal::new(None::<PostParams>);
     provide_context(current_post_params);
@@ -172,12 +183,23 @@ pub fn App() -> impl IntoView {
                         />
                         <Route path=path!("/hot-or-not/:canister_id/:post_id") view=PostView />
                         <Route path=path!("/post/:canister_id/:post_id") view=SinglePost />
+                        <Route path=path!("/following") view=FollowingFeed />
+                        <Route path=path!("/tag/:tag") view=TagFeed />
                         <Route path=path!("/profile/:canister_id/post/:post_id") view=ProfilePost />
                         <Route path=path!("/upload") view=UploadPostPage />
+                        <Route path=path!("/my-uploads") view=MyUploads />
                         <Route path=path!("/error") view=ServerErrorPage />
+                        <Route path=path!("/admin") view=AdminConsole />
+                        <Route path=path!("/admin/moderation") view=ModerationQueue />
+                        <Route path=path!("/admin/audit") view=AuditLog />
                         <Route path=path!("/menu") view=Menu />
                         <Route path=path!("/settings") view=Settings />
+                        <Route path=path!("/settings/bet-limits") view=BetLimitsSettings />
+                        <Route path=path!("/settings/profile") view=ProfileSettings />
+                        <Route path=path!("/settings/export") view=DataExport />
+                        <Route path=path!("/settings/privacy") view=PrivacySettings />
                         <Route path=path!("/settings/:action") view=Settings />
+                        <Route path=path!("/account-deletion/:job_id") view=AccountDeletionStatus />
                         <Route path=path!("/refer-earn") view=ReferEarn />
                         <Route path=path!("/profile/:id/:tab") view=ProfileView />
                         <Route path=path!("/profile/:tab") view=LoggedInUserProfileView />
@@ -25,9 +25,19 @@ pub struct AnonymousIdentity {
     pub refresh_token: String,
 }
 
-fn delegate_identity_with_max_age(
+pub fn delegate_identity_with_max_age(
     from: &impl Identity,
     max_age: Duration,
+) -> DelegatedIdentityWire {
+    delegate_identity_with_targets(from, max_age, None)
+}
+
+/// Like [delegate_identity_with_max_age], but the delegated identity can
+/// only call the canisters in `targets`
+pub fn delegate_identity_with_targets(
+    from: &impl Identity,
+    max_age: Duration,
+    targets: Option<Vec<Principal>>,
 ) -> DelegatedIdentityWire {
     let to_secret = k256::SecretKey::random(&mut OsRng);
     let to_identity = Secp256k1Identity::from_private_key(to_secret.clone());
@@ -36,7 +46,7 @@ fn delegate_identity_with_max_age(
     let delegation = Delegation {
         pubkey: to_identity.public_key().unwrap(),
         expiration: expiry_ns,
-        targets: None,
+        targets,
     };
     let sig = from.sign_delegation(&delegation).unwrap();
     let signed_delegation = SignedDelegation {
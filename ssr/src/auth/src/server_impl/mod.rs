@@ -133,6 +133,14 @@ async fn extract_identity_legacy(
 }
 
 pub async fn extract_identity_impl() -> Result<Option<DelegatedIdentityWire>, ServerFnError> {
+    let identity = extract_identity_with_login().await?;
+    Ok(identity.map(|(id, _)| id))
+}
+
+/// Identity attached to the current request and whether it belongs to a user
+/// logged in with OAuth, anonymous and legacy identities are not logged in
+async fn extract_identity_with_login(
+) -> Result<Option<(DelegatedIdentityWire, bool)>, ServerFnError> {
     let key: Key = expect_context();
     let jar: SignedCookieJar = extract_with_state(&key).await?;
 
@@ -145,7 +153,7 @@ pub async fn extract_identity_impl() -> Result<Option<DelegatedIdentityWire>, Se
             return Ok(None);
         };
 
-        Ok(Some(delegate_identity(&base_identity)))
+        Ok(Some((delegate_identity(&base_identity), false)))
     }
 
     #[cfg(feature = "oauth-ssr")]
@@ -158,7 +166,7 @@ pub async fn extract_identity_impl() -> Result<Option<DelegatedIdentityWire>, Se
         };
 
         if let Some(id) = extract_identity_legacy(&jar, &refresh_token).await? {
-            return Ok(Some(id));
+            return Ok(Some((id, false)));
         }
 
         let oauth2: YralOAuthClient = expect_context();
@@ -172,12 +180,30 @@ pub async fn extract_identity_impl() -> Result<Option<DelegatedIdentityWire>, Se
             .id_token()
             .expect("Yral Auth V2 must return an ID token");
         let id_claims = id_token.claims(&yral::token_verifier(), yral::no_op_nonce_verifier)?;
-        let identity = id_claims.additional_claims().ext_delegated_identity.clone();
+        let claims = id_claims.additional_claims();
 
-        Ok(Some(identity))
+        Ok(Some((
+            claims.ext_delegated_identity.clone(),
+            !claims.ext_is_anonymous,
+        )))
     }
 }
 
+/// Principal of the identity attached to the current request,
+/// returns None if refresh token doesn't exist
+pub async fn extract_principal_impl() -> Result<Option<Principal>, ServerFnError> {
+    let identity = extract_identity_impl().await?;
+    Ok(identity.map(|id| Principal::self_authenticating(&id.from_key)))
+}
+
+/// Principal of the identity attached to the current request
+/// and whether the user is logged in with OAuth
+pub async fn extract_principal_with_login_impl() -> Result<Option<(Principal, bool)>, ServerFnError>
+{
+    let identity = extract_identity_with_login().await?;
+    Ok(identity.map(|(id, logged_in)| (Principal::self_authenticating(&id.from_key), logged_in)))
+}
+
 pub async fn logout_identity_impl() -> Result<DelegatedIdentityWire, ServerFnError> {
     let key: Key = expect_context();
     let jar: SignedCookieJar = extract_with_state(&key).await?;
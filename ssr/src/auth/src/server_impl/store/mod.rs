@@ -1,6 +1,8 @@
 pub mod redb_kv;
 pub mod redis_kv;
 
+use std::time::Duration;
+
 use enum_dispatch::enum_dispatch;
 use redis::RedisError;
 use thiserror::Error;
@@ -18,9 +20,45 @@ pub enum KVError {
 }
 
 #[enum_dispatch]
-pub(crate) trait KVStore: Send {
+#[allow(async_fn_in_trait)]
+pub trait KVStore: Send {
     async fn read(&self, key: String) -> Result<Option<String>, KVError>;
     async fn write(&self, key: String, value: String) -> Result<(), KVError>;
+    /// No-op if the key doesn't exist
+    async fn delete(&self, key: String) -> Result<(), KVError>;
+    /// Atomically adds `by` to the integer stored at `key` (0 if missing),
+    /// returns the new value
+    async fn increment(&self, key: String, by: i64) -> Result<i64, KVError>;
+    /// Deletes `key` once `ttl` has passed, later writes keep the expiry
+    async fn expire(&self, key: String, ttl: Duration) -> Result<(), KVError>;
+
+    // Sorted set ("index") operations, members are unique and ordered by score.
+    // Index keys live in their own namespace, don't mix them with the keys above
+
+    /// Adds `member` or updates its score, returns whether it was added
+    async fn index_add(&self, key: String, member: String, score: f64) -> Result<bool, KVError>;
+    /// Returns whether `member` was in the index
+    async fn index_remove(&self, key: String, member: String) -> Result<bool, KVError>;
+    async fn index_score(&self, key: String, member: String) -> Result<Option<f64>, KVError>;
+    async fn index_len(&self, key: String) -> Result<usize, KVError>;
+    /// Up to `limit` members from `offset` in ascending score order, descending if `rev`
+    async fn index_range(
+        &self,
+        key: String,
+        offset: usize,
+        limit: usize,
+        rev: bool,
+    ) -> Result<Vec<String>, KVError>;
+    /// Up to `limit` of the lowest scored members with a score of at most `max`
+    async fn index_range_by_score(
+        &self,
+        key: String,
+        max: f64,
+        limit: usize,
+    ) -> Result<Vec<(String, f64)>, KVError>;
+    /// Keeps the `keep` highest scored members, returns the removed ones
+    async fn index_trim(&self, key: String, keep: usize) -> Result<Vec<String>, KVError>;
+    async fn index_delete(&self, key: String) -> Result<(), KVError>;
 }
 
 #[derive(Clone)]
@@ -1,12 +1,53 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-use redb::{Database, TableDefinition};
+use redb::{Database, ReadableTable, TableDefinition};
 use tokio::task::spawn_blocking;
 
 use super::{KVError, KVStore};
 
 const TABLE: TableDefinition<&str, &str> = TableDefinition::new("kv");
 const RAW_METADATA_TABLE: TableDefinition<&str, &str> = TableDefinition::new("kv-meta");
+/// Expiry of a key in [TABLE], epoch secs
+const EXPIRY_TABLE: TableDefinition<&str, u64> = TableDefinition::new("kv-expiry");
+/// Sorted set members, (index key, member) -> score
+const INDEX_TABLE: TableDefinition<(&str, &str), f64> = TableDefinition::new("kv-index");
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn is_expired(
+    expiry: &impl ReadableTable<&'static str, u64>,
+    key: &str,
+) -> Result<bool, redb::Error> {
+    Ok(expiry
+        .get(key)?
+        .is_some_and(|until| until.value() <= now_secs()))
+}
+
+/// Members of the index at `key` in ascending score order, ties by member like Redis
+fn index_entries(
+    table: &impl ReadableTable<(&'static str, &'static str), f64>,
+    key: &str,
+) -> Result<Vec<(String, f64)>, redb::Error> {
+    let mut entries = vec![];
+    for entry in table.range((key, "")..)? {
+        let (k, score) = entry?;
+        let (index, member) = k.value();
+        if index != key {
+            break;
+        }
+        entries.push((member.to_string(), score.value()));
+    }
+    entries.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(entries)
+}
 
 #[derive(Clone)]
 pub struct ReDBKV(Arc<Database>);
@@ -19,6 +60,8 @@ impl ReDBKV {
         {
             write_txn.open_table(TABLE)?;
             write_txn.open_table(RAW_METADATA_TABLE)?;
+            write_txn.open_table(EXPIRY_TABLE)?;
+            write_txn.open_table(INDEX_TABLE)?;
         }
         write_txn.commit()?;
         Ok(Self(Arc::new(db)))
@@ -39,6 +82,9 @@ impl KVStore for ReDBKV {
         self.spawn_blocking(move |db| {
             let read_txn = db.begin_read()?;
             let value = {
+                if is_expired(&read_txn.open_table(EXPIRY_TABLE)?, &key)? {
+                    return Ok(None);
+                }
                 let table = read_txn.open_table(TABLE)?;
                 let v = table.get(key.as_str())?;
                 v.map(|ag| ag.value().to_string())
@@ -53,6 +99,10 @@ impl KVStore for ReDBKV {
         self.spawn_blocking(move |db| {
             let write_txn = db.begin_write()?;
             {
+                let mut expiry = write_txn.open_table(EXPIRY_TABLE)?;
+                if is_expired(&expiry, &key)? {
+                    expiry.remove(key.as_str())?;
+                }
                 let mut table = write_txn.open_table(TABLE)?;
                 table.insert(key.as_str(), value.as_str())?;
             }
@@ -62,4 +112,196 @@ impl KVStore for ReDBKV {
         .await
         .unwrap()
     }
+
+    async fn delete(&self, key: String) -> Result<(), KVError> {
+        self.spawn_blocking(move |db| {
+            let write_txn = db.begin_write()?;
+            {
+                let mut table = write_txn.open_table(TABLE)?;
+                table.remove(key.as_str())?;
+                write_txn.open_table(EXPIRY_TABLE)?.remove(key.as_str())?;
+            }
+            write_txn.commit()?;
+            Ok::<_, redb::Error>(())
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn increment(&self, key: String, by: i64) -> Result<i64, KVError> {
+        self.spawn_blocking(move |db| {
+            let write_txn = db.begin_write()?;
+            let value = {
+                let mut expiry = write_txn.open_table(EXPIRY_TABLE)?;
+                let expired = is_expired(&expiry, &key)?;
+                if expired {
+                    expiry.remove(key.as_str())?;
+                }
+                let mut table = write_txn.open_table(TABLE)?;
+                let current = match table.get(key.as_str())? {
+                    Some(v) if !expired => v.value().parse::<i64>().map_err(|_| {
+                        redb::Error::Corrupted(format!("value of {key} is not an integer"))
+                    })?,
+                    _ => 0,
+                };
+                let value = current + by;
+                table.insert(key.as_str(), value.to_string().as_str())?;
+                value
+            };
+            write_txn.commit()?;
+            Ok(value)
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn expire(&self, key: String, ttl: Duration) -> Result<(), KVError> {
+        self.spawn_blocking(move |db| {
+            let write_txn = db.begin_write()?;
+            {
+                let mut expiry = write_txn.open_table(EXPIRY_TABLE)?;
+                expiry.insert(key.as_str(), now_secs() + ttl.as_secs())?;
+            }
+            write_txn.commit()?;
+            Ok::<_, redb::Error>(())
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn index_add(&self, key: String, member: String, score: f64) -> Result<bool, KVError> {
+        self.spawn_blocking(move |db| {
+            let write_txn = db.begin_write()?;
+            let added = {
+                let mut table = write_txn.open_table(INDEX_TABLE)?;
+                let prev = table.insert((key.as_str(), member.as_str()), score)?;
+                prev.is_none()
+            };
+            write_txn.commit()?;
+            Ok(added)
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn index_remove(&self, key: String, member: String) -> Result<bool, KVError> {
+        self.spawn_blocking(move |db| {
+            let write_txn = db.begin_write()?;
+            let removed = {
+                let mut table = write_txn.open_table(INDEX_TABLE)?;
+                let prev = table.remove((key.as_str(), member.as_str()))?;
+                prev.is_some()
+            };
+            write_txn.commit()?;
+            Ok(removed)
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn index_score(&self, key: String, member: String) -> Result<Option<f64>, KVError> {
+        self.spawn_blocking(move |db| {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_table(INDEX_TABLE)?;
+            let score = table.get((key.as_str(), member.as_str()))?;
+            Ok(score.map(|s| s.value()))
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn index_len(&self, key: String) -> Result<usize, KVError> {
+        self.spawn_blocking(move |db| {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_table(INDEX_TABLE)?;
+            Ok(index_entries(&table, &key)?.len())
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn index_range(
+        &self,
+        key: String,
+        offset: usize,
+        limit: usize,
+        rev: bool,
+    ) -> Result<Vec<String>, KVError> {
+        self.spawn_blocking(move |db| {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_table(INDEX_TABLE)?;
+            let mut entries = index_entries(&table, &key)?;
+            if rev {
+                entries.reverse();
+            }
+            Ok(entries
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .map(|(member, _)| member)
+                .collect())
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn index_range_by_score(
+        &self,
+        key: String,
+        max: f64,
+        limit: usize,
+    ) -> Result<Vec<(String, f64)>, KVError> {
+        self.spawn_blocking(move |db| {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_table(INDEX_TABLE)?;
+            Ok(index_entries(&table, &key)?
+                .into_iter()
+                .take_while(|(_, score)| *score <= max)
+                .take(limit)
+                .collect())
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn index_trim(&self, key: String, keep: usize) -> Result<Vec<String>, KVError> {
+        self.spawn_blocking(move |db| {
+            let write_txn = db.begin_write()?;
+            let removed = {
+                let mut table = write_txn.open_table(INDEX_TABLE)?;
+                let entries = index_entries(&table, &key)?;
+                let overflow = entries.len().saturating_sub(keep);
+                let removed = entries
+                    .into_iter()
+                    .take(overflow)
+                    .map(|(member, _)| member)
+                    .collect::<Vec<_>>();
+                for member in &removed {
+                    table.remove((key.as_str(), member.as_str()))?;
+                }
+                removed
+            };
+            write_txn.commit()?;
+            Ok(removed)
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn index_delete(&self, key: String) -> Result<(), KVError> {
+        self.spawn_blocking(move |db| {
+            let write_txn = db.begin_write()?;
+            {
+                let mut table = write_txn.open_table(INDEX_TABLE)?;
+                let entries = index_entries(&table, &key)?;
+                for (member, _) in entries {
+                    table.remove((key.as_str(), member.as_str()))?;
+                }
+            }
+            write_txn.commit()?;
+            Ok::<_, redb::Error>(())
+        })
+        .await
+        .unwrap()
+    }
 }
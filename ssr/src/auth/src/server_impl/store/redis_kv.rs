@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use bb8_redis::RedisConnectionManager;
 use redis::{AsyncCommands, RedisError};
 
@@ -27,4 +29,97 @@ impl KVStore for RedisKV {
         con.hset::<_, _, _, ()>(key, AUTH_FIELD, value).await?;
         Ok(())
     }
+
+    async fn delete(&self, key: String) -> Result<(), KVError> {
+        let mut con = self.0.get().await?;
+        con.hdel::<_, _, ()>(key, AUTH_FIELD).await?;
+        Ok(())
+    }
+
+    async fn increment(&self, key: String, by: i64) -> Result<i64, KVError> {
+        let mut con = self.0.get().await?;
+        let value: i64 = con.hincr(key, AUTH_FIELD, by).await?;
+        Ok(value)
+    }
+
+    async fn expire(&self, key: String, ttl: Duration) -> Result<(), KVError> {
+        let mut con = self.0.get().await?;
+        con.expire::<_, ()>(key, ttl.as_secs() as i64).await?;
+        Ok(())
+    }
+
+    async fn index_add(&self, key: String, member: String, score: f64) -> Result<bool, KVError> {
+        let mut con = self.0.get().await?;
+        let added: i64 = con.zadd(key, member, score).await?;
+        Ok(added > 0)
+    }
+
+    async fn index_remove(&self, key: String, member: String) -> Result<bool, KVError> {
+        let mut con = self.0.get().await?;
+        let removed: i64 = con.zrem(key, member).await?;
+        Ok(removed > 0)
+    }
+
+    async fn index_score(&self, key: String, member: String) -> Result<Option<f64>, KVError> {
+        let mut con = self.0.get().await?;
+        let score: Option<f64> = con.zscore(key, member).await?;
+        Ok(score)
+    }
+
+    async fn index_len(&self, key: String) -> Result<usize, KVError> {
+        let mut con = self.0.get().await?;
+        let len: usize = con.zcard(key).await?;
+        Ok(len)
+    }
+
+    async fn index_range(
+        &self,
+        key: String,
+        offset: usize,
+        limit: usize,
+        rev: bool,
+    ) -> Result<Vec<String>, KVError> {
+        if limit == 0 {
+            return Ok(vec![]);
+        }
+        let mut con = self.0.get().await?;
+        let (start, stop) = (offset as isize, (offset + limit - 1) as isize);
+        let members: Vec<String> = if rev {
+            con.zrevrange(key, start, stop).await?
+        } else {
+            con.zrange(key, start, stop).await?
+        };
+        Ok(members)
+    }
+
+    async fn index_range_by_score(
+        &self,
+        key: String,
+        max: f64,
+        limit: usize,
+    ) -> Result<Vec<(String, f64)>, KVError> {
+        let mut con = self.0.get().await?;
+        let members: Vec<(String, f64)> = con
+            .zrangebyscore_limit_withscores(key, "-inf", max, 0, limit as isize)
+            .await?;
+        Ok(members)
+    }
+
+    async fn index_trim(&self, key: String, keep: usize) -> Result<Vec<String>, KVError> {
+        let mut con = self.0.get().await?;
+        let stop = -(keep as isize) - 1;
+        let (removed, _): (Vec<String>, i64) = redis::pipe()
+            .atomic()
+            .zrange(&key, 0, stop)
+            .zremrangebyrank(&key, 0, stop)
+            .query_async(&mut *con)
+            .await?;
+        Ok(removed)
+    }
+
+    async fn index_delete(&self, key: String) -> Result<(), KVError> {
+        let mut con = self.0.get().await?;
+        con.del::<_, ()>(key).await?;
+        Ok(())
+    }
 }
@@ -4,6 +4,7 @@ use ic_agent::identity::DelegatedIdentity;
 use leptos::{ev, prelude::*};
 use leptos_use::{storage::use_local_storage, use_event_listener, use_interval_fn, use_window};
 use state::canisters::auth_state;
+use utils::app_error::error_path;
 use utils::mixpanel::mixpanel_events::*;
 use yral_canisters_common::yral_auth_login_hint;
 use yral_types::delegated_identity::DelegatedIdentityWire;
@@ -59,7 +60,7 @@ pub fn YralAuthProvider() -> impl IntoView {
                 let url = match url_fut.await {
                     Ok(url) => url,
                     Err(e) => {
-                        format!("{origin}/error?err={e}")
+                        format!("{origin}{}", error_path(e))
                     }
                 };
                 target
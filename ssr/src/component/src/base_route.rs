@@ -14,9 +14,13 @@ use utils::mixpanel::mixpanel_events::{
     MixPanelEvent, MixpanelGlobalProps, MixpanelPageViewedProps,
 };
 use utils::notifications::get_fcm_token;
+use utils::send_wrap;
 use utils::sentry::{set_sentry_user, set_sentry_user_canister};
 use yral_metadata_client::MetadataClient;
 
+use crate::block_list::use_block_list;
+use crate::consent_banner::ConsentBanner;
+
 #[derive(Clone)]
 pub struct Notification(pub RwSignal<Option<serde_json::Value>>);
 
@@ -67,6 +71,21 @@ fn CtxProvider(children: Children) -> impl IntoView {
         set_sentry_user(user_principal);
     });
 
+    // registered users get their block list from other devices
+    let block_list = use_block_list();
+    let sync_block_list = Action::new(move |_: &()| {
+        send_wrap(async move {
+            if let Err(e) = block_list.sync().await {
+                log::warn!("failed to sync block list {e}");
+            }
+        })
+    });
+    Effect::new(move |_| {
+        if auth.is_logged_in_with_oauth().get() {
+            sync_block_list.dispatch(());
+        }
+    });
+
     // migrates account connected local storage to cookie
     let (_, set_new_account_connected_store) = use_cookie_with_options::<bool, FromToStringCodec>(
         ACCOUNT_CONNECTED_STORE,
@@ -159,6 +178,7 @@ pub fn BaseRoute() -> impl IntoView {
     view! {
         <CtxProvider>
             <Outlet />
+            <ConsentBanner />
         </CtxProvider>
     }
 }
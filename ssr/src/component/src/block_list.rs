@@ -0,0 +1,183 @@
+use candid::Principal;
+use codee::string::{FromToStringCodec, JsonSerdeCodec};
+use consts::{BLOCKED_USERS_MERGED_STORE, BLOCKED_USERS_STORE};
+use leptos::prelude::*;
+use leptos::server_fn::codec::Json;
+use leptos_use::storage::use_local_storage;
+
+/// Oldest blocks are dropped beyond this
+pub const MAX_BLOCKED_USERS: usize = 1000;
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use auth::server_impl::store::{KVStore, KVStoreImpl};
+    use candid::Principal;
+    use leptos::prelude::ServerFnError;
+
+    fn blocks_key(user: Principal) -> String {
+        format!("blocked-users:{user}")
+    }
+
+    /// `None` if the user never synced their block list
+    pub async fn blocked_users(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<Option<Vec<Principal>>, ServerFnError> {
+        match kv.read(blocks_key(user)).await? {
+            Some(raw) if !raw.is_empty() => Ok(Some(serde_json::from_str(&raw)?)),
+            _ => Ok(None),
+        }
+    }
+
+    pub async fn set_blocked_users(
+        kv: &KVStoreImpl,
+        user: Principal,
+        blocked: &[Principal],
+    ) -> Result<(), ServerFnError> {
+        kv.write(blocks_key(user), serde_json::to_string(blocked)?)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_blocked_users(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<(), ServerFnError> {
+        kv.delete(blocks_key(user)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ssr")]
+async fn caller() -> Result<Principal, ServerFnError> {
+    auth::server_impl::extract_principal_impl()
+        .await?
+        .ok_or_else(|| ServerFnError::new("Login required to block users"))
+}
+
+/// The server copy of the caller's block list
+///
+/// `local` blocks made on this device are merged into it first, persisting the union
+/// of both. Devices only merge once, after that the server copy is the source of truth
+/// so unblocks reach every device
+#[server(endpoint = "sync_blocked_users", input = Json)]
+pub async fn sync_blocked_users(
+    local: Option<Vec<Principal>>,
+) -> Result<Vec<Principal>, ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+
+    let user = caller().await?;
+    let kv: KVStoreImpl = expect_context();
+    let mut blocked = server::blocked_users(&kv, user).await?.unwrap_or_default();
+    let synced = blocked.len();
+    for p in local.unwrap_or_default() {
+        if p != user && !blocked.contains(&p) {
+            blocked.push(p);
+        }
+    }
+    blocked.truncate(MAX_BLOCKED_USERS);
+    if blocked.len() != synced {
+        server::set_blocked_users(&kv, user, &blocked).await?;
+    }
+    Ok(blocked)
+}
+
+#[server(endpoint = "set_user_blocked", input = Json)]
+pub async fn set_user_blocked(
+    target: Principal,
+    blocked: bool,
+) -> Result<Vec<Principal>, ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+
+    let user = caller().await?;
+    if user == target {
+        return Err(ServerFnError::new("You can't block yourself"));
+    }
+    let kv: KVStoreImpl = expect_context();
+    let mut list = server::blocked_users(&kv, user).await?.unwrap_or_default();
+    list.retain(|p| *p != target);
+    if blocked {
+        list.insert(0, target);
+        list.truncate(MAX_BLOCKED_USERS);
+    }
+    server::set_blocked_users(&kv, user, &list).await?;
+    Ok(list)
+}
+
+/// Users blocked on this device, newest first
+///
+/// Kept in local storage so logged out users can block too,
+/// registered users sync it through the server
+#[derive(Clone, Copy)]
+pub struct BlockList {
+    blocked: Signal<Vec<Principal>>,
+    set_blocked: WriteSignal<Vec<Principal>>,
+    /// the local list mirrors the server copy, cleared by blocks that weren't synced
+    merged: Signal<bool>,
+    set_merged: WriteSignal<bool>,
+}
+
+pub fn use_block_list() -> BlockList {
+    let (blocked, set_blocked, _) =
+        use_local_storage::<Vec<Principal>, JsonSerdeCodec>(BLOCKED_USERS_STORE);
+    let (merged, set_merged, _) =
+        use_local_storage::<bool, FromToStringCodec>(BLOCKED_USERS_MERGED_STORE);
+    BlockList {
+        blocked,
+        set_blocked,
+        merged,
+        set_merged,
+    }
+}
+
+impl BlockList {
+    pub fn is_blocked(&self, user: Principal) -> bool {
+        self.blocked.with(|b| b.contains(&user))
+    }
+
+    pub fn is_blocked_untracked(&self, user: Principal) -> bool {
+        self.blocked.with_untracked(|b| b.contains(&user))
+    }
+
+    fn set_local(&self, user: Principal, blocked: bool) {
+        let mut list = self.blocked.get_untracked();
+        list.retain(|p| *p != user);
+        if blocked {
+            list.insert(0, user);
+            list.truncate(MAX_BLOCKED_USERS);
+        }
+        self.set_blocked.set(list);
+    }
+
+    /// Applied locally right away, `sync` also persists it for the user's other devices
+    pub async fn set(
+        self,
+        user: Principal,
+        blocked: bool,
+        sync: bool,
+    ) -> Result<(), ServerFnError> {
+        self.set_local(user, blocked);
+        if !sync {
+            self.set_merged.set(false);
+            return Ok(());
+        }
+        let list = set_user_blocked(user, blocked).await?;
+        if self.merged.get_untracked() {
+            self.set_blocked.set(list);
+            Ok(())
+        } else {
+            self.sync().await
+        }
+    }
+
+    /// Replaces this device's list with the server copy, only meant for registered users
+    ///
+    /// Blocks made on this device before it synced are merged into the server copy first
+    pub async fn sync(self) -> Result<(), ServerFnError> {
+        let local = (!self.merged.get_untracked()).then(|| self.blocked.get_untracked());
+        let list = sync_blocked_users(local).await?;
+        self.set_blocked.set(list);
+        self.set_merged.set(true);
+        Ok(())
+    }
+}
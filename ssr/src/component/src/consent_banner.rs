@@ -0,0 +1,46 @@
+use leptos::prelude::*;
+use utils::consent::{browser_privacy_signal, store, use_consent_cookie, Consent};
+
+/// Asks for analytics and personalization consent until the user picks something
+///
+/// Not shown when the browser already opted out through Do Not Track or
+/// Global Privacy Control
+#[component]
+pub fn ConsentBanner() -> impl IntoView {
+    let (consent, set_consent) = use_consent_cookie();
+    // privacy signals are only known in the browser
+    let show = RwSignal::new(false);
+    Effect::new(move |_| {
+        show.set(consent.get().is_none() && !browser_privacy_signal());
+    });
+
+    view! {
+        <Show when=show>
+            <div class="fixed inset-x-0 bottom-0 z-50 p-4 text-white border-t bg-neutral-900 border-white/10">
+                <div class="flex flex-col gap-3 mx-auto max-w-md">
+                    <span class="font-semibold">We value your privacy</span>
+                    <p class="text-sm text-neutral-400">
+                        "We use analytics to improve the app and your watch history to personalize your feed. "
+                        <a href="/settings/privacy" class="underline text-primary-600">
+                            "Choose what you share"
+                        </a>
+                    </p>
+                    <div class="flex flex-row gap-2">
+                        <button
+                            class="flex-1 py-2 rounded-md border border-white/20"
+                            on:click=move |_| store(set_consent, Consent::ESSENTIAL_ONLY)
+                        >
+                            "Essential only"
+                        </button>
+                        <button
+                            class="flex-1 py-2 rounded-md bg-primary-600"
+                            on:click=move |_| store(set_consent, Consent::ALL)
+                        >
+                            "Accept all"
+                        </button>
+                    </div>
+                </div>
+            </div>
+        </Show>
+    }
+}
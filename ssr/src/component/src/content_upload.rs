@@ -1,82 +1,363 @@
 use super::spinner::Spinner;
 use auth::delegate_short_lived_identity;
+use codee::string::JsonSerdeCodec;
+use consts::CONTENT_IMPORT_QUEUE_STORE;
+use leptos::task::spawn_local;
+use leptos_use::{storage::use_local_storage, use_interval, UseIntervalReturn};
+use serde::{Deserialize, Serialize};
 use state::{
     canisters::{auth_state, unauth_canisters},
-    content_seed_client::ContentSeedClient,
+    content_seed_client::{ContentSeedClient, ImportJobStatus},
 };
 #[derive(Default, Clone, Copy)]
 pub struct AuthorizedUserToSeedContent(pub RwSignal<Option<(bool, Principal)>>);
 use candid::Principal;
 use leptos::prelude::*;
-use yral_canisters_common::Canisters;
+use reqwest::Url;
+use utils::http_client::HttpClientError;
+use wasm_bindgen::JsCast;
+
+/// Import jobs are polled at this interval (ms)
+const IMPORT_POLL_INTERVAL_MS: u64 = 5_000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, thiserror::Error)]
+pub enum ImportError {
+    #[error("Not a valid http(s) link")]
+    InvalidUrl,
+    #[error("Login required to import videos")]
+    Unauthenticated,
+    #[error("Interrupted before the link was submitted")]
+    Interrupted,
+    #[error(transparent)]
+    Service(#[from] HttpClientError),
+    #[error("Import failed: {0}")]
+    ImportFailed(String),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ImportStatus {
+    Pending,
+    Submitting,
+    /// accepted by a service which doesn't report the job status
+    Submitted,
+    InProgress {
+        job_id: String,
+        status: ImportJobStatus,
+    },
+    Done {
+        video_uid: Option<String>,
+    },
+    Failed(ImportError),
+}
+
+impl ImportStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Pending => "Waiting",
+            Self::Submitting => "Submitting",
+            Self::Submitted => "Submitted",
+            Self::InProgress { status, .. } => status.label(),
+            Self::Done { .. } => "Imported",
+            Self::Failed(_) => "Failed",
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self, Self::Submitted | Self::Done { .. })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ImportItem {
+    pub id: String,
+    pub url: String,
+    pub status: ImportStatus,
+}
+
+fn validate_url(url: &str) -> Result<(), ImportError> {
+    match Url::parse(url) {
+        Ok(u) if matches!(u.scheme(), "http" | "https") && u.host().is_some() => Ok(()),
+        _ => Err(ImportError::InvalidUrl),
+    }
+}
+
+/// Links separated by whitespace or commas, as pasted or read from a file
+fn parse_url_list(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|u| !u.is_empty())
+}
+
+#[component]
+fn ImportItemRow(
+    item: ImportItem,
+    on_retry: impl Fn(String) + Copy + Send + Sync + 'static,
+    on_remove: impl Fn(String) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let ImportItem { id, url, status } = item;
+    let failed = matches!(status, ImportStatus::Failed(_));
+    let error = match &status {
+        ImportStatus::Failed(e) => Some(e.to_string()),
+        _ => None,
+    };
+    let removable = failed || status.is_finished() || status == ImportStatus::Pending;
+    let id = StoredValue::new(id);
+
+    view! {
+        <div class="flex flex-row gap-3 justify-between items-center p-2 w-full rounded-md bg-neutral-900">
+            <div class="flex flex-col gap-1 min-w-0">
+                <span class="text-sm text-white truncate">{url}</span>
+                <span
+                    class="text-xs font-semibold"
+                    class=("text-red-500", failed)
+                    class=("text-neutral-400", !failed)
+                >
+                    {status.label()}
+                </span>
+                {error.map(|e| view! { <span class="text-xs text-neutral-400 break-words">{e}</span> })}
+            </div>
+            <div class="flex flex-row gap-2 shrink-0">
+                <Show when=move || failed>
+                    <button
+                        class="py-1 px-3 text-xs text-white rounded-full bg-primary-600"
+                        on:click=move |_| on_retry(id.get_value())
+                    >
+                        Retry
+                    </button>
+                </Show>
+                <Show when=move || removable>
+                    <button
+                        class="py-1 px-3 text-xs text-red-400 rounded-full bg-neutral-800"
+                        on:click=move |_| on_remove(id.get_value())
+                    >
+                        Remove
+                    </button>
+                </Show>
+            </div>
+        </div>
+    }
+}
 
 #[component]
 fn YoutubeUploadInner(#[prop(optional)] url: String) -> impl IntoView {
-    let url_value = RwSignal::new(url);
-    let create_short_lived_delegated_identity = |canisters: &Canisters<true>| {
-        let id = canisters.identity();
-        delegate_short_lived_identity(id)
+    let url_input = RwSignal::new(url);
+    let (queue, set_queue, _) =
+        use_local_storage::<Vec<ImportItem>, JsonSerdeCodec>(CONTENT_IMPORT_QUEUE_STORE);
+
+    let set_status = move |id: &str, status: ImportStatus| {
+        set_queue.update(|q| {
+            if let Some(item) = q.iter_mut().find(|i| i.id == id) {
+                item.status = status;
+            }
+        })
     };
 
     let auth = auth_state();
     let base = unauth_canisters();
-    let on_submit: Action<(), String> = Action::new_unsync(move |_| {
+    let content_seed_client = StoredValue::new_local(expect_context::<ContentSeedClient>());
+    // submits pending items one at a time
+    let process_queue: Action<(), ()> = Action::new_unsync(move |_| {
         let base = base.clone();
+        let content_seed_client = content_seed_client.get_value();
         async move {
-            let cans = match auth.auth_cans(base).await {
-                Ok(c) => c,
-                Err(e) => return e.to_string(),
-            };
-
-            let delegated_identity = create_short_lived_delegated_identity(&cans);
-            let content_seed_client: ContentSeedClient = expect_context();
-            let res = content_seed_client
-                .upload_content(url_value(), delegated_identity)
-                .await;
-            match res {
-                Err(e) => e.to_string(),
-                _ => "Submitted!".to_string(),
+            let cans = auth.auth_cans(base).await;
+            loop {
+                let Some(item) = queue.with_untracked(|q| {
+                    q.iter()
+                        .find(|i| i.status == ImportStatus::Pending)
+                        .cloned()
+                }) else {
+                    break;
+                };
+                let Ok(cans) = &cans else {
+                    set_status(&item.id, ImportStatus::Failed(ImportError::Unauthenticated));
+                    continue;
+                };
+
+                set_status(&item.id, ImportStatus::Submitting);
+                let identity = delegate_short_lived_identity(cans.identity());
+                let status = match content_seed_client.upload_content(item.url, identity).await {
+                    Ok(Some(job_id)) => ImportStatus::InProgress {
+                        job_id,
+                        status: ImportJobStatus::Queued,
+                    },
+                    Ok(None) => ImportStatus::Submitted,
+                    Err(e) => ImportStatus::Failed(e.into()),
+                };
+                set_status(&item.id, status);
             }
         }
     });
-    let submit_res = on_submit.value();
+    let processing = process_queue.pending();
+    let start_processing = move || {
+        if !processing.get_untracked() {
+            process_queue.dispatch(());
+        }
+    };
 
-    view! {
-        <div data-hk="1-0-0-3" class="flex justify-around items-center p-4 h-full">
-            <div data-hk="1-0-0-4" class="flex flex-col justify-center items-center">
-                <div class="flex flex-col gap-6 justify-around h-full">
-                    <div class="flex flex-col justify-center items-center basis-9/12">
-                        <h1 data-hk="1-0-0-5" class="text-2xl text-white md:text-3xl">
-                            VIDEO IMPORTER
-                        </h1>
-                    </div>
-                    <div class="flex flex-col gap-4 justify-around items-center basis-3/12">
-                        <input
-                            type="text"
-                            value=move || url_value.get()
-                            on:input=move |ev| {
-                                let val = event_target_value(&ev);
-                                url_value.set(val);
-                            }
+    // a reload while submitting leaves items which may or may not have been submitted,
+    // items still waiting are picked up again
+    Effect::new(move |_| {
+        set_queue.update(|q| {
+            for item in q.iter_mut() {
+                if item.status == ImportStatus::Submitting {
+                    item.status = ImportStatus::Failed(ImportError::Interrupted);
+                }
+            }
+        });
+        if queue.with_untracked(|q| q.iter().any(|i| i.status == ImportStatus::Pending)) {
+            start_processing();
+        }
+    });
+
+    let enqueue = move |input: &str| {
+        set_queue.update(|q| {
+            for url in parse_url_list(input) {
+                let already_queued = q
+                    .iter()
+                    .any(|i| i.url == url && !matches!(i.status, ImportStatus::Failed(_)));
+                if already_queued {
+                    continue;
+                }
+                let status = match validate_url(url) {
+                    Ok(_) => ImportStatus::Pending,
+                    Err(e) => ImportStatus::Failed(e),
+                };
+                q.push(ImportItem {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    url: url.to_string(),
+                    status,
+                });
+            }
+        });
+        start_processing();
+    };
+
+    let on_retry = move |id: String| {
+        let url = queue.with_untracked(|q| q.iter().find(|i| i.id == id).map(|i| i.url.clone()));
+        let Some(url) = url else {
+            return;
+        };
+        let status = match validate_url(&url) {
+            Ok(_) => ImportStatus::Pending,
+            Err(e) => ImportStatus::Failed(e),
+        };
+        set_status(&id, status);
+        start_processing();
+    };
+    let on_remove = move |id: String| set_queue.update(|q| q.retain(|i| i.id != id));
+    let clear_finished = move |_| set_queue.update(|q| q.retain(|i| !i.status.is_finished()));
+
+    let on_file = move |ev: leptos::ev::Event| {
+        let Some(file) = ev
+            .target()
+            .and_then(|t| t.dyn_into::<leptos::web_sys::HtmlInputElement>().ok())
+            .and_then(|input| input.files()?.get(0))
+        else {
+            return;
+        };
+        spawn_local(async move {
+            match wasm_bindgen_futures::JsFuture::from(file.text()).await {
+                Ok(text) => enqueue(&text.as_string().unwrap_or_default()),
+                Err(e) => log::warn!("failed to read url list {e:?}"),
+            }
+        });
+    };
 
-                            placeholder=" Paste your link here"
-                            class="p-1 md:text-xl"
-                        />
+    let UseIntervalReturn { counter, .. } = use_interval(IMPORT_POLL_INTERVAL_MS);
+    Effect::new(move |_| {
+        counter.track();
+        let jobs = queue.with_untracked(|q| {
+            q.iter()
+                .filter_map(|i| match &i.status {
+                    ImportStatus::InProgress { job_id, .. } => Some((i.id.clone(), job_id.clone())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        });
+        for (id, job_id) in jobs {
+            let content_seed_client = content_seed_client.get_value();
+            spawn_local(async move {
+                let status = match content_seed_client.import_status(&job_id).await {
+                    Ok(ImportJobStatus::Completed { video_uid }) => {
+                        ImportStatus::Done { video_uid }
+                    }
+                    Ok(ImportJobStatus::Failed { error }) => {
+                        ImportStatus::Failed(ImportError::ImportFailed(error))
+                    }
+                    Ok(status) => ImportStatus::InProgress { job_id, status },
+                    // the service returned a job id but doesn't expose its status
+                    Err(HttpClientError::Status {
+                        status: 404 | 405 | 501,
+                        ..
+                    }) => ImportStatus::Submitted,
+                    Err(e) => {
+                        // transient, polled again on the next tick
+                        log::warn!("failed to fetch import status {e}");
+                        return;
+                    }
+                };
+                set_status(&id, status);
+            });
+        }
+    });
+
+    view! {
+        <div class="flex justify-around items-center p-4 h-full">
+            <div class="flex flex-col gap-6 items-center w-full max-w-lg">
+                <h1 class="text-2xl text-white md:text-3xl">VIDEO IMPORTER</h1>
+                <div class="flex flex-col gap-3 w-full">
+                    <textarea
+                        rows=4
+                        prop:value=move || url_input.get()
+                        on:input=move |ev| url_input.set(event_target_value(&ev))
+                        placeholder="Paste your links here, one per line"
+                        class="p-2 w-full text-sm text-black rounded-md md:text-base"
+                    />
+                    <div class="flex flex-row gap-3 justify-between items-center">
+                        <label class="text-sm text-white underline cursor-pointer">
+                            Upload a list
+                            <input type="file" accept=".txt,.csv,text/plain" class="hidden" on:change=on_file />
+                        </label>
                         <button
                             type="submit"
                             class="px-4 text-xl text-white border border-solid md:text-2xl hover:text-black hover:bg-white w-fit"
                             on:click=move |_| {
-                                on_submit.dispatch(());
+                                enqueue(&url_input.get_untracked());
+                                url_input.set(String::new());
                             }
                         >
-
-                            Submit
+                            Add to queue
                         </button>
-                        <p class="text-base text-white md:text-lg">
-                            {move || submit_res.get().unwrap_or_default()}
-                        </p>
                     </div>
                 </div>
+                <Show when=move || queue.with(|q| !q.is_empty())>
+                    <div class="flex flex-col gap-2 w-full">
+                        <div class="flex flex-row justify-between items-center">
+                            <span class="text-sm text-neutral-300">
+                                {move || {
+                                    let (done, total) = queue
+                                        .with(|q| {
+                                            (q.iter().filter(|i| i.status.is_finished()).count(), q.len())
+                                        });
+                                    format!("{done} of {total} imported")
+                                }}
+                            </span>
+                            <button class="text-xs text-neutral-400 underline" on:click=clear_finished>
+                                Clear finished
+                            </button>
+                        </div>
+                        <div class="flex overflow-y-auto flex-col gap-2 max-h-80">
+                            {move || {
+                                queue
+                                    .get()
+                                    .into_iter()
+                                    .map(|item| view! { <ImportItemRow item on_retry on_remove /> })
+                                    .collect_view()
+                            }}
+                        </div>
+                    </div>
+                </Show>
             </div>
         </div>
     }
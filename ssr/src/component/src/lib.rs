@@ -3,10 +3,12 @@ pub mod airdrop_logo;
 pub mod auth_providers;
 pub mod back_btn;
 pub mod base_route;
+pub mod block_list;
 pub mod bullet_loader;
 pub mod buttons;
 pub mod coming_soon;
 pub mod connect;
+pub mod consent_banner;
 pub mod content_upload;
 pub mod dashbox;
 pub mod feed_popup;
@@ -94,6 +94,14 @@ fn yral_nav_items() -> Vec<NavItem> {
                     .starts_with(&format!("/profile/{user_principal}"))
             }),
         },
+        NavItem {
+            render_data: NavItemRenderData::Icon {
+                icon: TrophySymbol,
+                filled_icon: Some(TrophySymbolFilled),
+                href: "/leaderboard".into(),
+            },
+            cur_selected: Signal::derive(move || path.get().starts_with("/leaderboard")),
+        },
         NavItem {
             render_data: NavItemRenderData::Icon {
                 icon: MenuSymbol,
@@ -258,29 +266,3 @@ fn UploadIcon(#[prop(into)] cur_selected: Signal<bool>) -> impl IntoView {
         </a>
     }
 }
-
-// #[component]
-// fn TrophyIcon(idx: usize, cur_selected: Memo<usize>) -> impl IntoView {
-//     view! {
-//         <a href="/leaderboard" class="flex justify-center items-center">
-//             <Show
-//                 when=move || cur_selected() == idx
-//                 fallback=move || {
-//                     view! {
-//                         <div class="py-5">
-//                             <Icon icon=TrophySymbol class="text-2xl text-white md:text-3xl fill-none"/>
-//                         </div>
-//                     }
-//                 }
-//             >
-//
-//                 <div class="py-5 border-t-2 border-t-pink-500">
-//                     <Icon
-//                         icon=TrophySymbolFilled
-//                         class="text-2xl text-white md:text-3xl fill-none aspect-square"
-//                     />
-//                 </div>
-//             </Show>
-//         </a>
-//     }
-// }
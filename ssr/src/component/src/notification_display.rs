@@ -2,12 +2,15 @@ use leptos::web_sys::AnimationEvent;
 use leptos::{html::Div, prelude::*};
 use wasm_bindgen::prelude::{wasm_bindgen, Closure};
 
-use crate::base_route::Notification;
+use crate::{base_route::Notification, block_list::use_block_list};
 
 #[derive(serde::Deserialize, Debug, Clone)]
 struct NotificationPayload {
     title: String,
     body: String,
+    /// user who triggered the notification, if any
+    #[serde(default)]
+    sender_principal: Option<candid::Principal>,
 }
 
 #[wasm_bindgen]
@@ -24,10 +27,19 @@ pub fn NotificationDisplay() -> impl IntoView {
     let is_sliding_out = RwSignal::new(false);
 
     let notification_node_ref = NodeRef::<Div>::new();
+    let block_list = use_block_list();
 
     Effect::new(move |_| {
         if let Some(value) = notification_context.0.get() {
             let payload = serde_json::from_value::<NotificationPayload>(value).ok();
+            if payload
+                .as_ref()
+                .and_then(|p| p.sender_principal)
+                .is_some_and(|sender| block_list.is_blocked_untracked(sender))
+            {
+                notification_context.0.set(None);
+                return;
+            }
             current_notification.set(payload);
             is_visible.set(true);
             is_sliding_out.set(false);
@@ -21,6 +21,7 @@ pub const FALLBACK_PROPIC_BASE: &str = "https://api.dicebear.com/7.x/big-smile/s
 // an example URL is "https://imagedelivery.net/abXI9nS4DYYtyR1yFFtziA/gob.5/public";
 pub const GOBGOB_PROPIC_URL: &str = "https://imagedelivery.net/abXI9nS4DYYtyR1yFFtziA/gob.";
 pub const GOBGOB_TOTAL_COUNT: u32 = 18557;
+pub const CF_IMAGES_DELIVERY_BASE: &str = "https://imagedelivery.net/abXI9nS4DYYtyR1yFFtziA";
 pub const CF_WATERMARK_UID: &str = "b5588fa1516ca33a08ebfef06c8edb33";
 pub const ACCOUNT_CONNECTED_STORE: &str = "account-connected-1";
 pub const DEVICE_ID: &str = "device_id";
@@ -31,11 +32,14 @@ pub static CF_BASE_URL: Lazy<Url> =
 pub const NOTIFICATIONS_ENABLED_STORE: &str = "yral-notifications-enabled";
 pub const NOTIFICATION_MIGRATED_STORE: &str = "notifications-migrated";
 pub const NSFW_TOGGLE_STORE: &str = "nsfw-enabled";
+pub const BLOCKED_USERS_STORE: &str = "blocked-users";
+pub const BLOCKED_USERS_MERGED_STORE: &str = "blocked-users-merged";
+pub const NOT_INTERESTED_STORE: &str = "not-interested-posts";
 pub const REFERRER_COOKIE: &str = "referrer";
+pub const CONSENT_COOKIE: &str = "privacy-consent";
 pub const USER_CANISTER_ID_STORE: &str = "user-canister-id";
 pub const USER_PRINCIPAL_STORE: &str = "user-principal";
 pub const USER_INTERNAL_STORE: &str = "user-internal";
-pub const WALLET_BALANCE_STORE_KEY: &str = "wallet-balance-sats";
 
 pub static OFF_CHAIN_AGENT_URL: Lazy<Url> =
     Lazy::new(|| Url::parse("https://icp-off-chain-agent.fly.dev").unwrap());
@@ -94,6 +98,9 @@ pub mod yral_auth {
 }
 
 pub const UPLOAD_URL: &str = "https://yral-upload-video.go-bazzinga.workers.dev";
+pub const RESUMABLE_UPLOAD_STORE: &str = "resumable-video-upload";
+pub const FAILED_UPLOADS_STORE: &str = "failed-video-uploads";
+pub const CONTENT_IMPORT_QUEUE_STORE: &str = "content-import-queue";
 
 pub const DOLR_AI_ROOT_CANISTER: &str = "67bll-riaaa-aaaaq-aaauq-cai";
 pub const DOLR_AI_LEDGER_CANISTER: &str = "6rdgd-kyaaa-aaaaq-aaavq-cai";
@@ -8,16 +8,51 @@ use axum_extra::extract::cookie::Key;
 use leptos::prelude::*;
 use leptos_axum::AxumRouteListing;
 use state::server::AppState;
+use utils::analytics::outbox::{AnalyticsOutbox, OutboxTargets};
 use yral_canisters_common::Canisters;
 
+/// The Cloudflare client and the images client sharing its credentials
 #[cfg(feature = "cloudflare")]
-fn init_cf() -> gob_cloudflare::CloudflareAuth {
+fn init_cf() -> (
+    gob_cloudflare::CloudflareAuth,
+    state::cf_images::CfImagesClient,
+) {
     use gob_cloudflare::{CloudflareAuth, Credentials};
     let creds = Credentials {
         token: env::var("CF_TOKEN").expect("`CF_TOKEN` is required!"),
         account_id: env::var("CF_ACCOUNT_ID").expect("`CF_ACCOUNT_ID` is required!"),
     };
-    CloudflareAuth::new(creds)
+    let images = state::cf_images::CfImagesClient::new(&creds);
+    (CloudflareAuth::new(creds), images)
+}
+
+/// Clients the analytics outbox delivers through
+pub fn analytics_outbox_targets(app_state: &AppState) -> OutboxTargets {
+    OutboxTargets {
+        #[cfg(feature = "ga4")]
+        warehouse: Some(app_state.grpc_offchain_channel.clone()),
+        #[cfg(feature = "qstash")]
+        qstash: env::var("ANALYTICS_SERVER_TOKEN")
+            .ok()
+            .map(|token| (app_state.qstash.clone(), token)),
+    }
+}
+
+/// `ADMIN_PRINCIPALS` is a comma separated list, admin pages are disabled if it's unset
+fn init_admin_allowlist() -> state::server::AdminAllowlist {
+    use candid::Principal;
+    use state::server::AdminAllowlist;
+
+    let Ok(raw) = env::var("ADMIN_PRINCIPALS") else {
+        return AdminAllowlist::default();
+    };
+    let principals = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| Principal::from_text(p).expect("Invalid principal in `ADMIN_PRINCIPALS`"))
+        .collect();
+    AdminAllowlist(std::sync::Arc::new(principals))
 }
 
 fn init_cookie_key() -> Key {
@@ -211,6 +246,8 @@ impl AppStateBuilder {
             self.containers.start_metadata().await;
         }
 
+        #[cfg(feature = "cloudflare")]
+        let (cloudflare, cf_images) = init_cf();
         let app_state = AppState {
             leptos_options: self.leptos_options,
             canisters: Canisters::default(),
@@ -218,7 +255,10 @@ impl AppStateBuilder {
             #[cfg(feature = "backend-admin")]
             admin_canisters: init_admin_canisters(),
             #[cfg(feature = "cloudflare")]
-            cloudflare: init_cf(),
+            cloudflare,
+            #[cfg(feature = "cloudflare")]
+            cf_images,
+            analytics_outbox: AnalyticsOutbox::new(kv.clone()),
             kv,
             cookie_key: init_cookie_key(),
             #[cfg(feature = "oauth-ssr")]
@@ -247,6 +287,8 @@ impl AppStateBuilder {
                     .await
                     .expect("connect to neon postgres")
             },
+            leaderboard_cache: Default::default(),
+            admin_allowlist: init_admin_allowlist(),
         };
 
         AppStateRes {
@@ -15,7 +15,10 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use utils::host::is_host_or_origin_from_preview_domain;
 
 use hot_or_not_web_leptos_ssr::app::shell;
-use hot_or_not_web_leptos_ssr::{app::App, init::AppStateBuilder};
+use hot_or_not_web_leptos_ssr::{
+    app::App,
+    init::{analytics_outbox_targets, AppStateBuilder},
+};
 use http::{header, HeaderName, Method};
 use leptos::logging::log;
 use leptos::prelude::*;
@@ -37,8 +40,12 @@ pub async fn server_fn_handler(
             #[cfg(feature = "backend-admin")]
             provide_context(app_state.admin_canisters.clone());
             #[cfg(feature = "cloudflare")]
-            provide_context(app_state.cloudflare.clone());
+            {
+                provide_context(app_state.cloudflare.clone());
+                provide_context(app_state.cf_images.clone());
+            }
             provide_context(app_state.kv.clone());
+            provide_context(app_state.analytics_outbox.clone());
             provide_context(app_state.cookie_key.clone());
 
             #[cfg(feature = "oauth-ssr")]
@@ -60,6 +67,8 @@ pub async fn server_fn_handler(
             }
             #[cfg(feature = "dolr-airdrop")]
             provide_context(app_state.dolr_airdrop_db.clone());
+            provide_context(app_state.leaderboard_cache.clone());
+            provide_context(app_state.admin_allowlist.clone());
         },
         request,
     )
@@ -76,8 +85,12 @@ pub async fn leptos_routes_handler(state: State<AppState>, req: Request<AxumBody
             #[cfg(feature = "backend-admin")]
             provide_context(app_state.admin_canisters.clone());
             #[cfg(feature = "cloudflare")]
-            provide_context(app_state.cloudflare.clone());
+            {
+                provide_context(app_state.cloudflare.clone());
+                provide_context(app_state.cf_images.clone());
+            }
             provide_context(app_state.kv.clone());
+            provide_context(app_state.analytics_outbox.clone());
             provide_context(app_state.cookie_key.clone());
             #[cfg(feature = "oauth-ssr")]
             provide_context(app_state.yral_oauth_client.clone());
@@ -95,6 +108,8 @@ pub async fn leptos_routes_handler(state: State<AppState>, req: Request<AxumBody
             }
             #[cfg(feature = "dolr-airdrop")]
             provide_context(app_state.dolr_airdrop_db.clone());
+            provide_context(app_state.leaderboard_cache.clone());
+            provide_context(app_state.admin_allowlist.clone());
         },
         move || shell(app_state.leptos_options.clone()),
     );
@@ -117,6 +132,13 @@ async fn main_impl() {
     let res = AppStateBuilder::new(leptos_options, routes.clone())
         .build()
         .await;
+    tokio::spawn(page::upload::run_post_scheduler(res.app_state.kv.clone()));
+    tokio::spawn(
+        res.app_state
+            .analytics_outbox
+            .clone()
+            .run(analytics_outbox_targets(&res.app_state)),
+    );
     let terminate = {
         use tokio::signal;
 
@@ -0,0 +1,233 @@
+use candid::Principal;
+use component::{back_btn::BackButton, infinite_scroller::InfiniteScroller, title::TitleText};
+use leptos::html;
+use leptos::prelude::*;
+use leptos_meta::*;
+use serde::{Deserialize, Serialize};
+use state::app_state::AppState;
+use utils::time::time_ago;
+use yral_canisters_common::{
+    cursored_data::{CursoredDataProvider, KeyedData, PageEntry},
+    utils::time::current_epoch,
+};
+
+use super::AdminGate;
+
+/// Only the most recent entries are kept
+pub const MAX_AUDIT_ENTRIES: usize = 5000;
+const AUDIT_CHUNK_SZ: usize = 20;
+
+/// A decision taken by an admin
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AuditEntry {
+    pub id: String,
+    pub admin: Principal,
+    /// e.g. `moderation:ban`
+    pub action: String,
+    /// what the action was applied to, e.g. a post as `canister_id/post_id`
+    pub target: String,
+    pub note: Option<String>,
+    /// epoch secs
+    pub at: u64,
+    /// the action errored after it was recorded
+    #[serde(default)]
+    pub failed: bool,
+}
+
+impl KeyedData for AuditEntry {
+    type Key = String;
+
+    fn key(&self) -> Self::Key {
+        self.id.clone()
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use std::future::Future;
+
+    use auth::server_impl::store::{KVStore, KVStoreImpl};
+    use candid::Principal;
+    use leptos::prelude::ServerFnError;
+    use yral_canisters_common::utils::time::current_epoch;
+
+    use super::*;
+
+    /// Entry ids scored by time, entries themselves are kept under [entry_key]
+    const AUDIT_INDEX_KEY: &str = "admin-audit-entries";
+
+    fn entry_key(id: &str) -> String {
+        format!("admin-audit-entry:{id}")
+    }
+
+    async fn write_entry(kv: &KVStoreImpl, entry: &AuditEntry) -> Result<(), ServerFnError> {
+        kv.write(entry_key(&entry.id), serde_json::to_string(entry)?)
+            .await?;
+        Ok(())
+    }
+
+    /// Newest first, with the total number of entries
+    pub async fn audit_log(
+        kv: &KVStoreImpl,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<AuditEntry>, usize), ServerFnError> {
+        let ids = kv
+            .index_range(AUDIT_INDEX_KEY.to_string(), offset, limit, true)
+            .await?;
+        let mut entries = vec![];
+        for id in ids {
+            match kv.read(entry_key(&id)).await? {
+                Some(raw) if !raw.is_empty() => entries.push(serde_json::from_str(&raw)?),
+                _ => (),
+            }
+        }
+        let total = kv.index_len(AUDIT_INDEX_KEY.to_string()).await?;
+        Ok((entries, total))
+    }
+
+    pub async fn record(
+        kv: &KVStoreImpl,
+        admin: Principal,
+        action: impl Into<String>,
+        target: impl Into<String>,
+        note: Option<String>,
+    ) -> Result<AuditEntry, ServerFnError> {
+        let entry = AuditEntry {
+            id: uuid::Uuid::new_v4().simple().to_string(),
+            admin,
+            action: action.into(),
+            target: target.into(),
+            note,
+            at: current_epoch().as_secs(),
+            failed: false,
+        };
+        log::info!(
+            "admin {} did {} on {}",
+            entry.admin,
+            entry.action,
+            entry.target
+        );
+
+        write_entry(kv, &entry).await?;
+        kv.index_add(
+            AUDIT_INDEX_KEY.to_string(),
+            entry.id.clone(),
+            entry.at as f64,
+        )
+        .await?;
+        let dropped = kv
+            .index_trim(AUDIT_INDEX_KEY.to_string(), MAX_AUDIT_ENTRIES)
+            .await?;
+        for id in dropped {
+            kv.delete(entry_key(&id)).await?;
+        }
+        Ok(entry)
+    }
+
+    /// Records the action before running `run`, so nothing happens unaudited
+    /// if the log can't be written, the entry is marked failed if `run` errors
+    pub async fn audited<T>(
+        kv: &KVStoreImpl,
+        admin: Principal,
+        action: impl Into<String>,
+        target: impl Into<String>,
+        note: Option<String>,
+        run: impl Future<Output = Result<T, ServerFnError>>,
+    ) -> Result<T, ServerFnError> {
+        let mut entry = record(kv, admin, action, target, note).await?;
+        let res = run.await;
+        if res.is_err() {
+            entry.failed = true;
+            if let Err(e) = write_entry(kv, &entry).await {
+                log::warn!("failed to mark audit entry {} as failed {e}", entry.id);
+            }
+        }
+        res
+    }
+}
+
+#[server(endpoint = "get_audit_log", input = server_fn::codec::Json)]
+pub async fn get_audit_log(
+    start: usize,
+    end: usize,
+) -> Result<(Vec<AuditEntry>, bool), ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+
+    super::server::require_admin().await?;
+    let kv: KVStoreImpl = expect_context();
+    let (entries, total) = server::audit_log(&kv, start, end.saturating_sub(start)).await?;
+    Ok((entries, end >= total))
+}
+
+#[derive(Clone, Copy)]
+struct AuditLogProvider;
+
+impl CursoredDataProvider for AuditLogProvider {
+    type Data = AuditEntry;
+    type Error = ServerFnError;
+
+    async fn get_by_cursor_inner(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Result<PageEntry<AuditEntry>, ServerFnError> {
+        let (data, end) = get_audit_log(start, end).await?;
+        Ok(PageEntry { data, end })
+    }
+}
+
+#[component]
+fn AuditRow(entry: AuditEntry, _ref: NodeRef<html::Div>) -> impl IntoView {
+    view! {
+        <div node_ref=_ref class="flex flex-col gap-1 py-2 w-full border-b border-neutral-800">
+            <div class="flex flex-row justify-between text-sm">
+                <span class="font-semibold">
+                    {entry.action}
+                    {entry.failed.then(|| view! { <span class="ml-2 text-red-500">failed</span> })}
+                </span>
+                <span class="text-xs text-neutral-400">
+                    {time_ago(entry.at, current_epoch().as_secs())}
+                </span>
+            </div>
+            <span class="text-xs break-all">{entry.target}</span>
+            <span class="text-xs truncate text-neutral-400">{format!("by {}", entry.admin)}</span>
+            {entry.note.map(|note| view! { <span class="text-xs italic text-neutral-300">{note}</span> })}
+        </div>
+    }
+}
+
+#[component]
+pub fn AuditLog() -> impl IntoView {
+    let app_state = use_context::<AppState>();
+    let page_title = app_state.unwrap().name.to_owned() + " - Audit log";
+
+    view! {
+        <Title text=page_title />
+        <div class="flex flex-col items-center pt-2 pb-12 min-h-screen text-white bg-black">
+            <TitleText justify_center=false>
+                <div class="flex flex-row justify-between">
+                    <BackButton fallback="/admin".to_string() />
+                    <span class="text-2xl font-bold">Audit log</span>
+                    <div></div>
+                </div>
+            </TitleText>
+            <AdminGate>
+                <div class="flex flex-col px-4 pt-4 w-full max-w-2xl">
+                    <InfiniteScroller
+                        provider=AuditLogProvider
+                        fetch_count=AUDIT_CHUNK_SZ
+                        children=|entry, _ref| {
+                            view! { <AuditRow entry _ref=_ref.unwrap_or_default() /> }
+                        }
+                        empty_content=|| {
+                            view! {
+                                <span class="py-12 text-center text-neutral-400">No admin actions yet</span>
+                            }
+                        }
+                    />
+                </div>
+            </AdminGate>
+        </div>
+    }
+}
@@ -0,0 +1,469 @@
+use candid::Principal;
+use component::{
+    back_btn::BackButton, infinite_scroller::InfiniteScroller, spinner::SpinnerCircle,
+    title::TitleText,
+};
+use leptos::html;
+use leptos::prelude::*;
+use leptos_meta::*;
+use serde::{Deserialize, Serialize};
+use state::app_state::AppState;
+use utils::{analytics::OutboxDepth, bg_url, send_wrap, types::PostId};
+use yral_canisters_common::cursored_data::{CursoredDataProvider, KeyedData, PageEntry};
+
+use super::AdminGate;
+use crate::wallet::airdrop::AirdropStatus;
+
+const POSTS_CHUNK_SZ: usize = 10;
+
+/// Everything the console shows about a user
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AdminUserInfo {
+    pub principal: Principal,
+    pub canister: Principal,
+    pub username_or_principal: String,
+    pub display_name: String,
+    pub profile_pic: String,
+    /// `None` if the balance couldn't be loaded
+    pub sats_balance: Option<String>,
+    pub cents_balance: Option<String>,
+    /// within the 24h window after a sats airdrop claim
+    pub sats_airdrop_claimed: Option<bool>,
+    pub dolr_airdrop: Option<AirdropStatus>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AdminPost {
+    pub canister_id: Principal,
+    pub post_id: u64,
+    pub video_uid: String,
+    pub description: String,
+    pub views: u64,
+    pub likes: u64,
+    pub is_nsfw: bool,
+}
+
+impl KeyedData for AdminPost {
+    type Key = PostId;
+
+    fn key(&self) -> Self::Key {
+        (self.canister_id, self.post_id)
+    }
+}
+
+#[cfg(feature = "ssr")]
+mod server {
+    use candid::Principal;
+    use leptos::prelude::ServerFnError;
+    use yral_canisters_common::{utils::profile::ProfileDetails, Canisters};
+
+    /// `query` may be either a user principal or a user canister,
+    /// returns `(user principal, user canister)`
+    pub async fn resolve_user(
+        cans: &Canisters<false>,
+        query: Principal,
+    ) -> Result<(Principal, Principal), ServerFnError> {
+        if let Some(canister) = cans
+            .get_individual_canister_by_user_principal(query)
+            .await?
+        {
+            return Ok((query, canister));
+        }
+        let user = cans.individual_user(query).await;
+        let details = user
+            .get_profile_details()
+            .await
+            .map_err(|_| ServerFnError::new("No user or user canister with this principal"))?;
+        Ok((ProfileDetails::from(details).principal, query))
+    }
+}
+
+/// Loads a user by principal or canister id, the lookup is audit logged
+#[server(endpoint = "admin_lookup_user", input = server_fn::codec::Json)]
+pub async fn admin_lookup_user(query: String) -> Result<AdminUserInfo, ServerFnError> {
+    use super::audit::server::record;
+    use crate::{
+        profile::follow::server::follow_profile,
+        wallet::airdrop::{dolr_airdrop::is_user_eligible_for_dolr_airdrop, is_airdrop_claimed},
+    };
+    use auth::server_impl::store::KVStoreImpl;
+    use state::canisters::unauth_canisters;
+    use yral_canisters_common::utils::token::{
+        balance::TokenBalance, load_cents_balance, load_sats_balance,
+    };
+
+    let admin = super::server::require_admin().await?;
+    let query = Principal::from_text(query.trim())
+        .map_err(|_| ServerFnError::new("Not a valid principal"))?;
+    let cans = unauth_canisters();
+    let kv: KVStoreImpl = expect_context();
+
+    let (principal, canister) = server::resolve_user(&cans, query).await?;
+    let profile = follow_profile(&cans, &kv, principal).await?;
+
+    let sats_balance = load_sats_balance(principal)
+        .await
+        .inspect_err(|e| log::warn!("admin: failed to load sats of {principal}: {e}"))
+        .ok()
+        .map(|info| TokenBalance::new(info.balance.into(), 0).humanize_float_truncate_to_dp(0));
+    let cents_balance = load_cents_balance(canister)
+        .await
+        .inspect_err(|e| log::warn!("admin: failed to load cents of {principal}: {e}"))
+        .ok()
+        .map(|info| TokenBalance::new(info.balance, 6).humanize_float_truncate_to_dp(2));
+    let sats_airdrop_claimed = is_airdrop_claimed(principal)
+        .await
+        .inspect_err(|e| log::warn!("admin: failed to load sats airdrop of {principal}: {e}"))
+        .ok();
+    let dolr_airdrop = is_user_eligible_for_dolr_airdrop(canister, principal)
+        .await
+        .inspect_err(|e| log::warn!("admin: failed to load dolr airdrop of {principal}: {e}"))
+        .ok();
+
+    record(&kv, admin, "console:lookup", principal.to_text(), None).await?;
+
+    Ok(AdminUserInfo {
+        principal,
+        canister,
+        username_or_principal: profile.username_or_principal,
+        display_name: profile.display_name,
+        profile_pic: profile.profile_pic,
+        sats_balance,
+        cents_balance,
+        sats_airdrop_claimed,
+        dolr_airdrop,
+    })
+}
+
+#[server(endpoint = "admin_user_posts", input = server_fn::codec::Json)]
+pub async fn admin_user_posts(
+    user_canister: Principal,
+    start: usize,
+    end: usize,
+) -> Result<(Vec<AdminPost>, bool), ServerFnError> {
+    use crate::profile::profile_iter::{FixedFetchCursor, ProfVideoStream, ProfileVideoStream};
+    use state::canisters::unauth_canisters;
+
+    type AdminVidStream = ProfileVideoStream<{ POSTS_CHUNK_SZ as u64 }>;
+
+    super::server::require_admin().await?;
+    let cans = unauth_canisters();
+    let cursor = FixedFetchCursor::<{ POSTS_CHUNK_SZ as u64 }> {
+        start: start as u64,
+        limit: end.saturating_sub(start) as u64,
+    };
+    let res = AdminVidStream::fetch_next_posts(cursor, &cans, user_canister).await?;
+    let posts = res
+        .posts
+        .into_iter()
+        .map(|p| AdminPost {
+            canister_id: p.canister_id,
+            post_id: p.post_id,
+            video_uid: p.uid,
+            description: p.description,
+            views: p.views,
+            likes: p.likes,
+            is_nsfw: p.is_nsfw,
+        })
+        .collect();
+    Ok((posts, res.end))
+}
+
+/// Bans a post in its creator's canister with `PostStatus::BannedDueToUserReporting`
+#[server(endpoint = "admin_ban_post", input = server_fn::codec::Json)]
+pub async fn admin_ban_post(
+    canister_id: Principal,
+    post_id: u64,
+    note: Option<String>,
+) -> Result<(), ServerFnError> {
+    use super::{audit::server::audited, moderation::server::ban_post};
+    use auth::server_impl::store::KVStoreImpl;
+
+    let admin = super::server::require_admin().await?;
+    let kv: KVStoreImpl = expect_context();
+    audited(
+        &kv,
+        admin,
+        "console:ban-post",
+        format!("{canister_id}/{post_id}"),
+        note.filter(|n| !n.trim().is_empty()),
+        ban_post((canister_id, post_id)),
+    )
+    .await
+}
+
+/// Depth of the analytics outbox shared by all instances
+#[server(endpoint = "admin_analytics_outbox", input = server_fn::codec::Json)]
+pub async fn admin_analytics_outbox() -> Result<OutboxDepth, ServerFnError> {
+    use utils::analytics::outbox::AnalyticsOutbox;
+
+    super::server::require_admin().await?;
+    let outbox: AnalyticsOutbox = expect_context();
+    Ok(outbox.depth().await)
+}
+
+#[derive(Clone, Copy)]
+struct AdminPostsProvider {
+    user_canister: Principal,
+}
+
+impl CursoredDataProvider for AdminPostsProvider {
+    type Data = AdminPost;
+    type Error = ServerFnError;
+
+    async fn get_by_cursor_inner(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Result<PageEntry<AdminPost>, ServerFnError> {
+        let (data, end) = admin_user_posts(self.user_canister, start, end).await?;
+        Ok(PageEntry { data, end })
+    }
+}
+
+fn airdrop_text(status: Option<AirdropStatus>) -> String {
+    match status {
+        Some(AirdropStatus::Available) => "available".to_string(),
+        Some(AirdropStatus::Claimed) => "claimed".to_string(),
+        Some(AirdropStatus::WaitFor(d)) => format!("claimed, next in {}h", d.as_secs() / 3600),
+        None => "unknown".to_string(),
+    }
+}
+
+#[component]
+fn InfoRow(label: &'static str, value: String) -> impl IntoView {
+    view! {
+        <div class="flex flex-row gap-4 justify-between text-sm">
+            <span class="text-neutral-400">{label}</span>
+            <span class="text-right break-all">{value}</span>
+        </div>
+    }
+}
+
+#[component]
+fn AdminPostRow(post: AdminPost, _ref: NodeRef<html::Div>) -> impl IntoView {
+    let (canister_id, post_id) = post.key();
+    let note = RwSignal::new(String::new());
+    let banned = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    let ban = Action::new(move |&()| {
+        let note = Some(note.get_untracked());
+        send_wrap(async move {
+            match admin_ban_post(canister_id, post_id, note).await {
+                Ok(()) => banned.set(true),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        })
+    });
+
+    view! {
+        <div node_ref=_ref class="flex flex-row gap-4 p-3 w-full rounded-md border border-neutral-800">
+            <a href=format!("/hot-or-not/{canister_id}/{post_id}") target="_blank" class="shrink-0">
+                <img class="object-cover w-20 rounded-md aspect-9/16" src=bg_url(post.video_uid.clone()) />
+            </a>
+            <div class="flex flex-col flex-1 gap-2 min-w-0">
+                <span class="text-sm line-clamp-2">{post.description.clone()}</span>
+                <span class="text-xs text-neutral-400">
+                    {format!(
+                        "#{post_id} · {} views · {} likes{}",
+                        post.views,
+                        post.likes,
+                        if post.is_nsfw { " · NSFW" } else { "" },
+                    )}
+                </span>
+                <Show
+                    when=move || !banned()
+                    fallback=|| view! { <span class="text-sm text-neutral-400">Banned</span> }
+                >
+                    <input
+                        class="py-1 px-2 text-sm text-white rounded-md bg-neutral-800"
+                        placeholder="Note for the audit log (optional)"
+                        prop:value=note
+                        on:input=move |ev| note.set(event_target_value(&ev))
+                    />
+                    <button
+                        class="self-start py-1 px-3 text-sm bg-red-700 rounded-md disabled:opacity-50"
+                        disabled=move || ban.pending().get()
+                        on:click=move |_| {
+                            ban.dispatch(());
+                        }
+                    >
+                        Ban
+                    </button>
+                </Show>
+                <Show when=move || error.with(|e| e.is_some())>
+                    <span class="text-xs text-red-500">{move || error.get().unwrap_or_default()}</span>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn AdminUserCard(user: AdminUserInfo) -> impl IntoView {
+    let user_canister = user.canister;
+
+    view! {
+        <div class="flex flex-col gap-3 p-4 w-full rounded-md border border-neutral-800">
+            <div class="flex flex-row gap-3 items-center">
+                <img class="object-cover w-14 h-14 rounded-full" src=user.profile_pic.clone() />
+                <div class="flex flex-col min-w-0">
+                    <a
+                        class="font-semibold truncate"
+                        href=format!("/profile/{}/posts", user.principal)
+                        target="_blank"
+                    >
+                        {user.display_name.clone()}
+                    </a>
+                    <span class="text-xs truncate text-neutral-400">
+                        {user.username_or_principal.clone()}
+                    </span>
+                </div>
+            </div>
+            <InfoRow label="Principal" value=user.principal.to_text() />
+            <InfoRow label="Canister" value=user.canister.to_text() />
+            <InfoRow
+                label="Sats"
+                value=user.sats_balance.clone().unwrap_or_else(|| "unknown".to_string())
+            />
+            <InfoRow
+                label="Cents"
+                value=user.cents_balance.clone().unwrap_or_else(|| "unknown".to_string())
+            />
+            <InfoRow
+                label="Sats airdrop"
+                value=match user.sats_airdrop_claimed {
+                    Some(true) => "claimed in the last 24h".to_string(),
+                    Some(false) => "not claimed in the last 24h".to_string(),
+                    None => "unknown".to_string(),
+                }
+            />
+            <InfoRow label="DOLR airdrop" value=airdrop_text(user.dolr_airdrop) />
+        </div>
+        <span class="pt-2 w-full text-lg font-semibold">Posts</span>
+        <InfiniteScroller
+            provider=AdminPostsProvider { user_canister }
+            fetch_count=POSTS_CHUNK_SZ
+            children=|post, _ref| view! { <AdminPostRow post _ref=_ref.unwrap_or_default() /> }
+            empty_content=|| {
+                view! { <span class="py-6 text-center text-neutral-400">No posts</span> }
+            }
+        />
+    }
+}
+
+#[component]
+fn AnalyticsOutboxStatus() -> impl IntoView {
+    let depth = Resource::new(|| (), |_| send_wrap(admin_analytics_outbox()));
+
+    view! {
+        <Suspense>
+            {move || Suspend::new(async move {
+                match depth.await {
+                    Ok(depth) => {
+                        view! {
+                            <div class="flex flex-col gap-1 p-4 w-full rounded-md border border-neutral-800">
+                                <span class="font-semibold">Analytics outbox</span>
+                                <InfoRow label="Pending" value=depth.pending.to_string() />
+                                <InfoRow label="Retrying" value=depth.retrying.to_string() />
+                                <InfoRow label="Dead letters" value=depth.dead_letters.to_string() />
+                                <InfoRow
+                                    label="Oldest pending"
+                                    value=depth
+                                        .oldest_pending_secs
+                                        .map(|secs| format!("{secs}s"))
+                                        .unwrap_or_else(|| "-".to_string())
+                                />
+                            </div>
+                        }
+                            .into_any()
+                    }
+                    Err(e) => {
+                        view! { <span class="text-sm text-red-500">{e.to_string()}</span> }
+                            .into_any()
+                    }
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn UserLookup() -> impl IntoView {
+    let query = RwSignal::new(String::new());
+    let lookup = Action::new(move |query: &String| {
+        let query = query.clone();
+        send_wrap(async move { admin_lookup_user(query).await })
+    });
+    let result = lookup.value();
+
+    view! {
+        <form
+            class="flex flex-row gap-2 w-full"
+            on:submit=move |ev| {
+                ev.prevent_default();
+                lookup.dispatch(query.get_untracked());
+            }
+        >
+            <input
+                class="flex-1 py-2 px-3 text-sm text-white rounded-md bg-neutral-800"
+                placeholder="User principal or canister id"
+                prop:value=query
+                on:input=move |ev| query.set(event_target_value(&ev))
+            />
+            <button
+                type="submit"
+                class="py-2 px-4 text-sm rounded-md bg-primary-600 disabled:opacity-50"
+                disabled=move || lookup.pending().get()
+            >
+                Look up
+            </button>
+        </form>
+        <Show when=move || lookup.pending().get()>
+            <div class="flex justify-center py-6">
+                <SpinnerCircle />
+            </div>
+        </Show>
+        {move || {
+            result
+                .get()
+                .map(|res| match res {
+                    Ok(user) => view! { <AdminUserCard user /> }.into_any(),
+                    Err(e) => {
+                        view! { <span class="text-sm text-red-500">{e.to_string()}</span> }
+                            .into_any()
+                    }
+                })
+        }}
+    }
+}
+
+#[component]
+pub fn AdminConsole() -> impl IntoView {
+    let app_state = use_context::<AppState>();
+    let page_title = app_state.unwrap().name.to_owned() + " - Admin";
+
+    view! {
+        <Title text=page_title />
+        <div class="flex flex-col items-center pt-2 pb-12 min-h-screen text-white bg-black">
+            <TitleText justify_center=false>
+                <div class="flex flex-row justify-between">
+                    <BackButton fallback="/".to_string() />
+                    <span class="text-2xl font-bold">Admin</span>
+                    <div></div>
+                </div>
+            </TitleText>
+            <AdminGate>
+                <div class="flex flex-col gap-3 px-4 pt-4 w-full max-w-2xl">
+                    <div class="flex flex-row gap-4 text-sm underline text-primary-500">
+                        <a href="/admin/moderation">Moderation queue</a>
+                        <a href="/admin/audit">Audit log</a>
+                    </div>
+                    <AnalyticsOutboxStatus />
+                    <UserLookup />
+                </div>
+            </AdminGate>
+        </div>
+    }
+}
@@ -0,0 +1,65 @@
+pub mod audit;
+pub mod console;
+pub mod moderation;
+
+use component::spinner::FullScreenSpinner;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use utils::{app_error::AppErrorCode, send_wrap};
+
+use crate::err::ErrorView;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+pub enum AdminError {
+    #[error("Not authorized to use admin pages")]
+    Unauthorized,
+    #[error("Backend admin is not available")]
+    BackendAdminUnavailable,
+}
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use auth::server_impl::extract_principal_impl;
+    use candid::Principal;
+    use leptos::prelude::*;
+    use state::server::AdminAllowlist;
+
+    use super::AdminError;
+
+    /// The calling admin, errors for everyone else
+    pub async fn require_admin() -> Result<Principal, ServerFnError> {
+        let allowlist: AdminAllowlist = use_context().unwrap_or_default();
+        match extract_principal_impl().await? {
+            Some(caller) if allowlist.contains(caller) => Ok(caller),
+            caller => {
+                log::warn!("admin access denied for {caller:?}");
+                Err(AdminError::Unauthorized.into())
+            }
+        }
+    }
+}
+
+#[server(endpoint = "is_admin", input = server_fn::codec::Json)]
+pub async fn is_admin() -> Result<bool, ServerFnError> {
+    Ok(server::require_admin().await.is_ok())
+}
+
+/// Renders `children` only for principals in the admin allowlist
+#[component]
+pub fn AdminGate(children: ChildrenFn) -> impl IntoView {
+    let admin = Resource::new(|| (), |_| send_wrap(is_admin()));
+    let children = StoredValue::new(children);
+
+    view! {
+        <Suspense fallback=FullScreenSpinner>
+            {move || Suspend::new(async move {
+                if admin.await.unwrap_or_default() {
+                    children.with_value(|c| c()).into_any()
+                } else {
+                    view! { <ErrorView error=AppErrorCode::Unauthorized.message().to_string() /> }
+                        .into_any()
+                }
+            })}
+        </Suspense>
+    }
+}
@@ -0,0 +1,1141 @@
+use std::collections::HashSet;
+
+use candid::Principal;
+use component::{back_btn::BackButton, infinite_scroller::InfiniteScroller, title::TitleText};
+use leptos::html;
+use leptos::prelude::*;
+use leptos_meta::*;
+use serde::{Deserialize, Serialize};
+use state::app_state::AppState;
+use utils::{bg_url, send_wrap, time::time_ago, types::PostId};
+use yral_canisters_common::{
+    cursored_data::{CursoredDataProvider, KeyedData, PageEntry},
+    utils::{posts::PostDetails, time::current_epoch},
+};
+
+use super::AdminGate;
+
+const QUEUE_CHUNK_SZ: usize = 10;
+/// Most posts or creators checked by a single [get_hidden_content] call
+pub const MAX_HIDDEN_QUERY: usize = 50;
+
+/// A report of a post or a user profile
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Report {
+    pub reporter: Principal,
+    pub reason: String,
+    /// epoch secs
+    pub reported_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModerationStatus {
+    Open,
+    Dismissed,
+    Hidden,
+    Banned,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModerationAction {
+    /// the reports are unfounded
+    Dismiss,
+    /// removed from our feeds, the post is left untouched on chain
+    Hide,
+    /// the post is banned in the creator's canister, posts only
+    Ban,
+}
+
+impl ModerationAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dismiss => "dismiss",
+            Self::Hide => "hide",
+            Self::Ban => "ban",
+        }
+    }
+
+    fn status(&self) -> ModerationStatus {
+        match self {
+            Self::Dismiss => ModerationStatus::Dismissed,
+            Self::Hide => ModerationStatus::Hidden,
+            Self::Ban => ModerationStatus::Banned,
+        }
+    }
+}
+
+/// A reported post, its reports are kept in their own index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReportedPost {
+    pub canister_id: Principal,
+    pub post_id: u64,
+    pub video_uid: String,
+    pub publisher: Principal,
+    pub status: ModerationStatus,
+}
+
+/// Reports of a post or a user profile, counted per reporter
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ReportSummary {
+    pub count: usize,
+    /// Number of reports per reason, most reported first
+    pub reasons: Vec<(String, usize)>,
+    /// epoch secs
+    pub last_reported_at: u64,
+}
+
+#[cfg(feature = "ssr")]
+fn is_valid_reason(reason: &str) -> bool {
+    use utils::report::ReportOption;
+
+    ReportOption::all()
+        .iter()
+        .any(|o| o.as_str().to_string() == reason)
+}
+
+impl ReportedPost {
+    pub fn key(&self) -> PostId {
+        (self.canister_id, self.post_id)
+    }
+}
+
+/// A post in the moderation queue
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct QueuedPost {
+    pub post: ReportedPost,
+    pub reports: ReportSummary,
+}
+
+impl KeyedData for QueuedPost {
+    type Key = PostId;
+
+    fn key(&self) -> Self::Key {
+        self.post.key()
+    }
+}
+
+/// A reported user profile, its reports are kept in their own index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReportedUser {
+    pub user: Principal,
+    pub status: ModerationStatus,
+}
+
+/// A user in the moderation queue
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct QueuedUser {
+    pub user: ReportedUser,
+    pub reports: ReportSummary,
+}
+
+impl KeyedData for QueuedUser {
+    type Key = Principal;
+
+    fn key(&self) -> Self::Key {
+        self.user.user
+    }
+}
+
+/// Posts and creators hidden from our feeds by moderators
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct HiddenContent {
+    pub posts: HashSet<PostId>,
+    pub creators: HashSet<Principal>,
+}
+
+impl HiddenContent {
+    pub fn is_hidden(&self, canister_id: Principal, post_id: u64, creator: Principal) -> bool {
+        self.creators.contains(&creator) || self.posts.contains(&(canister_id, post_id))
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use auth::server_impl::store::{KVStore, KVStoreImpl};
+    use candid::Principal;
+    use leptos::prelude::ServerFnError;
+    use utils::report::ReportOption;
+
+    use super::*;
+
+    /// Reported posts with open reports, scored by last report time
+    const QUEUE_KEY: &str = "moderation-post-queue";
+    /// Posts hidden from our feeds, scored by when they were hidden
+    const HIDDEN_POSTS_KEY: &str = "moderation-hidden-post-ids";
+    /// Reported users with open reports, scored by last report time
+    const USER_QUEUE_KEY: &str = "moderation-user-queue-ids";
+    /// Creators whose posts are hidden from our feeds, scored by when they were hidden
+    const HIDDEN_CREATORS_KEY: &str = "moderation-hidden-creator-ids";
+
+    fn post_key((canister_id, post_id): PostId) -> String {
+        format!("moderation-post:{canister_id}:{post_id}")
+    }
+
+    fn user_key(user: Principal) -> String {
+        format!("moderation-user:{user}")
+    }
+
+    /// Posts (`post:{canister_id}:{post_id}`) and users (`user:{principal}`)
+    /// reported by a user, scored by report time
+    fn reports_by_key(reporter: Principal) -> String {
+        format!("moderation-reports-by:{reporter}")
+    }
+
+    fn post_subject(id: PostId) -> String {
+        format!("post:{}", post_member(id))
+    }
+
+    fn user_subject(user: Principal) -> String {
+        format!("user:{user}")
+    }
+
+    /// Principals that reported a post or user, scored by report time
+    fn reporters_key(subject: &str) -> String {
+        format!("moderation-reporters:{subject}")
+    }
+
+    fn report_key(subject: &str, reporter: Principal) -> String {
+        format!("moderation-report:{subject}:{reporter}")
+    }
+
+    /// Number of reporters that picked `reason`
+    fn reason_count_key(subject: &str, reason: &str) -> String {
+        format!("moderation-report-reasons:{subject}:{reason}")
+    }
+
+    fn reasons() -> impl Iterator<Item = String> {
+        ReportOption::all()
+            .into_iter()
+            .map(|o| o.as_str().to_string())
+    }
+
+    fn post_member((canister_id, post_id): PostId) -> String {
+        format!("{canister_id}:{post_id}")
+    }
+
+    fn parse_post_member(member: &str) -> Option<PostId> {
+        let (canister_id, post_id) = member.split_once(':')?;
+        Some((
+            Principal::from_text(canister_id).ok()?,
+            post_id.parse().ok()?,
+        ))
+    }
+
+    async fn read_json<T: serde::de::DeserializeOwned>(
+        kv: &KVStoreImpl,
+        key: String,
+    ) -> Result<Option<T>, ServerFnError> {
+        match kv.read(key).await? {
+            Some(raw) if !raw.is_empty() => Ok(Some(serde_json::from_str(&raw)?)),
+            _ => Ok(None),
+        }
+    }
+
+    async fn write_json<T: Serialize>(
+        kv: &KVStoreImpl,
+        key: String,
+        value: &T,
+    ) -> Result<(), ServerFnError> {
+        kv.write(key, serde_json::to_string(value)?).await?;
+        Ok(())
+    }
+
+    fn now() -> f64 {
+        current_epoch().as_secs() as f64
+    }
+
+    async fn index_all(kv: &KVStoreImpl, key: String) -> Result<Vec<String>, ServerFnError> {
+        let len = kv.index_len(key.clone()).await?;
+        Ok(kv.index_range(key, 0, len, false).await?)
+    }
+
+    /// Records `report` once per reporter, a repeated report replaces the earlier one
+    async fn record_report(
+        kv: &KVStoreImpl,
+        subject: &str,
+        report: &Report,
+    ) -> Result<(), ServerFnError> {
+        let reporter = report.reporter;
+        let previous: Option<Report> = read_json(kv, report_key(subject, reporter)).await?;
+        match previous {
+            Some(previous) if previous.reason == report.reason => {}
+            previous => {
+                if let Some(previous) = previous {
+                    kv.increment(reason_count_key(subject, &previous.reason), -1)
+                        .await?;
+                }
+                kv.increment(reason_count_key(subject, &report.reason), 1)
+                    .await?;
+            }
+        }
+        write_json(kv, report_key(subject, reporter), report).await?;
+
+        kv.index_add(
+            reporters_key(subject),
+            reporter.to_text(),
+            report.reported_at as f64,
+        )
+        .await?;
+        kv.index_add(
+            reports_by_key(reporter),
+            subject.to_string(),
+            report.reported_at as f64,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Removes `reporter`'s report, returns the number of reports left
+    async fn remove_report(
+        kv: &KVStoreImpl,
+        subject: &str,
+        reporter: Principal,
+    ) -> Result<usize, ServerFnError> {
+        let report: Option<Report> = read_json(kv, report_key(subject, reporter)).await?;
+        if let Some(report) = report {
+            kv.increment(reason_count_key(subject, &report.reason), -1)
+                .await?;
+            kv.delete(report_key(subject, reporter)).await?;
+        }
+        kv.index_remove(reporters_key(subject), reporter.to_text())
+            .await?;
+        kv.index_remove(reports_by_key(reporter), subject.to_string())
+            .await?;
+        Ok(kv.index_len(reporters_key(subject)).await?)
+    }
+
+    /// Removes every report of a post or user
+    async fn delete_reports(kv: &KVStoreImpl, subject: &str) -> Result<(), ServerFnError> {
+        for reporter in index_all(kv, reporters_key(subject)).await? {
+            if let Ok(reporter) = Principal::from_text(reporter) {
+                kv.delete(report_key(subject, reporter)).await?;
+                kv.index_remove(reports_by_key(reporter), subject.to_string())
+                    .await?;
+            }
+        }
+        for reason in reasons() {
+            kv.delete(reason_count_key(subject, &reason)).await?;
+        }
+        kv.index_delete(reporters_key(subject)).await?;
+        Ok(())
+    }
+
+    async fn report_summary(
+        kv: &KVStoreImpl,
+        subject: &str,
+    ) -> Result<ReportSummary, ServerFnError> {
+        let key = reporters_key(subject);
+        let count = kv.index_len(key.clone()).await?;
+        let mut last_reported_at = 0;
+        if let Some(latest) = kv.index_range(key.clone(), 0, 1, true).await?.pop() {
+            last_reported_at = kv.index_score(key, latest).await?.unwrap_or_default() as u64;
+        }
+
+        let mut reasons_reported = vec![];
+        for reason in reasons() {
+            let reporters = kv
+                .read(reason_count_key(subject, &reason))
+                .await?
+                .and_then(|c| c.parse::<i64>().ok())
+                .unwrap_or_default();
+            if reporters > 0 {
+                reasons_reported.push((reason, reporters as usize));
+            }
+        }
+        reasons_reported.sort_by(|a, b| b.1.cmp(&a.1));
+
+        Ok(ReportSummary {
+            count,
+            reasons: reasons_reported,
+            last_reported_at,
+        })
+    }
+
+    /// Posts with open reports, most recently reported first, with the queue length
+    pub async fn queue(
+        kv: &KVStoreImpl,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<PostId>, usize), ServerFnError> {
+        let members = kv
+            .index_range(QUEUE_KEY.to_string(), offset, limit, true)
+            .await?;
+        let total = kv.index_len(QUEUE_KEY.to_string()).await?;
+        Ok((
+            members
+                .iter()
+                .filter_map(|m| parse_post_member(m))
+                .collect(),
+            total,
+        ))
+    }
+
+    pub async fn reported_post(
+        kv: &KVStoreImpl,
+        post: PostId,
+    ) -> Result<Option<ReportedPost>, ServerFnError> {
+        read_json(kv, post_key(post)).await
+    }
+
+    pub async fn post_reports(
+        kv: &KVStoreImpl,
+        post: PostId,
+    ) -> Result<ReportSummary, ServerFnError> {
+        report_summary(kv, &post_subject(post)).await
+    }
+
+    /// Which of `posts` and `creators` were hidden from our feeds by moderators
+    pub async fn hidden_among(
+        kv: &KVStoreImpl,
+        posts: Vec<PostId>,
+        creators: Vec<Principal>,
+    ) -> Result<HiddenContent, ServerFnError> {
+        let mut hidden = HiddenContent::default();
+        for post in posts {
+            if kv
+                .index_score(HIDDEN_POSTS_KEY.to_string(), post_member(post))
+                .await?
+                .is_some()
+            {
+                hidden.posts.insert(post);
+            }
+        }
+        for creator in creators {
+            if kv
+                .index_score(HIDDEN_CREATORS_KEY.to_string(), creator.to_text())
+                .await?
+                .is_some()
+            {
+                hidden.creators.insert(creator);
+            }
+        }
+        Ok(hidden)
+    }
+
+    pub async fn is_hidden(
+        kv: &KVStoreImpl,
+        post: PostId,
+        creator: Principal,
+    ) -> Result<bool, ServerFnError> {
+        if kv
+            .index_score(HIDDEN_POSTS_KEY.to_string(), post_member(post))
+            .await?
+            .is_some()
+        {
+            return Ok(true);
+        }
+        Ok(kv
+            .index_score(HIDDEN_CREATORS_KEY.to_string(), creator.to_text())
+            .await?
+            .is_some())
+    }
+
+    pub async fn add_report(
+        kv: &KVStoreImpl,
+        mut post: ReportedPost,
+        report: Report,
+    ) -> Result<(), ServerFnError> {
+        let id = post.key();
+        let existing = reported_post(kv, id).await?.map(|p| p.status);
+        // new reports reopen posts which were dismissed before
+        if !matches!(
+            existing,
+            Some(ModerationStatus::Open | ModerationStatus::Banned)
+        ) {
+            post.status = ModerationStatus::Open;
+            write_json(kv, post_key(id), &post).await?;
+        }
+
+        record_report(kv, &post_subject(id), &report).await?;
+        kv.index_add(QUEUE_KEY.to_string(), post_member(id), now())
+            .await?;
+        Ok(())
+    }
+
+    #[cfg(feature = "backend-admin")]
+    pub async fn ban_post((canister_id, post_id): PostId) -> Result<(), ServerFnError> {
+        use state::admin_canisters::admin_canisters;
+        use yral_canisters_client::individual_user_template::PostStatus;
+
+        let admin = admin_canisters();
+        let user = admin.individual_user_for(canister_id).await;
+        user.update_post_status(post_id, PostStatus::BannedDueToUserReporting)
+            .await?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "backend-admin"))]
+    pub async fn ban_post(_post: PostId) -> Result<(), ServerFnError> {
+        Err(crate::admin::AdminError::BackendAdminUnavailable.into())
+    }
+
+    pub async fn apply_action(
+        kv: &KVStoreImpl,
+        id: PostId,
+        action: ModerationAction,
+    ) -> Result<(), ServerFnError> {
+        let mut post = reported_post(kv, id)
+            .await?
+            .ok_or_else(|| ServerFnError::new("Reported post not found"))?;
+
+        match action {
+            ModerationAction::Ban => ban_post(id).await?,
+            ModerationAction::Hide => {
+                kv.index_add(HIDDEN_POSTS_KEY.to_string(), post_member(id), now())
+                    .await?;
+            }
+            ModerationAction::Dismiss => {
+                kv.index_remove(HIDDEN_POSTS_KEY.to_string(), post_member(id))
+                    .await?;
+            }
+        }
+
+        post.status = action.status();
+        write_json(kv, post_key(id), &post).await?;
+
+        kv.index_remove(QUEUE_KEY.to_string(), post_member(id))
+            .await?;
+        Ok(())
+    }
+
+    /// Users with open reports, most recently reported first, with the queue length
+    pub async fn user_queue(
+        kv: &KVStoreImpl,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Principal>, usize), ServerFnError> {
+        let members = kv
+            .index_range(USER_QUEUE_KEY.to_string(), offset, limit, true)
+            .await?;
+        let total = kv.index_len(USER_QUEUE_KEY.to_string()).await?;
+        Ok((
+            members
+                .iter()
+                .filter_map(|m| Principal::from_text(m).ok())
+                .collect(),
+            total,
+        ))
+    }
+
+    pub async fn reported_user(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<Option<ReportedUser>, ServerFnError> {
+        read_json(kv, user_key(user)).await
+    }
+
+    pub async fn user_reports(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<ReportSummary, ServerFnError> {
+        report_summary(kv, &user_subject(user)).await
+    }
+
+    pub async fn add_user_report(
+        kv: &KVStoreImpl,
+        user: Principal,
+        report: Report,
+    ) -> Result<(), ServerFnError> {
+        let existing = reported_user(kv, user).await?.map(|u| u.status);
+        if existing != Some(ModerationStatus::Open) {
+            let reported = ReportedUser {
+                user,
+                status: ModerationStatus::Open,
+            };
+            write_json(kv, user_key(user), &reported).await?;
+        }
+
+        record_report(kv, &user_subject(user), &report).await?;
+        kv.index_add(USER_QUEUE_KEY.to_string(), user.to_text(), now())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn apply_user_action(
+        kv: &KVStoreImpl,
+        user: Principal,
+        action: ModerationAction,
+    ) -> Result<(), ServerFnError> {
+        let mut reported = reported_user(kv, user)
+            .await?
+            .ok_or_else(|| ServerFnError::new("Reported user not found"))?;
+
+        match action {
+            ModerationAction::Ban => {
+                return Err(ServerFnError::new("Only posts can be banned"));
+            }
+            ModerationAction::Hide => {
+                kv.index_add(HIDDEN_CREATORS_KEY.to_string(), user.to_text(), now())
+                    .await?;
+            }
+            ModerationAction::Dismiss => {
+                kv.index_remove(HIDDEN_CREATORS_KEY.to_string(), user.to_text())
+                    .await?;
+            }
+        }
+
+        reported.status = action.status();
+        write_json(kv, user_key(user), &reported).await?;
+
+        kv.index_remove(USER_QUEUE_KEY.to_string(), user.to_text())
+            .await?;
+        Ok(())
+    }
+
+    /// Latest post and user reports made by the user, newest first, with the
+    /// reported `post:{canister_id}:{post_id}` or `user:{principal}`
+    pub async fn reports_by(
+        kv: &KVStoreImpl,
+        user: Principal,
+        limit: usize,
+    ) -> Result<Vec<(String, Report)>, ServerFnError> {
+        let mut reports = vec![];
+        for subject in kv.index_range(reports_by_key(user), 0, limit, true).await? {
+            if let Some(report) = read_json(kv, report_key(&subject, user)).await? {
+                reports.push((subject, report));
+            }
+        }
+        Ok(reports)
+    }
+
+    /// Removes the reports made by the user and every report of their profile,
+    /// posts and users left without reports leave the queue
+    pub async fn delete_user_data(kv: &KVStoreImpl, user: Principal) -> Result<(), ServerFnError> {
+        let key = reports_by_key(user);
+        for subject in index_all(kv, key.clone()).await? {
+            let left = remove_report(kv, &subject, user).await?;
+            if left > 0 {
+                continue;
+            }
+            if let Some(id) = subject.strip_prefix("post:").and_then(parse_post_member) {
+                kv.delete(post_key(id)).await?;
+                kv.index_remove(QUEUE_KEY.to_string(), post_member(id))
+                    .await?;
+            } else if let Some(reported) = subject
+                .strip_prefix("user:")
+                .and_then(|p| Principal::from_text(p).ok())
+            {
+                kv.delete(user_key(reported)).await?;
+                kv.index_remove(USER_QUEUE_KEY.to_string(), reported.to_text())
+                    .await?;
+            }
+        }
+        kv.index_delete(key).await?;
+
+        delete_reports(kv, &user_subject(user)).await?;
+        kv.delete(user_key(user)).await?;
+        kv.index_remove(USER_QUEUE_KEY.to_string(), user.to_text())
+            .await?;
+        kv.index_remove(HIDDEN_CREATORS_KEY.to_string(), user.to_text())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Persists a post report for the moderation queue, `reason` is one of `ReportOption`
+///
+/// The video and its creator are looked up from the post, never taken from the client
+#[server(endpoint = "record_post_report", input = server_fn::codec::Json)]
+pub async fn record_post_report(
+    canister_id: Principal,
+    post_id: u64,
+    reason: String,
+) -> Result<(), ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+    use yral_canisters_common::Canisters;
+
+    let Some(reporter) = extract_principal_impl().await? else {
+        return Err(ServerFnError::new("Login required to report posts"));
+    };
+    if !is_valid_reason(&reason) {
+        return Err(ServerFnError::new("Invalid report reason"));
+    }
+
+    let cans: Canisters<false> = expect_context();
+    let details = cans
+        .get_post_details(canister_id, post_id)
+        .await?
+        .ok_or_else(|| ServerFnError::new("Post not found"))?;
+
+    let kv: KVStoreImpl = expect_context();
+    let post = ReportedPost {
+        canister_id,
+        post_id,
+        video_uid: details.uid,
+        publisher: details.poster_principal,
+        status: ModerationStatus::Open,
+    };
+    let report = Report {
+        reporter,
+        reason,
+        reported_at: current_epoch().as_secs(),
+    };
+    server::add_report(&kv, post, report).await
+}
+
+#[server(endpoint = "get_moderation_queue", input = server_fn::codec::Json)]
+pub async fn get_moderation_queue(
+    start: usize,
+    end: usize,
+) -> Result<(Vec<QueuedPost>, bool), ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+
+    super::server::require_admin().await?;
+    let kv: KVStoreImpl = expect_context();
+    let (queue, total) = server::queue(&kv, start, end.saturating_sub(start)).await?;
+    let mut posts = vec![];
+    for id in queue {
+        if let Some(post) = server::reported_post(&kv, id).await? {
+            let reports = server::post_reports(&kv, id).await?;
+            posts.push(QueuedPost { post, reports });
+        }
+    }
+    Ok((posts, end >= total))
+}
+
+#[server(endpoint = "moderate_post", input = server_fn::codec::Json)]
+pub async fn moderate_post(
+    canister_id: Principal,
+    post_id: u64,
+    action: ModerationAction,
+    note: Option<String>,
+) -> Result<(), ServerFnError> {
+    use super::audit::server::audited;
+    use auth::server_impl::store::KVStoreImpl;
+
+    let admin = super::server::require_admin().await?;
+    let kv: KVStoreImpl = expect_context();
+    audited(
+        &kv,
+        admin,
+        format!("moderation:{}", action.as_str()),
+        format!("{canister_id}/{post_id}"),
+        note.filter(|n| !n.trim().is_empty()),
+        server::apply_action(&kv, (canister_id, post_id), action),
+    )
+    .await
+}
+
+/// Persists a report of `user`'s profile, `reason` is one of `ReportOption`
+#[server(endpoint = "record_user_report", input = server_fn::codec::Json)]
+pub async fn record_user_report(user: Principal, reason: String) -> Result<(), ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let Some(reporter) = extract_principal_impl().await? else {
+        return Err(ServerFnError::new("Login required to report users"));
+    };
+    if reporter == user {
+        return Err(ServerFnError::new("You can't report yourself"));
+    }
+    if !is_valid_reason(&reason) {
+        return Err(ServerFnError::new("Invalid report reason"));
+    }
+
+    let kv: KVStoreImpl = expect_context();
+    let report = Report {
+        reporter,
+        reason,
+        reported_at: current_epoch().as_secs(),
+    };
+    server::add_user_report(&kv, user, report).await
+}
+
+#[server(endpoint = "get_reported_users", input = server_fn::codec::Json)]
+pub async fn get_reported_users(
+    start: usize,
+    end: usize,
+) -> Result<(Vec<QueuedUser>, bool), ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+
+    super::server::require_admin().await?;
+    let kv: KVStoreImpl = expect_context();
+    let (queue, total) = server::user_queue(&kv, start, end.saturating_sub(start)).await?;
+    let mut users = vec![];
+    for user in queue {
+        if let Some(reported) = server::reported_user(&kv, user).await? {
+            let reports = server::user_reports(&kv, user).await?;
+            users.push(QueuedUser {
+                user: reported,
+                reports,
+            });
+        }
+    }
+    Ok((users, end >= total))
+}
+
+#[server(endpoint = "moderate_user", input = server_fn::codec::Json)]
+pub async fn moderate_user(
+    user: Principal,
+    action: ModerationAction,
+    note: Option<String>,
+) -> Result<(), ServerFnError> {
+    use super::audit::server::audited;
+    use auth::server_impl::store::KVStoreImpl;
+
+    let admin = super::server::require_admin().await?;
+    let kv: KVStoreImpl = expect_context();
+    audited(
+        &kv,
+        admin,
+        format!("moderation:user-{}", action.as_str()),
+        user.to_text(),
+        note.filter(|n| !n.trim().is_empty()),
+        server::apply_user_action(&kv, user, action),
+    )
+    .await
+}
+
+/// Which of `posts` and `creators` were hidden by moderators,
+/// at most [MAX_HIDDEN_QUERY] of each are checked per call
+#[server(endpoint = "hidden_content", input = server_fn::codec::Json)]
+pub async fn get_hidden_content(
+    posts: Vec<PostId>,
+    creators: Vec<Principal>,
+) -> Result<HiddenContent, ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+
+    if posts.len() > MAX_HIDDEN_QUERY || creators.len() > MAX_HIDDEN_QUERY {
+        return Err(ServerFnError::new("Too many items to check"));
+    }
+    let kv: KVStoreImpl = expect_context();
+    server::hidden_among(&kv, posts, creators).await
+}
+
+/// Whether the post or its creator was hidden by moderators
+#[server(endpoint = "is_post_hidden", input = server_fn::codec::Json)]
+pub async fn is_post_hidden(
+    canister_id: Principal,
+    post_id: u64,
+    creator: Principal,
+) -> Result<bool, ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+
+    let kv: KVStoreImpl = expect_context();
+    server::is_hidden(&kv, (canister_id, post_id), creator).await
+}
+
+/// Hidden content among `posts` and their creators, nothing is hidden if the check fails
+pub async fn hidden_among(posts: &[PostDetails]) -> HiddenContent {
+    let mut hidden = HiddenContent::default();
+    for chunk in posts.chunks(MAX_HIDDEN_QUERY) {
+        let ids = chunk.iter().map(|p| (p.canister_id, p.post_id)).collect();
+        let creators = chunk
+            .iter()
+            .map(|p| p.poster_principal)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        match get_hidden_content(ids, creators).await {
+            Ok(found) => {
+                hidden.posts.extend(found.posts);
+                hidden.creators.extend(found.creators);
+            }
+            Err(e) => log::warn!("failed to check hidden content {e}"),
+        }
+    }
+    hidden
+}
+
+/// Whether the post or its creator was hidden by moderators, nothing is hidden until it loads
+pub fn use_post_hidden(canister_id: Principal, post_id: u64, creator: Principal) -> Signal<bool> {
+    let hidden = LocalResource::new(move || async move {
+        is_post_hidden(canister_id, post_id, creator)
+            .await
+            .inspect_err(|e| log::warn!("failed to check hidden post {e}"))
+            .unwrap_or_default()
+    });
+    Signal::derive(move || hidden.get().unwrap_or_default())
+}
+
+/// Whether the creator was hidden by moderators, nothing is hidden until it loads
+pub fn use_creator_hidden(creator: Principal) -> Signal<bool> {
+    let hidden = LocalResource::new(move || async move {
+        get_hidden_content(vec![], vec![creator])
+            .await
+            .inspect_err(|e| log::warn!("failed to check hidden creator {e}"))
+            .is_ok_and(|h| h.creators.contains(&creator))
+    });
+    Signal::derive(move || hidden.get().unwrap_or_default())
+}
+
+#[derive(Clone, Copy)]
+pub struct ModerationQueueProvider;
+
+impl CursoredDataProvider for ModerationQueueProvider {
+    type Data = QueuedPost;
+    type Error = ServerFnError;
+
+    async fn get_by_cursor_inner(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Result<PageEntry<QueuedPost>, ServerFnError> {
+        let (data, end) = get_moderation_queue(start, end).await?;
+        Ok(PageEntry { data, end })
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ReportedUsersProvider;
+
+impl CursoredDataProvider for ReportedUsersProvider {
+    type Data = QueuedUser;
+    type Error = ServerFnError;
+
+    async fn get_by_cursor_inner(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Result<PageEntry<QueuedUser>, ServerFnError> {
+        let (data, end) = get_reported_users(start, end).await?;
+        Ok(PageEntry { data, end })
+    }
+}
+
+fn reason_chips(reasons: Vec<(String, usize)>) -> impl IntoView {
+    reasons
+        .into_iter()
+        .map(|(reason, count)| {
+            view! {
+                <span class="py-0.5 px-2 text-xs rounded-full bg-neutral-800">
+                    {format!("{reason} × {count}")}
+                </span>
+            }
+        })
+        .collect_view()
+}
+
+#[component]
+fn ReportedPostRow(queued: QueuedPost, _ref: NodeRef<html::Div>) -> impl IntoView {
+    let QueuedPost { post, reports } = queued;
+    let (canister_id, post_id) = post.key();
+    let note = RwSignal::new(String::new());
+    let decided = RwSignal::new(None::<ModerationAction>);
+    let error = RwSignal::new(None::<String>);
+
+    let moderate = Action::new(move |&action: &ModerationAction| {
+        let note = Some(note.get_untracked());
+        send_wrap(async move {
+            match moderate_post(canister_id, post_id, action, note).await {
+                Ok(()) => decided.set(Some(action)),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        })
+    });
+
+    let reasons = reason_chips(reports.reasons);
+    let action_button =
+        move |action: ModerationAction, label: &'static str, class: &'static str| {
+            view! {
+                <button
+                    class=format!("py-1 px-3 text-sm rounded-md disabled:opacity-50 {class}")
+                    disabled=move || moderate.pending().get()
+                    on:click=move |_| {
+                        moderate.dispatch(action);
+                    }
+                >
+                    {label}
+                </button>
+            }
+        };
+
+    view! {
+        <div node_ref=_ref class="flex flex-row gap-4 p-3 w-full rounded-md border border-neutral-800">
+            <a href=format!("/hot-or-not/{canister_id}/{post_id}") target="_blank" class="shrink-0">
+                <img class="object-cover w-20 rounded-md aspect-9/16" src=bg_url(post.video_uid.clone()) />
+            </a>
+            <div class="flex flex-col flex-1 gap-2 min-w-0">
+                <div class="flex flex-row justify-between text-xs text-neutral-400">
+                    <a class="truncate" href=format!("/profile/{}/posts", post.publisher)>
+                        {post.publisher.to_text()}
+                    </a>
+                    <span class="shrink-0">
+                        {format!(
+                            "{} reports · {}",
+                            reports.count,
+                            time_ago(reports.last_reported_at, current_epoch().as_secs()),
+                        )}
+                    </span>
+                </div>
+                <div class="flex flex-row flex-wrap gap-1">{reasons}</div>
+                <Show
+                    when=move || decided.with(|d| d.is_none())
+                    fallback=move || {
+                        view! {
+                            <span class="text-sm text-neutral-400">
+                                {move || decided.get().map(|a| format!("Done: {}", a.as_str()))}
+                            </span>
+                        }
+                    }
+                >
+                    <input
+                        class="py-1 px-2 text-sm text-white rounded-md bg-neutral-800"
+                        placeholder="Note for the audit log (optional)"
+                        prop:value=note
+                        on:input=move |ev| note.set(event_target_value(&ev))
+                    />
+                    <div class="flex flex-row gap-2">
+                        {action_button(ModerationAction::Dismiss, "Dismiss", "bg-neutral-700")}
+                        {action_button(ModerationAction::Hide, "Hide", "bg-yellow-700")}
+                        {action_button(ModerationAction::Ban, "Ban", "bg-red-700")}
+                    </div>
+                </Show>
+                <Show when=move || error.with(|e| e.is_some())>
+                    <span class="text-xs text-red-500">{move || error.get().unwrap_or_default()}</span>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ReportedUserRow(queued: QueuedUser, _ref: NodeRef<html::Div>) -> impl IntoView {
+    let QueuedUser { user, reports } = queued;
+    let user = user.user;
+    let note = RwSignal::new(String::new());
+    let decided = RwSignal::new(None::<ModerationAction>);
+    let error = RwSignal::new(None::<String>);
+
+    let moderate = Action::new(move |&action: &ModerationAction| {
+        let note = Some(note.get_untracked());
+        send_wrap(async move {
+            match moderate_user(user, action, note).await {
+                Ok(()) => decided.set(Some(action)),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        })
+    });
+
+    let reasons = reason_chips(reports.reasons);
+    let action_button =
+        move |action: ModerationAction, label: &'static str, class: &'static str| {
+            view! {
+                <button
+                    class=format!("py-1 px-3 text-sm rounded-md disabled:opacity-50 {class}")
+                    disabled=move || moderate.pending().get()
+                    on:click=move |_| {
+                        moderate.dispatch(action);
+                    }
+                >
+                    {label}
+                </button>
+            }
+        };
+
+    view! {
+        <div node_ref=_ref class="flex flex-col gap-2 p-3 w-full rounded-md border border-neutral-800">
+            <div class="flex flex-row justify-between text-xs text-neutral-400">
+                <a class="truncate" href=format!("/profile/{user}/posts")>
+                    {user.to_text()}
+                </a>
+                <span class="shrink-0">
+                    {format!(
+                        "{} reports · {}",
+                        reports.count,
+                        time_ago(reports.last_reported_at, current_epoch().as_secs()),
+                    )}
+                </span>
+            </div>
+            <div class="flex flex-row flex-wrap gap-1">{reasons}</div>
+            <Show
+                when=move || decided.with(|d| d.is_none())
+                fallback=move || {
+                    view! {
+                        <span class="text-sm text-neutral-400">
+                            {move || decided.get().map(|a| format!("Done: {}", a.as_str()))}
+                        </span>
+                    }
+                }
+            >
+                <input
+                    class="py-1 px-2 text-sm text-white rounded-md bg-neutral-800"
+                    placeholder="Note for the audit log (optional)"
+                    prop:value=note
+                    on:input=move |ev| note.set(event_target_value(&ev))
+                />
+                <div class="flex flex-row gap-2">
+                    {action_button(ModerationAction::Dismiss, "Dismiss", "bg-neutral-700")}
+                    {action_button(ModerationAction::Hide, "Hide posts", "bg-yellow-700")}
+                </div>
+            </Show>
+            <Show when=move || error.with(|e| e.is_some())>
+                <span class="text-xs text-red-500">{move || error.get().unwrap_or_default()}</span>
+            </Show>
+        </div>
+    }
+}
+
+#[component]
+pub fn ModerationQueue() -> impl IntoView {
+    let app_state = use_context::<AppState>();
+    let page_title = app_state.unwrap().name.to_owned() + " - Moderation";
+    let show_users = RwSignal::new(false);
+    let tab_class = move |users: bool| {
+        if show_users() == users {
+            "py-2 w-full text-center border-b-4 text-primary-500 border-primary-500"
+        } else {
+            "py-2 w-full text-center text-white"
+        }
+    };
+
+    view! {
+        <Title text=page_title />
+        <div class="flex flex-col items-center pt-2 pb-12 min-h-screen text-white bg-black">
+            <TitleText justify_center=false>
+                <div class="flex flex-row justify-between">
+                    <BackButton fallback="/admin".to_string() />
+                    <span class="text-2xl font-bold">Moderation queue</span>
+                    <div></div>
+                </div>
+            </TitleText>
+            <AdminGate>
+                <div class="flex flex-row px-4 pt-4 w-full max-w-2xl">
+                    <button class=move || tab_class(false) on:click=move |_| show_users.set(false)>
+                        Posts
+                    </button>
+                    <button class=move || tab_class(true) on:click=move |_| show_users.set(true)>
+                        Users
+                    </button>
+                </div>
+                <div class="flex flex-col gap-3 px-4 pt-4 w-full max-w-2xl">
+                    <Show
+                        when=show_users
+                        fallback=|| {
+                            view! {
+                                <InfiniteScroller
+                                    provider=ModerationQueueProvider
+                                    fetch_count=QUEUE_CHUNK_SZ
+                                    children=|queued, _ref| {
+                                        view! { <ReportedPostRow queued _ref=_ref.unwrap_or_default() /> }
+                                    }
+                                    empty_content=|| {
+                                        view! {
+                                            <span class="py-12 text-center text-neutral-400">
+                                                No open reports
+                                            </span>
+                                        }
+                                    }
+                                />
+                            }
+                        }
+                    >
+                        <InfiniteScroller
+                            provider=ReportedUsersProvider
+                            fetch_count=QUEUE_CHUNK_SZ
+                            children=|queued, _ref| {
+                                view! { <ReportedUserRow queued _ref=_ref.unwrap_or_default() /> }
+                            }
+                            empty_content=|| {
+                                view! {
+                                    <span class="py-12 text-center text-neutral-400">
+                                        No reported users
+                                    </span>
+                                }
+                            }
+                        />
+                    </Show>
+                </div>
+            </AdminGate>
+        </div>
+    }
+}
@@ -9,7 +9,11 @@ use leptos::prelude::*;
 use leptos_router::hooks::use_navigate;
 use limits::{MAX_WITHDRAWAL_PER_TXN_SATS, MIN_WITHDRAWAL_PER_TXN_SATS};
 use log;
-use state::{canisters::auth_state, server::HonWorkerJwt};
+use state::{
+    balance_store::{BalanceKey, BalanceStore},
+    canisters::auth_state,
+    server::HonWorkerJwt,
+};
 use utils::send_wrap;
 use yral_canisters_client::individual_user_template::{Result7, SessionType};
 use yral_canisters_common::{utils::token::balance::TokenBalance, Canisters};
@@ -203,11 +207,13 @@ pub fn HonWithdrawal() -> impl IntoView {
     });
     let is_claiming = send_claim.pending();
     let claim_res = send_claim.value();
+    let balance_store = BalanceStore::use_store();
     Effect::new(move |_| {
         if let Some(res) = claim_res.get() {
             let nav = use_navigate();
             match res {
                 Ok(_) => {
+                    balance_store.debit(BalanceKey::Sats, sats());
                     nav(
                         &format!("/hot-or-not/withdraw/success?sats={}", sats()),
                         Default::default(),
@@ -0,0 +1,238 @@
+mod server_impl;
+
+use component::{back_btn::BackButton, infinite_scroller::InfiniteScroller, title::TitleText};
+use leptos::html;
+use leptos::prelude::*;
+use leptos_meta::*;
+use server_impl::{fetch_leaderboard, fetch_leaderboard_rank};
+use state::{
+    app_state::AppState,
+    canisters::auth_state,
+    leaderboard::{LeaderboardEntry, LeaderboardKind, LeaderboardPeriod},
+};
+use utils::send_wrap;
+use yral_canisters_common::{
+    cursored_data::{CursoredDataProvider, PageEntry},
+    utils::token::balance::TokenBalance,
+};
+
+const FETCH_CNT: usize = 20;
+/// Number of ranks shown above the user in the "around me" view
+const AROUND_ME_WINDOW: u64 = 5;
+
+#[derive(Clone)]
+struct LeaderboardProvider {
+    period: LeaderboardPeriod,
+    kind: LeaderboardKind,
+    /// rank offset to start fetching from
+    offset: usize,
+}
+
+impl CursoredDataProvider for LeaderboardProvider {
+    type Data = LeaderboardEntry;
+    type Error = ServerFnError;
+
+    async fn get_by_cursor_inner(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Result<PageEntry<LeaderboardEntry>, ServerFnError> {
+        let page = fetch_leaderboard(
+            self.period,
+            self.kind,
+            self.offset + start,
+            self.offset + end,
+        )
+        .await?;
+
+        Ok(PageEntry {
+            data: page.entries,
+            end: page.end,
+        })
+    }
+}
+
+#[component]
+fn LeaderboardRow(
+    entry: LeaderboardEntry,
+    #[prop(into)] is_current_user: Signal<bool>,
+    #[prop(optional)] _ref: NodeRef<html::Div>,
+) -> impl IntoView {
+    let principal = entry.principal;
+    let (name, propic) = entry
+        .profile
+        .map(|p| (p.display_name, p.profile_pic))
+        .unwrap_or_else(|| (principal.to_text(), String::new()));
+    let amount = TokenBalance::new(entry.amount.into(), 0).humanize();
+
+    view! {
+        <div
+            node_ref=_ref
+            class="flex gap-4 items-center py-3 px-4 w-full rounded-lg"
+            class=("bg-primary-600/20", is_current_user)
+            class=("border", is_current_user)
+            class=("border-primary-600", is_current_user)
+        >
+            <span class="w-10 text-lg font-bold text-center text-neutral-300">{entry.rank}</span>
+            <a href=format!("/profile/{principal}/posts") class="flex flex-1 gap-3 items-center min-w-0">
+                <img class="object-cover w-10 h-10 rounded-full shrink-0" src=propic />
+                <span class="font-semibold text-white truncate">{name}</span>
+            </a>
+            <span class="text-sm font-semibold text-white shrink-0">{amount} SATS</span>
+        </div>
+    }
+}
+
+#[component]
+fn LeaderboardList(
+    period: LeaderboardPeriod,
+    kind: LeaderboardKind,
+    #[prop(optional)] offset: usize,
+) -> impl IntoView {
+    let auth = auth_state();
+    let provider = LeaderboardProvider {
+        period,
+        kind,
+        offset,
+    };
+
+    view! {
+        <div class="flex flex-col gap-2 w-full">
+            <InfiniteScroller
+                provider
+                fetch_count=FETCH_CNT
+                children=move |entry, _ref| {
+                    let principal = entry.principal;
+                    let is_current_user = Signal::derive(move || {
+                        auth.user_principal
+                            .get()
+                            .and_then(|p| p.ok())
+                            .map(|p| p == principal)
+                            .unwrap_or_default()
+                    });
+                    view! {
+                        <LeaderboardRow entry is_current_user _ref=_ref.unwrap_or_default() />
+                    }
+                }
+                empty_content=move || {
+                    view! {
+                        <span class="py-9 w-full text-center text-neutral-400">
+                            No games played in this period yet
+                        </span>
+                    }
+                }
+            />
+        </div>
+    }
+}
+
+#[component]
+fn AroundMe(period: LeaderboardPeriod, kind: LeaderboardKind) -> impl IntoView {
+    let auth = auth_state();
+    let user_rank = Resource::new(
+        move || (),
+        move |_| {
+            send_wrap(async move {
+                let user_principal = auth.user_principal.await?;
+                fetch_leaderboard_rank(period, kind, user_principal).await
+            })
+        },
+    );
+
+    view! {
+        <Suspense>
+            {move || Suspend::new(async move {
+                match user_rank.await {
+                    Ok(Some(rank)) => {
+                        let offset = rank.saturating_sub(AROUND_ME_WINDOW + 1) as usize;
+                        view! { <LeaderboardList period kind offset /> }.into_any()
+                    }
+                    Ok(None) => {
+                        view! {
+                            <span class="py-9 w-full text-center text-neutral-400">
+                                "You're not on this leaderboard yet, play a few games to get ranked!"
+                            </span>
+                        }
+                            .into_any()
+                    }
+                    Err(e) => {
+                        log::warn!("failed to fetch leaderboard rank {e}");
+                        view! {
+                            <span class="py-9 w-full text-center text-neutral-400">
+                                Failed to load your rank
+                            </span>
+                        }
+                            .into_any()
+                    }
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn TabButton<T: PartialEq + Copy + Send + Sync + 'static>(
+    value: T,
+    selected: RwSignal<T>,
+    label: &'static str,
+) -> impl IntoView {
+    view! {
+        <button
+            class="py-2 px-3 text-sm font-semibold rounded-full"
+            class=("bg-primary-600", move || selected() == value)
+            class=("text-white", move || selected() == value)
+            class=("bg-neutral-900", move || selected() != value)
+            class=("text-neutral-400", move || selected() != value)
+            on:click=move |_| selected.set(value)
+        >
+            {label}
+        </button>
+    }
+}
+
+#[component]
+pub fn Leaderboard() -> impl IntoView {
+    let app_state = use_context::<AppState>();
+    let page_title = app_state.unwrap().name.to_owned() + " - Leaderboard";
+
+    let period = RwSignal::new(LeaderboardPeriod::Daily);
+    let kind = RwSignal::new(LeaderboardKind::Winners);
+    let around_me = RwSignal::new(false);
+
+    view! {
+        <Title text=page_title />
+        <div class="flex flex-col items-center pt-2 pb-24 bg-black min-w-dvw min-h-dvh">
+            <TitleText justify_center=false>
+                <div class="flex flex-row justify-between">
+                    <BackButton fallback="/menu".to_string() />
+                    <span class="text-lg font-bold text-white">Leaderboard</span>
+                    <div></div>
+                </div>
+            </TitleText>
+            <div class="flex flex-col gap-4 px-4 w-full max-w-md">
+                <div class="flex flex-row gap-2 justify-center">
+                    <TabButton value=LeaderboardKind::Winners selected=kind label=LeaderboardKind::Winners.label() />
+                    <TabButton value=LeaderboardKind::Creators selected=kind label=LeaderboardKind::Creators.label() />
+                </div>
+                <div class="flex flex-row gap-2 justify-center">
+                    <TabButton value=LeaderboardPeriod::Daily selected=period label=LeaderboardPeriod::Daily.label() />
+                    <TabButton value=LeaderboardPeriod::Weekly selected=period label=LeaderboardPeriod::Weekly.label() />
+                    <TabButton value=LeaderboardPeriod::AllTime selected=period label=LeaderboardPeriod::AllTime.label() />
+                </div>
+                <div class="flex flex-row gap-2 justify-end">
+                    <TabButton value=false selected=around_me label="Top" />
+                    <TabButton value=true selected=around_me label="Around Me" />
+                </div>
+                {move || {
+                    let period = period();
+                    let kind = kind();
+                    if around_me() {
+                        view! { <AroundMe period kind /> }.into_any()
+                    } else {
+                        view! { <LeaderboardList period kind /> }.into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
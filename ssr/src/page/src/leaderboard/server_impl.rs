@@ -0,0 +1,196 @@
+use candid::Principal;
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use state::leaderboard::LeaderboardProfile;
+use state::leaderboard::{LeaderboardEntry, LeaderboardKind, LeaderboardPeriod};
+
+/// Maximum number of ranks computed for a single leaderboard
+pub const LEADERBOARD_MAX_ENTRIES: usize = 500;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct LeaderboardPage {
+    pub entries: Vec<LeaderboardEntry>,
+    pub end: bool,
+}
+
+#[cfg(feature = "ssr")]
+async fn cached_leaderboard(
+    period: LeaderboardPeriod,
+    kind: LeaderboardKind,
+) -> Result<std::sync::Arc<Vec<LeaderboardEntry>>, ServerFnError> {
+    #[cfg(feature = "alloydb")]
+    use alloydb::compute_leaderboard;
+    #[cfg(not(feature = "alloydb"))]
+    use mock::compute_leaderboard;
+    use state::leaderboard::LeaderboardCache;
+
+    let cache: LeaderboardCache = expect_context();
+    if let Some(board) = cache.get(period, kind) {
+        return Ok(board);
+    }
+
+    let board = std::sync::Arc::new(compute_leaderboard(period, kind).await?);
+    cache.insert(period, kind, board.clone());
+
+    Ok(board)
+}
+
+#[cfg(feature = "ssr")]
+async fn load_profile(principal: Principal) -> Option<LeaderboardProfile> {
+    use state::canisters::unauth_canisters;
+    use yral_canisters_common::utils::profile::ProfileDetails;
+
+    let cans = unauth_canisters();
+    let user_canister = cans
+        .get_individual_canister_by_user_principal(principal)
+        .await
+        .ok()??;
+    let user = cans.individual_user(user_canister).await;
+    let details = ProfileDetails::from(user.get_profile_details().await.ok()?);
+    Some(LeaderboardProfile {
+        display_name: details.display_name_or_fallback(),
+        profile_pic: details.profile_pic_or_random(),
+    })
+}
+
+/// Adds names and avatars to a page of entries, so rows don't each hit a canister
+#[cfg(feature = "ssr")]
+async fn with_profiles(mut entries: Vec<LeaderboardEntry>) -> Vec<LeaderboardEntry> {
+    use futures::future::join_all;
+    use state::leaderboard::LeaderboardCache;
+
+    let cache: LeaderboardCache = expect_context();
+    let profiles = join_all(entries.iter().map(|entry| {
+        let cache = cache.clone();
+        let principal = entry.principal;
+        async move {
+            if let Some(profile) = cache.get_profile(principal) {
+                return profile;
+            }
+            let profile = load_profile(principal).await;
+            cache.insert_profile(principal, profile.clone());
+            profile
+        }
+    }))
+    .await;
+    for (entry, profile) in entries.iter_mut().zip(profiles) {
+        entry.profile = profile;
+    }
+    entries
+}
+
+#[server(endpoint = "leaderboard", input = server_fn::codec::Json)]
+pub async fn fetch_leaderboard(
+    period: LeaderboardPeriod,
+    kind: LeaderboardKind,
+    start: usize,
+    end: usize,
+) -> Result<LeaderboardPage, ServerFnError> {
+    let board = cached_leaderboard(period, kind).await?;
+    let end = end.min(board.len());
+    let entries = board
+        .get(start..end)
+        .map(|e| e.to_vec())
+        .unwrap_or_default();
+    let entries = with_profiles(entries).await;
+
+    Ok(LeaderboardPage {
+        entries,
+        end: end >= board.len(),
+    })
+}
+
+/// returns the rank of the given user, if they are on the leaderboard
+#[server(endpoint = "leaderboard_rank", input = server_fn::codec::Json)]
+pub async fn fetch_leaderboard_rank(
+    period: LeaderboardPeriod,
+    kind: LeaderboardKind,
+    user_principal: Principal,
+) -> Result<Option<u64>, ServerFnError> {
+    let board = cached_leaderboard(period, kind).await?;
+
+    Ok(board
+        .iter()
+        .find(|entry| entry.principal == user_principal)
+        .map(|entry| entry.rank))
+}
+
+#[cfg(feature = "alloydb")]
+mod alloydb {
+    use super::*;
+    use state::alloydb::AlloyDbInstance;
+
+    pub async fn compute_leaderboard(
+        period: LeaderboardPeriod,
+        kind: LeaderboardKind,
+    ) -> Result<Vec<LeaderboardEntry>, ServerFnError> {
+        // sanitization is not required here, period and kind are fixed strings
+        let query = format!(
+            "select user_principal, amount from hot_or_not_evaluator.get_leaderboard_v1('{}', '{}', {LEADERBOARD_MAX_ENTRIES})",
+            kind.as_str(),
+            period.as_str(),
+        );
+
+        let alloydb: AlloyDbInstance = expect_context();
+        let mut res = alloydb.execute_sql_raw(query).await?;
+        let res = res
+            .sql_results
+            .pop()
+            .expect("hot_or_not_evaluator.get_leaderboard_v1 MUST return a result");
+
+        res.rows
+            .into_iter()
+            .enumerate()
+            .map(|(idx, row)| {
+                let mut values = row.values.into_iter().map(|v| v.value);
+                let principal = values
+                    .next()
+                    .flatten()
+                    .ok_or_else(|| ServerFnError::new("leaderboard row without principal"))?;
+                let amount = values
+                    .next()
+                    .flatten()
+                    .ok_or_else(|| ServerFnError::new("leaderboard row without amount"))?;
+
+                Ok(LeaderboardEntry {
+                    rank: idx as u64 + 1,
+                    principal: Principal::from_text(principal)?,
+                    amount: amount.parse()?,
+                    profile: None,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(feature = "ssr", not(feature = "alloydb")))]
+mod mock {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    use super::*;
+
+    pub async fn compute_leaderboard(
+        period: LeaderboardPeriod,
+        kind: LeaderboardKind,
+    ) -> Result<Vec<LeaderboardEntry>, ServerFnError> {
+        let seed = period as u64 * 2 + kind as u64;
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let mut amount = 100_000u64;
+
+        let board = (0..LEADERBOARD_MAX_ENTRIES as u64)
+            .map(|idx| {
+                amount = amount.saturating_sub(rng.random_range(0..200));
+                LeaderboardEntry {
+                    rank: idx + 1,
+                    principal: Principal::self_authenticating(
+                        [seed.to_le_bytes(), idx.to_le_bytes()].concat(),
+                    ),
+                    amount,
+                    profile: None,
+                }
+            })
+            .collect();
+
+        Ok(board)
+    }
+}
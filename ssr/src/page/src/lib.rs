@@ -1,5 +1,6 @@
 #![recursion_limit = "256"]
 pub mod about_us;
+pub mod admin;
 pub mod airdrop;
 pub mod consts;
 pub mod err;
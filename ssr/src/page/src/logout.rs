@@ -6,6 +6,7 @@ use leptos::prelude::*;
 use leptos_router::components::Redirect;
 use leptos_use::storage::use_local_storage;
 use state::canisters::auth_state;
+use utils::app_error::error_path;
 use utils::event_streaming::events::{LogoutClicked, LogoutConfirmation};
 
 #[component]
@@ -35,7 +36,7 @@ pub fn Logout() -> impl IntoView {
                             view! { <Redirect path="/menu" /> }
                         }
                         Err(e) => {
-                            view! { <Redirect path=format!("/error?err={e}") /> }
+                            view! { <Redirect path=error_path(e) /> }
                         }
                     }
                 })}
@@ -19,6 +19,7 @@ use state::app_state::AppState;
 use state::canisters::auth_state;
 use state::canisters::unauth_canisters;
 use state::content_seed_client::ContentSeedClient;
+use utils::app_error::error_path;
 use utils::mixpanel::mixpanel_events::*;
 use utils::send_wrap;
 use yral_canisters_common::utils::profile::ProfileDetails;
@@ -229,7 +230,7 @@ pub fn Menu() -> impl IntoView {
         <Suspense>
             {move || Suspend::new(async move {
                 if let Err(e) = authorized_fetch_res.await {
-                    return Either::Left(view! { <Redirect path=format!("/error?err={e}") /> });
+                    return Either::Left(view! { <Redirect path=error_path(e) /> });
                 }
                 Either::Right(
                     view! {
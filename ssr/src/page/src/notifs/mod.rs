@@ -2,6 +2,7 @@ use leptos::{either::Either, prelude::*};
 use leptos_router::components::Redirect;
 use state::canisters::auth_state;
 
+use utils::app_error::error_path;
 use utils::notifications::get_device_registeration_token;
 use yral_canisters_common::utils::profile::ProfileDetails;
 use yral_metadata_client::MetadataClient;
@@ -54,7 +55,7 @@ pub fn Notif() -> impl IntoView {
                             )
                         }
                         Err(e) => {
-                            Either::Right(view! { <Redirect path=format!("/error?err={e}") /> })
+                            Either::Right(view! { <Redirect path=error_path(e) /> })
                         }
                     }
                 })}
@@ -0,0 +1,423 @@
+use leptos::prelude::*;
+use limits::{CoinState, DEFAULT_BET_COIN_STATE};
+use serde::{Deserialize, Serialize};
+use utils::send_wrap;
+
+const ALL_COINS: [CoinState; 7] = [
+    CoinState::C1,
+    CoinState::C5,
+    CoinState::C10,
+    CoinState::C20,
+    CoinState::C50,
+    CoinState::C100,
+    CoinState::C200,
+];
+
+pub fn coin_from_amount(amount: u64) -> Option<CoinState> {
+    ALL_COINS.into_iter().find(|c| c.to_cents() == amount)
+}
+
+/// Global bet configuration, provided by the server
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BetConfig {
+    /// allowed bet amounts in SATS, sorted ascending
+    pub denominations: Vec<u64>,
+    pub default_amount: u64,
+}
+
+impl Default for BetConfig {
+    fn default() -> Self {
+        Self {
+            denominations: ALL_COINS.iter().map(|c| c.to_cents()).collect(),
+            default_amount: DEFAULT_BET_COIN_STATE.to_cents(),
+        }
+    }
+}
+
+impl BetConfig {
+    pub fn coins(&self) -> Vec<CoinState> {
+        self.denominations
+            .iter()
+            .filter_map(|amt| coin_from_amount(*amt))
+            .collect()
+    }
+
+    pub fn default_coin(&self) -> CoinState {
+        coin_from_amount(self.default_amount).unwrap_or(DEFAULT_BET_COIN_STATE)
+    }
+
+    pub fn is_allowed(&self, amount: u64) -> bool {
+        self.denominations.contains(&amount)
+    }
+
+    /// next allowed coin, wrapping around to the smallest
+    pub fn next_coin(&self, current: CoinState) -> CoinState {
+        let coins = self.coins();
+        let cur = current.to_cents();
+        coins
+            .iter()
+            .find(|c| c.to_cents() > cur)
+            .or(coins.first())
+            .copied()
+            .unwrap_or(current)
+    }
+
+    /// previous allowed coin, wrapping around to the largest
+    pub fn prev_coin(&self, current: CoinState) -> CoinState {
+        let coins = self.coins();
+        let cur = current.to_cents();
+        coins
+            .iter()
+            .rev()
+            .find(|c| c.to_cents() < cur)
+            .or(coins.last())
+            .copied()
+            .unwrap_or(current)
+    }
+}
+
+/// Delay before a looser limit takes effect
+pub const LIMIT_INCREASE_DELAY_SECS: u64 = 24 * 60 * 60;
+
+/// Longest cooling-off break that can be requested at once
+pub const MAX_COOLING_OFF_DAYS: u64 = 365;
+
+/// A raised or removed daily cap, waiting out [LIMIT_INCREASE_DELAY_SECS]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PendingCapChange {
+    pub daily_wager_cap: Option<u64>,
+    /// epoch secs
+    pub effective_at: u64,
+}
+
+/// Self imposed betting limits, set by the user in settings
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct BetLimits {
+    /// maximum SATS that can be wagered in a (UTC) day
+    pub daily_wager_cap: Option<u64>,
+    /// betting is disabled till this time (epoch secs)
+    pub cooling_off_until: Option<u64>,
+    #[serde(default)]
+    pub pending_cap: Option<PendingCapChange>,
+}
+
+impl BetLimits {
+    pub fn cooling_off_active(&self, now_secs: u64) -> bool {
+        self.cooling_off_until
+            .map(|until| now_secs < until)
+            .unwrap_or_default()
+    }
+
+    /// Daily cap in force at `now_secs`, a pending change applies once it's due
+    pub fn daily_cap(&self, now_secs: u64) -> Option<u64> {
+        match &self.pending_cap {
+            Some(pending) if pending.effective_at <= now_secs => pending.daily_wager_cap,
+            _ => self.daily_wager_cap,
+        }
+    }
+
+    /// Lower caps apply right away, raising or removing one waits out the delay
+    pub fn request_daily_cap(&mut self, cap: Option<u64>, now_secs: u64) {
+        let current = self.daily_cap(now_secs);
+        self.daily_wager_cap = current;
+        self.pending_cap = None;
+        let stricter = match (cap, current) {
+            (_, None) => true,
+            (Some(cap), Some(current)) => cap <= current,
+            (None, Some(_)) => false,
+        };
+        if stricter {
+            self.daily_wager_cap = cap;
+        } else {
+            self.pending_cap = Some(PendingCapChange {
+                daily_wager_cap: cap,
+                effective_at: now_secs + LIMIT_INCREASE_DELAY_SECS,
+            });
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct UserBetConfig {
+    pub config: BetConfig,
+    pub limits: BetLimits,
+    /// SATS wagered by the user today
+    pub wagered_today: u64,
+    /// current time as seen by the server (epoch secs)
+    pub now_secs: u64,
+}
+
+impl UserBetConfig {
+    /// Reason why betting `amount` is not allowed, if any
+    pub fn bet_blocked_reason(&self, amount: u64) -> Option<String> {
+        if self.limits.cooling_off_active(self.now_secs) {
+            return Some("You're on a cooling-off break, betting is paused".into());
+        }
+        if let Some(cap) = self.limits.daily_cap(self.now_secs) {
+            if self.wagered_today + amount > cap {
+                return Some(format!("Daily limit of {cap} SATS reached"));
+            }
+        }
+        None
+    }
+}
+
+/// Bet configuration for the current user, shared across all bet overlays
+#[derive(Clone, Copy)]
+pub struct BetConfigCtx {
+    pub config: Resource<UserBetConfig>,
+    refetch: Trigger,
+}
+
+impl BetConfigCtx {
+    /// Provided once at the root, every bet overlay shares the same fetch
+    pub fn init() -> Self {
+        let refetch = Trigger::new();
+        let config = Resource::new(
+            move || refetch.track(),
+            move |_| {
+                send_wrap(async move {
+                    get_bet_config().await.unwrap_or_else(|e| {
+                        log::warn!("failed to fetch bet config {e}");
+                        UserBetConfig::default()
+                    })
+                })
+            },
+        );
+        let this = Self { config, refetch };
+        provide_context(this);
+        this
+    }
+
+    pub fn use_ctx() -> Self {
+        expect_context()
+    }
+
+    pub fn refetch(&self) {
+        self.refetch.notify();
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use auth::server_impl::store::{KVStore, KVStoreImpl};
+    use candid::Principal;
+    use limits::MAX_BET_AMOUNT_SATS;
+    use yral_canisters_common::utils::time::current_epoch;
+
+    use super::*;
+
+    const DAY_SECS: u64 = 24 * 60 * 60;
+    /// Daily wager counters expire after this
+    const WAGERED_TTL_DAYS: u64 = 2;
+
+    fn limits_key(user: Principal) -> String {
+        format!("hon-bet-limits:{user}")
+    }
+
+    fn wagered_key(user: Principal, now_secs: u64) -> String {
+        format!("hon-wagered:{user}:{}", now_secs / DAY_SECS)
+    }
+
+    pub fn now_secs() -> u64 {
+        current_epoch().as_secs()
+    }
+
+    /// Reads the bet configuration from the environment
+    /// `HON_BET_DENOMINATIONS` - comma separated list of allowed bet amounts
+    /// `HON_DEFAULT_BET_AMOUNT` - bet amount selected by default
+    pub fn bet_config() -> BetConfig {
+        let default = BetConfig::default();
+        let mut denominations: Vec<u64> = std::env::var("HON_BET_DENOMINATIONS")
+            .ok()
+            .map(|raw| {
+                raw.split(',')
+                    .filter_map(|amt| amt.trim().parse().ok())
+                    .filter(|amt| *amt <= MAX_BET_AMOUNT_SATS as u64)
+                    .filter(|amt| coin_from_amount(*amt).is_some())
+                    .collect()
+            })
+            .unwrap_or_default();
+        denominations.sort_unstable();
+        denominations.dedup();
+        if denominations.is_empty() {
+            return default;
+        }
+
+        let default_amount = std::env::var("HON_DEFAULT_BET_AMOUNT")
+            .ok()
+            .and_then(|amt| amt.parse().ok())
+            .filter(|amt| denominations.contains(amt))
+            .unwrap_or(denominations[0]);
+
+        BetConfig {
+            denominations,
+            default_amount,
+        }
+    }
+
+    pub async fn bet_limits(kv: &KVStoreImpl, user: Principal) -> Result<BetLimits, ServerFnError> {
+        let Some(raw) = kv.read(limits_key(user)).await? else {
+            return Ok(BetLimits::default());
+        };
+        Ok(serde_json::from_str(&raw)?)
+    }
+
+    pub async fn set_bet_limits(
+        kv: &KVStoreImpl,
+        user: Principal,
+        limits: &BetLimits,
+    ) -> Result<(), ServerFnError> {
+        kv.write(limits_key(user), serde_json::to_string(limits)?)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn wagered_today(
+        kv: &KVStoreImpl,
+        user: Principal,
+        now_secs: u64,
+    ) -> Result<u64, ServerFnError> {
+        let wagered = kv
+            .read(wagered_key(user, now_secs))
+            .await?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default();
+        Ok(wagered)
+    }
+
+    /// A bet counted towards the daily total, released if the bet doesn't go through
+    pub struct WagerReservation {
+        key: String,
+        amount: u64,
+    }
+
+    impl WagerReservation {
+        pub async fn release(self, kv: &KVStoreImpl) -> Result<(), ServerFnError> {
+            kv.increment(self.key, -(self.amount as i64)).await?;
+            Ok(())
+        }
+    }
+
+    /// Adds `amount` to today's total, failing if that goes over `cap`
+    /// the check and the increment are a single atomic step, so parallel
+    /// bets can't slip past the cap together
+    async fn reserve_wager(
+        kv: &KVStoreImpl,
+        user: Principal,
+        amount: u64,
+        cap: Option<u64>,
+        now_secs: u64,
+    ) -> Result<WagerReservation, ServerFnError> {
+        let key = wagered_key(user, now_secs);
+        let total = kv.increment(key.clone(), amount as i64).await?;
+        kv.expire(
+            key.clone(),
+            std::time::Duration::from_secs(WAGERED_TTL_DAYS * DAY_SECS),
+        )
+        .await?;
+
+        let reservation = WagerReservation { key, amount };
+        if let Some(cap) = cap.filter(|cap| total > *cap as i64) {
+            reservation.release(kv).await?;
+            return Err(ServerFnError::new(format!(
+                "Daily limit of {cap} SATS reached"
+            )));
+        }
+        Ok(reservation)
+    }
+
+    pub async fn delete_user_data(kv: &KVStoreImpl, user: Principal) -> Result<(), ServerFnError> {
+        kv.delete(limits_key(user)).await?;
+        let now = now_secs();
+        for day in 0..WAGERED_TTL_DAYS {
+            kv.delete(wagered_key(user, now.saturating_sub(day * DAY_SECS)))
+                .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn user_bet_config(
+        kv: &KVStoreImpl,
+        user: Option<Principal>,
+    ) -> Result<UserBetConfig, ServerFnError> {
+        let now = now_secs();
+        let (limits, wagered_today) = if let Some(user) = user {
+            (
+                bet_limits(kv, user).await?,
+                wagered_today(kv, user, now).await?,
+            )
+        } else {
+            Default::default()
+        };
+
+        Ok(UserBetConfig {
+            config: bet_config(),
+            limits,
+            wagered_today,
+            now_secs: now,
+        })
+    }
+
+    /// Validates the bet against the global config and the user's limits
+    /// and reserves it in today's total
+    pub async fn reserve_bet(
+        kv: &KVStoreImpl,
+        user: Principal,
+        amount: u64,
+    ) -> Result<WagerReservation, ServerFnError> {
+        if !bet_config().is_allowed(amount) {
+            return Err(ServerFnError::new(format!(
+                "bet amount not allowed: {amount}"
+            )));
+        }
+        let now = now_secs();
+        let limits = bet_limits(kv, user).await?;
+        if limits.cooling_off_active(now) {
+            return Err(ServerFnError::new(
+                "You're on a cooling-off break, betting is paused",
+            ));
+        }
+        reserve_wager(kv, user, amount, limits.daily_cap(now), now).await
+    }
+}
+
+#[server(endpoint = "bet_config", input = server_fn::codec::Json)]
+pub async fn get_bet_config() -> Result<UserBetConfig, ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let kv: KVStoreImpl = expect_context();
+    let user = extract_principal_impl().await?;
+    server::user_bet_config(&kv, user).await
+}
+
+/// Updates the caller's self imposed limits
+/// cooling-off periods can only be extended, never shortened, and a higher
+/// or removed daily cap only applies after [LIMIT_INCREASE_DELAY_SECS]
+#[server(endpoint = "set_bet_limits", input = server_fn::codec::Json)]
+pub async fn update_bet_limits(
+    daily_wager_cap: Option<u64>,
+    cooling_off_days: Option<u64>,
+) -> Result<BetLimits, ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let kv: KVStoreImpl = expect_context();
+    let user = extract_principal_impl()
+        .await?
+        .ok_or_else(|| ServerFnError::new("not logged in"))?;
+
+    let now = server::now_secs();
+    let mut limits = server::bet_limits(&kv, user).await?;
+    limits.request_daily_cap(daily_wager_cap.filter(|cap| *cap > 0), now);
+    if let Some(days) = cooling_off_days.filter(|days| *days > 0) {
+        let days = days.min(MAX_COOLING_OFF_DAYS);
+        let until = now.saturating_add(days.saturating_mul(24 * 60 * 60));
+        limits.cooling_off_until = Some(limits.cooling_off_until.unwrap_or_default().max(until));
+    } else if !limits.cooling_off_active(now) {
+        limits.cooling_off_until = None;
+    }
+
+    server::set_bet_limits(&kv, user, &limits).await?;
+
+    Ok(limits)
+}
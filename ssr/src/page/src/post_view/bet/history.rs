@@ -0,0 +1,257 @@
+use leptos::prelude::*;
+use leptos_icons::*;
+use serde::{Deserialize, Serialize};
+use state::hn_bet_state::{HnBetState, VideoComparisonRecord, VideoComparisonResult};
+use utils::send_wrap;
+
+/// Maximum number of comparisons kept per user and per video
+pub const SCORE_HISTORY_MAX_RECORDS: usize = 50;
+
+/// Score history shown for a single video
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct VideoScoreHistory {
+    /// Latest comparisons of the video by every voter, oldest first
+    pub comparisons: Vec<VideoComparisonRecord>,
+    /// The caller's latest result on the video
+    pub own_latest: Option<VideoComparisonResult>,
+}
+
+const CHART_WIDTH: f32 = 300.0;
+const CHART_HEIGHT: f32 = 140.0;
+const CHART_PADDING: f32 = 12.0;
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use auth::server_impl::store::{KVStore, KVStoreImpl};
+    use candid::Principal;
+
+    use super::*;
+
+    /// Every comparison of the user across videos, scored by `compared_at`
+    fn history_key(user: Principal) -> String {
+        format!("hon-score-history:{user}")
+    }
+
+    /// Every comparison of the video by any voter, scored by `compared_at`
+    fn video_history_key(video_uid: &str) -> String {
+        format!("hon-video-score-history:{video_uid}")
+    }
+
+    /// Latest records of the index, oldest first
+    async fn latest_records(
+        kv: &KVStoreImpl,
+        key: String,
+    ) -> Result<Vec<VideoComparisonRecord>, ServerFnError> {
+        let mut history = kv
+            .index_range(key, 0, SCORE_HISTORY_MAX_RECORDS, true)
+            .await?
+            .into_iter()
+            .map(|raw| serde_json::from_str(&raw))
+            .collect::<Result<Vec<VideoComparisonRecord>, _>>()?;
+        history.reverse();
+        Ok(history)
+    }
+
+    /// Latest comparisons of the user, oldest first
+    pub async fn score_history(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<Vec<VideoComparisonRecord>, ServerFnError> {
+        latest_records(kv, history_key(user)).await
+    }
+
+    /// Latest comparisons of the video, oldest first
+    pub async fn video_score_history(
+        kv: &KVStoreImpl,
+        video_uid: &str,
+    ) -> Result<Vec<VideoComparisonRecord>, ServerFnError> {
+        latest_records(kv, video_history_key(video_uid)).await
+    }
+
+    pub async fn record_comparison(
+        kv: &KVStoreImpl,
+        user: Principal,
+        record: VideoComparisonRecord,
+    ) -> Result<(), ServerFnError> {
+        let raw = serde_json::to_string(&record)?;
+        for key in [history_key(user), video_history_key(&record.video_uid)] {
+            kv.index_add(key.clone(), raw.clone(), record.compared_at as f64)
+                .await?;
+            kv.index_trim(key, SCORE_HISTORY_MAX_RECORDS).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn delete_user_data(kv: &KVStoreImpl, user: Principal) -> Result<(), ServerFnError> {
+        kv.index_delete(history_key(user)).await?;
+        Ok(())
+    }
+}
+
+#[server(endpoint = "score_history", input = server_fn::codec::Json)]
+pub async fn fetch_score_history(video_uid: String) -> Result<VideoScoreHistory, ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let kv: KVStoreImpl = expect_context();
+    let comparisons = server::video_score_history(&kv, &video_uid).await?;
+    let own_latest = match extract_principal_impl().await? {
+        Some(user) => server::score_history(&kv, user)
+            .await?
+            .into_iter()
+            .rev()
+            .find(|r| r.video_uid == video_uid)
+            .map(|r| r.result),
+        None => None,
+    };
+    Ok(VideoScoreHistory {
+        comparisons,
+        own_latest,
+    })
+}
+
+fn chart_points(scores: impl Iterator<Item = f32>, count: usize, max: f32) -> String {
+    let step = if count > 1 {
+        (CHART_WIDTH - 2.0 * CHART_PADDING) / (count - 1) as f32
+    } else {
+        0.0
+    };
+    scores
+        .enumerate()
+        .map(|(idx, score)| {
+            let x = CHART_PADDING + step * idx as f32;
+            let y =
+                CHART_HEIGHT - CHART_PADDING - (score / max) * (CHART_HEIGHT - 2.0 * CHART_PADDING);
+            format!("{x:.1},{y:.1}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[component]
+fn ScoreChart(history: Vec<VideoComparisonRecord>) -> impl IntoView {
+    let count = history.len();
+    let max = history
+        .iter()
+        .flat_map(|r| [r.result.current_video_score, r.result.previous_video_score])
+        .fold(1.0f32, f32::max);
+
+    let current = chart_points(
+        history.iter().map(|r| r.result.current_video_score),
+        count,
+        max,
+    );
+    let previous = chart_points(
+        history.iter().map(|r| r.result.previous_video_score),
+        count,
+        max,
+    );
+
+    view! {
+        <svg class="w-full" viewBox=format!("0 0 {CHART_WIDTH} {CHART_HEIGHT}")>
+            <polyline points=previous fill="none" stroke="#A3A3A3" stroke-width="2" stroke-dasharray="4 3" />
+            <polyline points=current fill="none" stroke="#E2017B" stroke-width="2" />
+        </svg>
+        <div class="flex flex-row gap-4 justify-center text-xs text-neutral-300">
+            <span class="flex gap-1 items-center">
+                <span class="w-3 h-0.5 bg-primary-600"></span>
+                Voted video
+            </span>
+            <span class="flex gap-1 items-center">
+                <span class="w-3 h-0.5 bg-neutral-400"></span>
+                Compared video
+            </span>
+        </div>
+    }
+}
+
+#[component]
+fn ScoreHistoryRow(record: VideoComparisonRecord) -> impl IntoView {
+    let current = record.result.current_video_score.round() as u32;
+    let previous = record.result.previous_video_score.round() as u32;
+    let (verdict, verdict_class) = if record.result.hot_or_not {
+        ("Hot", "text-[#158F5C]")
+    } else {
+        ("Not", "text-[#F14331]")
+    };
+
+    view! {
+        <div class="flex flex-row justify-between items-center py-2 w-full text-sm border-b border-neutral-800">
+            <span class="font-semibold">{current}</span>
+            <span class="text-neutral-400">vs</span>
+            <span class="font-semibold">{previous}</span>
+            <span class=format!("font-bold {verdict_class}")>{verdict}</span>
+        </div>
+    }
+}
+
+/// Shows how the score of `video_uid` moved against the videos it was
+/// compared to
+#[component]
+pub fn ScoreChartPanel(video_uid: String) -> impl IntoView {
+    let video_uid = StoredValue::new(video_uid);
+    let history = Resource::new(
+        move || (),
+        move |_| send_wrap(fetch_score_history(video_uid.get_value())),
+    );
+
+    // restore the caller's latest result for this video after navigation
+    Effect::new(move || {
+        let Some(Ok(history)) = history.get() else {
+            return;
+        };
+        let video_uid = video_uid.get_value();
+        if untrack(|| HnBetState::get(video_uid.clone())).is_some() {
+            return;
+        }
+        if let Some(latest) = history.own_latest {
+            HnBetState::set(video_uid, latest);
+        }
+    });
+
+    view! {
+        <div class="flex flex-col gap-4 w-full text-white">
+            <div class="flex flex-row gap-2 justify-center items-center">
+                <Icon attr:class="text-xl" icon=icondata::AiLineChartOutlined />
+                <span class="text-lg font-bold">Score history</span>
+            </div>
+            <Suspense fallback=|| {
+                view! { <div class="w-full h-36 rounded-lg animate-pulse bg-white/10"></div> }
+            }>
+                {move || Suspend::new(async move {
+                    match history.await {
+                        Ok(VideoScoreHistory { comparisons, .. }) if !comparisons.is_empty() => {
+                            view! {
+                                <ScoreChart history=comparisons.clone() />
+                                <div class="flex flex-col w-full">
+                                    {comparisons
+                                        .into_iter()
+                                        .rev()
+                                        .map(|record| view! { <ScoreHistoryRow record /> })
+                                        .collect_view()}
+                                </div>
+                            }
+                                .into_any()
+                        }
+                        Ok(_) => {
+                            view! {
+                                <span class="text-sm text-center text-neutral-400">
+                                    "No rounds played on this video yet"
+                                </span>
+                            }
+                                .into_any()
+                        }
+                        Err(e) => {
+                            log::warn!("failed to load score history {e}");
+                            view! {
+                                <span class="text-sm text-center text-neutral-400">
+                                    Failed to load score history
+                                </span>
+                            }
+                                .into_any()
+                        }
+                    }
+                })}
+            </Suspense>
+        </div>
+    }
+}
@@ -1,8 +1,11 @@
+pub mod config;
+pub mod history;
 mod server_impl;
 
-use codee::string::{FromToStringCodec, JsonSerdeCodec};
+use codee::string::JsonSerdeCodec;
 use component::{bullet_loader::BulletLoader, hn_icons::*, show_any::ShowAny, spinner::SpinnerFit};
-use consts::{UserOnboardingStore, USER_ONBOARDING_STORE_KEY, WALLET_BALANCE_STORE_KEY};
+use config::{coin_from_amount, BetConfigCtx};
+use consts::{UserOnboardingStore, USER_ONBOARDING_STORE_KEY};
 use hon_worker_common::{
     sign_vote_request_v3, GameInfo, GameInfoReqV3, GameResult, GameResultV2, VoteRequestV3,
     VoteResV2, WORKER_URL,
@@ -13,13 +16,17 @@ use leptos::prelude::*;
 use leptos_icons::*;
 use leptos_use::storage::use_local_storage;
 use limits::{CoinState, DEFAULT_BET_COIN_STATE};
-use num_traits::cast::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use server_impl::vote_with_cents_on_post;
+use state::balance_store::{BalanceKey, BalanceStore};
 use state::canisters::auth_state;
 use state::hn_bet_state::{HnBetState, VideoComparisonResult};
 use utils::try_or_redirect_opt;
-use utils::{mixpanel::mixpanel_events::*, send_wrap};
+use utils::{
+    analytics::{dispatch, AnalyticsEvent, GameStake, VideoProps},
+    mixpanel::mixpanel_events::*,
+    send_wrap,
+};
 use yral_canisters_common::utils::{
     posts::PostDetails, token::balance::TokenBalance, vote::VoteKind,
 };
@@ -92,7 +99,12 @@ fn HNButtonOverlay(
     audio_ref: NodeRef<Audio>,
 ) -> impl IntoView {
     let auth = auth_state();
-    let is_connected = auth.is_logged_in_with_oauth();
+    let ev_ctx = auth.event_ctx();
+    let bet_config = BetConfigCtx::use_ctx();
+    let balance_store = BalanceStore::use_store();
+    let user_config = Signal::derive(move || bet_config.config.get().unwrap_or_default());
+    let blocked_reason =
+        Signal::derive(move || user_config.with(|c| c.bet_blocked_reason(coin().to_cents())));
 
     fn play_win_sound_and_vibrate(audio_ref: NodeRef<Audio>, won: bool) {
         #[cfg(not(feature = "hydrate"))]
@@ -144,38 +156,31 @@ fn HNButtonOverlay(
             };
             let prev_post = prev_post.as_ref().map(|p| (p.canister_id, p.post_id));
 
-            let post_mix = post.clone();
+            let video = VideoProps::from(&post);
+            let stake = GameStake {
+                stake_amount: bet_amount,
+                stake_type: StakeType::Sats,
+                option_chosen: bet_direction.into(),
+            };
             send_wrap(async move {
                 let cans = auth.auth_cans(expect_context()).await.ok()?;
-                let is_logged_in = is_connected.get_untracked();
-                let global = MixpanelGlobalProps::try_get(&cans, is_logged_in);
-                MixPanelEvent::track_game_clicked(MixpanelGameClickedProps {
-                    is_nsfw: post_mix.is_nsfw,
-                    user_id: global.user_id,
-                    visitor_id: global.visitor_id,
-                    is_logged_in: global.is_logged_in,
-                    canister_id: global.canister_id,
-                    is_nsfw_enabled: global.is_nsfw_enabled,
-                    game_type: MixpanelPostGameType::HotOrNot,
-                    option_chosen: bet_direction.into(),
-                    publisher_user_id: post_mix.poster_principal.to_text(),
-                    video_id: post_mix.uid.clone(),
-                    view_count: post_mix.views,
-                    like_count: post_mix.likes,
-                    stake_amount: bet_amount,
-                    is_game_enabled: true,
-                    stake_type: StakeType::Sats,
-                });
+                dispatch(
+                    ev_ctx,
+                    AnalyticsEvent::GameVoted {
+                        video: video.clone(),
+                        stake: stake.clone(),
+                    },
+                );
                 let identity = cans.identity();
                 let sender = identity.sender().unwrap();
                 let sig = sign_vote_request_v3(identity, req_v3).ok()?;
 
+                balance_store.debit(BalanceKey::Sats, bet_amount);
                 let res = vote_with_cents_on_post(sender, req, sig, prev_post).await;
                 refetch_bet.notify();
+                bet_config.refetch();
                 match res {
                     Ok(res) => {
-                        let is_logged_in = is_connected.get_untracked();
-                        let global = MixpanelGlobalProps::try_get(&cans, is_logged_in);
                         let game_conclusion = match res.game_result.game_result {
                             GameResultV2::Win { .. } => GameConclusion::Win,
                             GameResultV2::Loss { .. } => GameConclusion::Loss,
@@ -191,42 +196,34 @@ fn HNButtonOverlay(
                             } => TokenBalance::new((lose_amt + 0u64).into(), 0).humanize(),
                         };
 
-                        let (_, set_wallet_balalnce_store, _) =
-                            use_local_storage::<u64, FromToStringCodec>(WALLET_BALANCE_STORE_KEY);
-
-                        HnBetState::set(post_mix.uid.clone(), res.video_comparison_result);
+                        HnBetState::set(video.video_id.clone(), res.video_comparison_result);
 
-                        set_wallet_balalnce_store.set(match res.game_result.game_result.clone() {
+                        let updated_balance = match res.game_result.game_result.clone() {
                             GameResultV2::Win {
                                 win_amt: _,
                                 updated_balance,
-                            } => updated_balance.to_u64().unwrap_or(0),
+                            } => updated_balance,
                             GameResultV2::Loss {
                                 lose_amt: _,
                                 updated_balance,
-                            } => updated_balance.to_u64().unwrap_or(0),
-                        });
-
-                        MixPanelEvent::track_game_played(MixpanelGamePlayedProps {
-                            is_nsfw: post_mix.is_nsfw,
-                            user_id: global.user_id,
-                            visitor_id: global.visitor_id,
-                            is_logged_in: global.is_logged_in,
-                            canister_id: global.canister_id,
-                            is_nsfw_enabled: global.is_nsfw_enabled,
-                            game_type: MixpanelPostGameType::HotOrNot,
-                            option_chosen: bet_direction.into(),
-                            publisher_user_id: post_mix.poster_principal.to_text(),
-                            video_id: post_mix.uid.clone(),
-                            view_count: post_mix.views,
-                            like_count: post_mix.likes,
-                            stake_amount: bet_amount,
-                            is_game_enabled: true,
-                            stake_type: StakeType::Sats,
-                            conclusion: game_conclusion,
-                            won_loss_amount: win_loss_amount,
-                            creator_commision_percentage: crate::consts::CREATOR_COMMISION_PERCENT,
-                        });
+                            } => updated_balance,
+                        };
+                        balance_store.confirm(
+                            BalanceKey::Sats,
+                            TokenBalance::new(updated_balance.into(), 0),
+                        );
+
+                        dispatch(
+                            ev_ctx,
+                            AnalyticsEvent::GamePlayed {
+                                video,
+                                stake,
+                                conclusion: game_conclusion,
+                                won_loss_amount: win_loss_amount,
+                                creator_commision_percentage:
+                                    crate::consts::CREATOR_COMMISION_PERCENT,
+                            },
+                        );
                         play_win_sound_and_vibrate(
                             audio_ref,
                             matches!(res.game_result.game_result, GameResultV2::Win { .. }),
@@ -235,6 +232,8 @@ fn HNButtonOverlay(
                     }
                     Err(e) => {
                         log::error!("{e}");
+                        // revert the optimistic debit
+                        balance_store.credit(BalanceKey::Sats, bet_amount);
                         None
                     }
                 }
@@ -242,10 +241,20 @@ fn HNButtonOverlay(
         });
 
     let running = place_bet_action.pending();
+    let bet_disabled = Signal::derive(move || running() || blocked_reason.with(|r| r.is_some()));
+    let next_coin = move |_| coin.update(|c| *c = user_config.with(|cfg| cfg.config.next_coin(*c)));
+    let prev_coin = move |_| coin.update(|c| *c = user_config.with(|cfg| cfg.config.prev_coin(*c)));
 
     view! {
+        <Show when=move || blocked_reason.with(|r| r.is_some())>
+            <div class="flex justify-center w-full">
+                <span class="py-1 px-3 text-sm font-medium text-center text-white rounded-full bg-black/60">
+                    {move || blocked_reason.get().unwrap_or_default()}
+                </span>
+            </div>
+        </Show>
         <div class="flex justify-center w-full touch-manipulation">
-            <button disabled=running on:click=move |_| coin.update(|c| *c = c.wrapping_next())>
+            <button disabled=running on:click=next_coin>
                 <Icon
                     attr:class="justify-self-end text-2xl text-white"
                     icon=icondata::AiUpOutlined
@@ -253,22 +262,22 @@ fn HNButtonOverlay(
             </button>
         </div>
         <div class="flex flex-row gap-6 justify-center items-center w-full touch-manipulation">
-            <HNButton disabled=running bet_direction kind=VoteKind::Hot place_bet_action />
-            <button disabled=running on:click=move |_| coin.update(|c| *c = c.wrapping_next())>
+            <HNButton disabled=bet_disabled bet_direction kind=VoteKind::Hot place_bet_action />
+            <button disabled=running on:click=next_coin>
                 <CoinStateView
                     disabled=running
                     class="w-12 h-12 md:w-14 md:h-14 lg:w-16 lg:h-16 drop-shadow-lg"
                     coin
                 />
             </button>
-            <HNButton disabled=running bet_direction kind=VoteKind::Not place_bet_action />
+            <HNButton disabled=bet_disabled bet_direction kind=VoteKind::Not place_bet_action />
         </div>
         // Bottom row: Hot <down arrow> Not
         // most of the CSS is for alignment with above icons
         <div class="flex gap-6 justify-center items-center pt-2 w-full text-base font-medium text-center md:text-lg lg:text-xl touch-manipulation">
             <p class="w-14 md:w-16 lg:w-18">Hot</p>
             <div class="flex justify-center w-12 md:w-14 lg:w-16">
-                <button disabled=running on:click=move |_| coin.update(|c| *c = c.wrapping_prev())>
+                <button disabled=running on:click=prev_coin>
                     <Icon attr:class="text-2xl text-white" icon=icondata::AiDownOutlined />
                 </button>
             </div>
@@ -314,7 +323,6 @@ fn HNWonLost(
     vote_amount: u64,
     bet_direction: RwSignal<Option<VoteKind>>,
     show_tutorial: RwSignal<bool>,
-    video_uid: String,
     post: PostDetails,
 ) -> impl IntoView {
     let auth = auth_state();
@@ -351,19 +359,10 @@ fn HNWonLost(
     };
 
     let bet_amount = vote_amount;
-    let coin = match bet_amount {
-        1 => CoinState::C1,
-        5 => CoinState::C5,
-        10 => CoinState::C10,
-        20 => CoinState::C20,
-        50 => CoinState::C50,
-        100 => CoinState::C100,
-        200 => CoinState::C200,
-        amt => {
-            log::warn!("Invalid bet amount: {amt}, using fallback");
-            CoinState::C50
-        }
-    };
+    let coin = coin_from_amount(bet_amount).unwrap_or_else(|| {
+        log::warn!("Invalid bet amount: {bet_amount}, using fallback");
+        CoinState::C50
+    });
 
     let vote_kind_image = match bet_direction.get() {
         Some(VoteKind::Hot) => "/img/hotornot/hot-circular.svg",
@@ -381,11 +380,13 @@ fn HNWonLost(
         }
     });
 
-    let (wallet_balance_store, _, _) =
-        use_local_storage::<u64, FromToStringCodec>(WALLET_BALANCE_STORE_KEY);
+    let sats_balance = BalanceStore::use_store().balance(BalanceKey::Sats);
 
     let total_balance_text = move || {
-        let balance = wallet_balance_store.get();
+        let balance = sats_balance
+            .get()
+            .map(|b| b.humanize())
+            .unwrap_or_else(|| "0".into());
         format!("Total balance: {balance} SATS")
     };
 
@@ -399,26 +400,24 @@ fn HNWonLost(
     let conclusion_cloned = conclusion.clone();
     let vote_amount_cloned = vote_amount;
 
+    let tutorial_video = VideoProps::from(&post);
     let tutorial_action = Action::new(move |_| {
-        let video_id = video_uid.clone();
+        let video = tutorial_video.clone();
         let conclusion = conclusion_cloned.clone();
         let vote_amount = vote_amount_cloned;
         async move {
-            if let Some(global) = MixpanelGlobalProps::from_ev_ctx(event_ctx) {
-                MixPanelEvent::track_how_to_play_clicked(MixpanelHowToPlayGameClickedProps {
-                    user_id: global.user_id,
-                    game_type: MixpanelPostGameType::HotOrNot,
-                    video_id,
-                    visitor_id: global.visitor_id,
-                    is_logged_in: global.is_logged_in,
-                    canister_id: global.canister_id,
-                    is_nsfw_enabled: global.is_nsfw_enabled,
-                    stake_amount: vote_amount,
-                    stake_type: StakeType::Sats,
-                    option_chosen: bet_direction.get().unwrap_or(VoteKind::Hot).into(),
+            dispatch(
+                event_ctx,
+                AnalyticsEvent::HowToPlayClicked {
+                    video,
+                    stake: GameStake {
+                        stake_amount: vote_amount,
+                        stake_type: StakeType::Sats,
+                        option_chosen: bet_direction.get().unwrap_or(VoteKind::Hot).into(),
+                    },
                     conclusion,
-                });
-            }
+                },
+            );
         }
     });
 
@@ -516,12 +515,10 @@ pub fn HNUserParticipation(
     let vote_amount: u64 = vote_amount
         .try_into()
         .expect("We only allow voting with 200 max, so this is alright");
-    let video_uid = post.uid.clone();
-
     let post = post.clone();
 
     view! {
-        <HNWonLost game_result vote_amount bet_direction show_tutorial video_uid post />
+        <HNWonLost game_result vote_amount bet_direction show_tutorial post />
         <ShadowBg />
     }
 }
@@ -553,6 +550,18 @@ pub fn HNGameOverlay(
 ) -> impl IntoView {
     let bet_direction = RwSignal::new(None::<VoteKind>);
     let coin = RwSignal::new(DEFAULT_BET_COIN_STATE);
+    let bet_config = BetConfigCtx::use_ctx();
+    // select the configured default coin once the config is loaded
+    Effect::new(move |applied: Option<bool>| {
+        if applied == Some(true) {
+            return true;
+        }
+        let Some(user_config) = bet_config.config.get() else {
+            return false;
+        };
+        coin.set(user_config.config.default_coin());
+        true
+    });
 
     let refetch_bet = Trigger::new();
     let post = StoredValue::new(post);
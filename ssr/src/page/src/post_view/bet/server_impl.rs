@@ -27,7 +27,21 @@ pub async fn vote_with_cents_on_post(
         )));
     }
 
-    vote_with_cents_on_post(sender, req, sig, prev_video_info).await
+    use super::config::server::reserve_bet;
+    use auth::server_impl::store::KVStoreImpl;
+
+    let kv: KVStoreImpl = expect_context();
+    let reservation = reserve_bet(&kv, sender, req.vote_amount as u64).await?;
+
+    match vote_with_cents_on_post(sender, req, sig, prev_video_info).await {
+        Ok(res) => Ok(res),
+        Err(e) => {
+            if let Err(release_err) = reservation.release(&kv).await {
+                log::warn!("failed to release wager for {sender}: {release_err}");
+            }
+            Err(e)
+        }
+    }
 }
 
 #[cfg(feature = "alloydb")]
@@ -35,8 +49,13 @@ mod alloydb {
     use crate::post_view::bet::{VideoComparisonResult, VoteAPIRes};
 
     use super::*;
+    use crate::post_view::bet::history::server::record_comparison;
+    use crate::post_view::tags::server::index_post;
+    use auth::server_impl::store::KVStoreImpl;
     use hon_worker_common::WORKER_URL;
     use hon_worker_common::{HoNGameVoteReqV3, HotOrNot, VoteRequestV3, VoteResV2};
+    use state::hn_bet_state::VideoComparisonRecord;
+    use yral_canisters_common::utils::time::current_epoch;
     pub async fn vote_with_cents_on_post(
         sender: Principal,
         req: VoteRequest,
@@ -54,15 +73,19 @@ mod alloydb {
         else {
             return Err(ServerFnError::new("post not found"));
         };
-        let prev_uid_formatted = if let Some((canister_id, post_id)) = prev_video_info {
+        let prev_uid = if let Some((canister_id, post_id)) = prev_video_info {
             let details = cans
                 .get_post_details(canister_id, post_id)
                 .await?
                 .ok_or_else(|| ServerFnError::new("previous post not found"))?;
-            format!("'{}'", details.uid)
+            Some(details.uid)
         } else {
-            "NULL".to_string()
+            None
         };
+        let prev_uid_formatted = prev_uid
+            .as_ref()
+            .map(|uid| format!("'{uid}'"))
+            .unwrap_or_else(|| "NULL".to_string());
 
         // sanitization is not required here, as get_post_details verifies that the post is valid
         // and exists on cloudflare
@@ -134,6 +157,21 @@ mod alloydb {
 
         let vote_res: VoteResV2 = res.json().await?;
 
+        let kv: KVStoreImpl = expect_context();
+        if let Err(e) = index_post(&kv, &post_info).await {
+            log::warn!("failed to index hashtags of post {}: {e}", post_info.uid);
+        }
+
+        let record = VideoComparisonRecord {
+            video_uid: post_info.uid.clone(),
+            result: video_comparison_result.clone(),
+            previous_video_uid: prev_uid,
+            compared_at: current_epoch().as_secs(),
+        };
+        if let Err(e) = record_comparison(&kv, sender, record).await {
+            log::warn!("failed to record score history for {sender}: {e}");
+        }
+
         Ok(VoteAPIRes {
             game_result: vote_res,
             video_comparison_result,
@@ -143,17 +181,21 @@ mod alloydb {
 
 #[cfg(not(feature = "alloydb"))]
 mod mock {
+    use auth::server_impl::store::KVStoreImpl;
     use hon_worker_common::{GameResultV2, VoteResV2};
-    use state::hn_bet_state::VideoComparisonResult;
+    use state::hn_bet_state::{VideoComparisonRecord, VideoComparisonResult};
+    use yral_canisters_common::{utils::time::current_epoch, Canisters};
 
     use super::*;
+    use crate::post_view::bet::history::server::record_comparison;
+    use crate::post_view::tags::server::index_post;
 
     #[allow(dead_code)]
     pub async fn vote_with_cents_on_post(
-        _sender: Principal,
-        _req: VoteRequest,
+        sender: Principal,
+        req: VoteRequest,
         _sig: Signature,
-        _prev_video_info: Option<(Principal, u64)>,
+        prev_video_info: Option<(Principal, u64)>,
     ) -> Result<VoteAPIRes, ServerFnError> {
         let game_result = VoteResV2 {
             game_result: GameResultV2::Win {
@@ -161,13 +203,42 @@ mod mock {
                 updated_balance: 0u32.into(),
             },
         };
+        let video_comparison_result = VideoComparisonResult {
+            hot_or_not: true,
+            current_video_score: 50.0,
+            previous_video_score: 10.0,
+        };
+
+        let cans: Canisters<false> = expect_context();
+        if let Some(post_info) = cans
+            .get_post_details(req.post_canister, req.post_id)
+            .await?
+        {
+            let kv: KVStoreImpl = expect_context();
+            if let Err(e) = index_post(&kv, &post_info).await {
+                log::warn!("failed to index hashtags of post {}: {e}", post_info.uid);
+            }
+            let previous_video_uid = match prev_video_info {
+                Some((canister_id, post_id)) => cans
+                    .get_post_details(canister_id, post_id)
+                    .await?
+                    .map(|details| details.uid),
+                None => None,
+            };
+            let record = VideoComparisonRecord {
+                video_uid: post_info.uid,
+                result: video_comparison_result.clone(),
+                previous_video_uid,
+                compared_at: current_epoch().as_secs(),
+            };
+            if let Err(e) = record_comparison(&kv, sender, record).await {
+                log::warn!("failed to record score history for {sender}: {e}");
+            }
+        }
+
         Ok(VoteAPIRes {
             game_result,
-            video_comparison_result: VideoComparisonResult {
-                hot_or_not: true,
-                current_video_score: 50.0,
-                previous_video_score: 10.0,
-            },
+            video_comparison_result,
         })
     }
 }
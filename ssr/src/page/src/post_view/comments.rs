@@ -0,0 +1,1094 @@
+use candid::Principal;
+use leptos::html;
+use leptos::prelude::*;
+use leptos_icons::*;
+use serde::{Deserialize, Serialize};
+use state::canisters::auth_state;
+use utils::{report::ReportOption, send_wrap, text_entities, time::time_ago};
+use yral_canisters_common::{
+    cursored_data::{CursoredDataProvider, KeyedData, PageEntry},
+    utils::time::current_epoch,
+};
+
+use component::infinite_scroller::InfiniteScroller;
+
+use super::overlay::entity_view;
+
+pub const MAX_COMMENT_LEN: usize = 500;
+/// Comments with more links than this are rejected as spam
+pub const MAX_COMMENT_LINKS: usize = 2;
+/// Comments are hidden once reported by this many different logged in users
+pub const REPORTS_TO_HIDE: usize = 3;
+/// Most comments returned for a single page
+pub const MAX_COMMENTS_PAGE: usize = 50;
+const COMMENTS_CHUNK_SZ: usize = 15;
+const REPLIES_CHUNK_SZ: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+pub enum CommentError {
+    #[error("Login required to comment")]
+    LoginRequired,
+    #[error("Comment can't be empty")]
+    Empty,
+    #[error("Comment can be at most {MAX_COMMENT_LEN} characters")]
+    TooLong,
+    #[error("Comment not found")]
+    NotFound,
+    #[error("Only the author can delete this comment")]
+    NotAuthor,
+    #[error("Invalid report reason")]
+    InvalidReason,
+    #[error("Your comment was not accepted: {0}")]
+    Rejected(String),
+}
+
+/// A comment as shown to the caller
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Comment {
+    pub id: String,
+    /// replies are one level deep, always pointing at a top level comment
+    pub parent_id: Option<String>,
+    pub author: Principal,
+    pub author_name: String,
+    pub author_pic: String,
+    pub text: String,
+    /// epoch secs
+    pub created_at: u64,
+    pub likes: usize,
+    /// liked by the caller
+    pub liked: bool,
+    pub reply_count: usize,
+    /// deleted by its author, kept as a placeholder while it has replies
+    pub deleted: bool,
+}
+
+impl KeyedData for Comment {
+    type Key = String;
+
+    fn key(&self) -> Self::Key {
+        self.id.clone()
+    }
+}
+
+pub fn validate_comment(text: &str) -> Result<String, CommentError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(CommentError::Empty);
+    }
+    if text.chars().count() > MAX_COMMENT_LEN {
+        return Err(CommentError::TooLong);
+    }
+    Ok(text.to_string())
+}
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use auth::server_impl::store::{KVStore, KVStoreImpl};
+    use candid::Principal;
+    use leptos::prelude::ServerFnError;
+    use utils::text_entities::{TextSegment, MAX_MENTIONS};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct CommentReport {
+        pub reporter: Principal,
+        pub reason: String,
+        /// epoch secs
+        pub reported_at: u64,
+        /// reporter was logged in with OAuth, only these reports count towards hiding
+        pub logged_in: bool,
+    }
+
+    /// Likes, replies and reports live in their own indexes so concurrent
+    /// updates never rewrite the comment itself
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct StoredComment {
+        pub id: String,
+        pub canister_id: Principal,
+        pub post_id: u64,
+        pub parent_id: Option<String>,
+        pub author: Principal,
+        pub author_name: String,
+        pub author_pic: String,
+        pub text: String,
+        pub created_at: u64,
+        pub deleted: bool,
+        /// hidden by moderation
+        pub hidden: bool,
+    }
+
+    impl StoredComment {
+        pub async fn to_comment(
+            &self,
+            kv: &KVStoreImpl,
+            caller: Option<Principal>,
+        ) -> Result<Comment, ServerFnError> {
+            let liked = match caller {
+                Some(caller) => kv
+                    .index_score(likes_key(&self.id), caller.to_text())
+                    .await?
+                    .is_some(),
+                None => false,
+            };
+            Ok(Comment {
+                id: self.id.clone(),
+                parent_id: self.parent_id.clone(),
+                author: self.author,
+                author_name: self.author_name.clone(),
+                author_pic: self.author_pic.clone(),
+                text: if self.deleted {
+                    String::new()
+                } else {
+                    self.text.clone()
+                },
+                created_at: self.created_at,
+                likes: kv.index_len(likes_key(&self.id)).await?,
+                liked,
+                reply_count: kv.index_len(replies_key(&self.id)).await?,
+                deleted: self.deleted,
+            })
+        }
+    }
+
+    /// Top level comment ids of a post, scored by creation time
+    fn post_comments_key(canister_id: Principal, post_id: u64) -> String {
+        format!("comment-ids:{canister_id}:{post_id}")
+    }
+
+    /// Reply ids of a top level comment, scored by creation time
+    fn replies_key(id: &str) -> String {
+        format!("comment-replies:{id}")
+    }
+
+    /// Principals that liked a comment, scored by like time
+    fn likes_key(id: &str) -> String {
+        format!("comment-likes:{id}")
+    }
+
+    /// Principals that reported a comment, scored by report time
+    fn reporters_key(id: &str) -> String {
+        format!("comment-reporters:{id}")
+    }
+
+    /// Reporters of a comment that were logged in with OAuth
+    fn logged_in_reporters_key(id: &str) -> String {
+        format!("comment-reporters-logged-in:{id}")
+    }
+
+    fn report_key(id: &str, reporter: Principal) -> String {
+        format!("comment-report:{id}:{reporter}")
+    }
+
+    fn comment_key(id: &str) -> String {
+        format!("comment:{id}")
+    }
+
+    /// Comments written by a user, scored by creation time
+    fn authored_key(user: Principal) -> String {
+        format!("user-comments:{user}")
+    }
+
+    /// Comments liked by a user, scored by like time
+    fn liked_key(user: Principal) -> String {
+        format!("user-comment-likes:{user}")
+    }
+
+    /// Comments reported by a user, scored by report time
+    fn reported_key(user: Principal) -> String {
+        format!("user-comment-reports:{user}")
+    }
+
+    async fn index_all(kv: &KVStoreImpl, key: String) -> Result<Vec<String>, ServerFnError> {
+        let len = kv.index_len(key.clone()).await?;
+        Ok(kv.index_range(key, 0, len, false).await?)
+    }
+
+    pub fn now_secs() -> u64 {
+        current_epoch().as_secs()
+    }
+
+    /// Moderation hook run on every comment before it's saved
+    pub fn moderate(text: &str) -> Result<(), CommentError> {
+        let segments = text_entities::parse(text);
+        let links = segments
+            .iter()
+            .filter(|s| matches!(s, TextSegment::Url(_)))
+            .count();
+        if links > MAX_COMMENT_LINKS {
+            return Err(CommentError::Rejected("too many links".into()));
+        }
+        if text_entities::extract_mentions(text).len() > MAX_MENTIONS {
+            return Err(CommentError::Rejected("too many mentions".into()));
+        }
+        Ok(())
+    }
+
+    /// Ids listed under a post (newest first) or under a parent comment (oldest first)
+    pub async fn comment_ids(
+        kv: &KVStoreImpl,
+        canister_id: Principal,
+        post_id: u64,
+        parent_id: Option<&str>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<String>, usize), ServerFnError> {
+        let (key, rev) = match parent_id {
+            Some(parent_id) => (replies_key(parent_id), false),
+            None => (post_comments_key(canister_id, post_id), true),
+        };
+        let ids = kv.index_range(key.clone(), offset, limit, rev).await?;
+        let total = kv.index_len(key).await?;
+        Ok((ids, total))
+    }
+
+    pub async fn stored_comment(
+        kv: &KVStoreImpl,
+        id: &str,
+    ) -> Result<Option<StoredComment>, ServerFnError> {
+        match kv.read(comment_key(id)).await? {
+            Some(raw) if !raw.is_empty() => Ok(Some(serde_json::from_str(&raw)?)),
+            _ => Ok(None),
+        }
+    }
+
+    pub async fn expect_comment(
+        kv: &KVStoreImpl,
+        id: &str,
+    ) -> Result<StoredComment, ServerFnError> {
+        stored_comment(kv, id)
+            .await?
+            .ok_or_else(|| CommentError::NotFound.into())
+    }
+
+    pub async fn write_comment(
+        kv: &KVStoreImpl,
+        comment: &StoredComment,
+    ) -> Result<(), ServerFnError> {
+        kv.write(comment_key(&comment.id), serde_json::to_string(comment)?)
+            .await?;
+        Ok(())
+    }
+
+    fn listing_key(comment: &StoredComment) -> String {
+        match &comment.parent_id {
+            Some(parent_id) => replies_key(parent_id),
+            None => post_comments_key(comment.canister_id, comment.post_id),
+        }
+    }
+
+    /// Adds `comment` to its post or parent and to its author's comments,
+    /// `comment` must already be written
+    pub async fn attach(kv: &KVStoreImpl, comment: &StoredComment) -> Result<(), ServerFnError> {
+        kv.index_add(
+            listing_key(comment),
+            comment.id.clone(),
+            comment.created_at as f64,
+        )
+        .await?;
+        kv.index_add(
+            authored_key(comment.author),
+            comment.id.clone(),
+            comment.created_at as f64,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Removes `comment` from the listing of its post or parent
+    pub async fn detach(kv: &KVStoreImpl, comment: &StoredComment) -> Result<(), ServerFnError> {
+        kv.index_remove(listing_key(comment), comment.id.clone())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn reply_count(kv: &KVStoreImpl, id: &str) -> Result<usize, ServerFnError> {
+        Ok(kv.index_len(replies_key(id)).await?)
+    }
+
+    /// Likes the comment if the caller hasn't yet, unlikes it otherwise,
+    /// returns whether it's now liked and the like count
+    pub async fn toggle_like(
+        kv: &KVStoreImpl,
+        id: &str,
+        caller: Principal,
+    ) -> Result<(bool, usize), ServerFnError> {
+        let key = likes_key(id);
+        let liked = if kv.index_remove(key.clone(), caller.to_text()).await? {
+            kv.index_remove(liked_key(caller), id.to_string()).await?;
+            false
+        } else {
+            let now = now_secs() as f64;
+            kv.index_add(key.clone(), caller.to_text(), now).await?;
+            kv.index_add(liked_key(caller), id.to_string(), now).await?;
+            true
+        };
+        Ok((liked, kv.index_len(key).await?))
+    }
+
+    /// Records `report` once per reporter, returns the number of
+    /// logged in users that reported the comment
+    pub async fn add_report(
+        kv: &KVStoreImpl,
+        id: &str,
+        report: &CommentReport,
+    ) -> Result<usize, ServerFnError> {
+        let reporter = report.reporter;
+        let added = kv
+            .index_add(
+                reporters_key(id),
+                reporter.to_text(),
+                report.reported_at as f64,
+            )
+            .await?;
+        if added {
+            kv.write(report_key(id, reporter), serde_json::to_string(report)?)
+                .await?;
+            kv.index_add(
+                reported_key(reporter),
+                id.to_string(),
+                report.reported_at as f64,
+            )
+            .await?;
+            if report.logged_in {
+                kv.index_add(
+                    logged_in_reporters_key(id),
+                    reporter.to_text(),
+                    report.reported_at as f64,
+                )
+                .await?;
+            }
+        }
+        Ok(kv.index_len(logged_in_reporters_key(id)).await?)
+    }
+
+    async fn remove_report(
+        kv: &KVStoreImpl,
+        id: &str,
+        reporter: Principal,
+    ) -> Result<(), ServerFnError> {
+        kv.index_remove(reporters_key(id), reporter.to_text())
+            .await?;
+        kv.index_remove(logged_in_reporters_key(id), reporter.to_text())
+            .await?;
+        kv.delete(report_key(id, reporter)).await?;
+        Ok(())
+    }
+
+    async fn latest_comments(
+        kv: &KVStoreImpl,
+        key: String,
+        limit: usize,
+    ) -> Result<Vec<StoredComment>, ServerFnError> {
+        let mut comments = vec![];
+        for id in kv.index_range(key, 0, limit, true).await? {
+            if let Some(comment) = stored_comment(kv, &id).await? {
+                comments.push(comment);
+            }
+        }
+        Ok(comments)
+    }
+
+    /// Latest comments written by the user, newest first
+    pub async fn authored_comments(
+        kv: &KVStoreImpl,
+        user: Principal,
+        limit: usize,
+    ) -> Result<Vec<StoredComment>, ServerFnError> {
+        latest_comments(kv, authored_key(user), limit).await
+    }
+
+    /// Latest comments liked by the user, newest first
+    pub async fn liked_comments(
+        kv: &KVStoreImpl,
+        user: Principal,
+        limit: usize,
+    ) -> Result<Vec<StoredComment>, ServerFnError> {
+        latest_comments(kv, liked_key(user), limit).await
+    }
+
+    /// Latest comment reports made by the user with the reported comment ids, newest first
+    pub async fn reports_by(
+        kv: &KVStoreImpl,
+        user: Principal,
+        limit: usize,
+    ) -> Result<Vec<(String, CommentReport)>, ServerFnError> {
+        let mut reports = vec![];
+        for id in kv.index_range(reported_key(user), 0, limit, true).await? {
+            if let Some(raw) = kv.read(report_key(&id, user)).await? {
+                reports.push((id, serde_json::from_str(&raw)?));
+            }
+        }
+        Ok(reports)
+    }
+
+    /// Removes the user's comments, likes and reports
+    ///
+    /// Comments with replies are kept as anonymous deleted placeholders
+    /// so the rest of the thread stays readable
+    pub async fn delete_user_data(kv: &KVStoreImpl, user: Principal) -> Result<(), ServerFnError> {
+        for id in index_all(kv, authored_key(user)).await? {
+            let Some(mut comment) = stored_comment(kv, &id).await? else {
+                continue;
+            };
+            for liker in index_all(kv, likes_key(&id)).await? {
+                if let Ok(liker) = Principal::from_text(liker) {
+                    kv.index_remove(liked_key(liker), id.clone()).await?;
+                }
+            }
+            kv.index_delete(likes_key(&id)).await?;
+            for reporter in index_all(kv, reporters_key(&id)).await? {
+                if let Ok(reporter) = Principal::from_text(reporter) {
+                    remove_report(kv, &id, reporter).await?;
+                    kv.index_remove(reported_key(reporter), id.clone()).await?;
+                }
+            }
+
+            if reply_count(kv, &id).await? == 0 {
+                detach(kv, &comment).await?;
+                kv.delete(comment_key(&id)).await?;
+                continue;
+            }
+            comment.author = Principal::anonymous();
+            comment.author_name.clear();
+            comment.author_pic.clear();
+            comment.text.clear();
+            comment.deleted = true;
+            write_comment(kv, &comment).await?;
+        }
+        kv.index_delete(authored_key(user)).await?;
+
+        for id in index_all(kv, liked_key(user)).await? {
+            kv.index_remove(likes_key(&id), user.to_text()).await?;
+        }
+        kv.index_delete(liked_key(user)).await?;
+
+        for id in index_all(kv, reported_key(user)).await? {
+            remove_report(kv, &id, user).await?;
+        }
+        kv.index_delete(reported_key(user)).await?;
+        Ok(())
+    }
+}
+
+#[server(endpoint = "get_comments", input = server_fn::codec::Json)]
+pub async fn get_comments(
+    canister_id: Principal,
+    post_id: u64,
+    parent_id: Option<String>,
+    start: usize,
+    end: usize,
+) -> Result<(Vec<Comment>, bool), ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+    use futures::{stream::FuturesOrdered, StreamExt};
+
+    let end = end.min(start.saturating_add(MAX_COMMENTS_PAGE));
+    let kv: KVStoreImpl = expect_context();
+    let caller = extract_principal_impl().await?;
+    if let Some(parent_id) = &parent_id {
+        server::expect_comment(&kv, parent_id).await?;
+    }
+    let (ids, total) = server::comment_ids(
+        &kv,
+        canister_id,
+        post_id,
+        parent_id.as_deref(),
+        start,
+        end.saturating_sub(start),
+    )
+    .await?;
+    let list_end = end >= total;
+    let kv = &kv;
+    let comments = ids
+        .into_iter()
+        .map(|id| async move {
+            match server::stored_comment(kv, &id).await? {
+                Some(c) => c.to_comment(kv, caller).await.map(Some),
+                None => Ok(None),
+            }
+        })
+        .collect::<FuturesOrdered<_>>()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, ServerFnError>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+    Ok((comments, list_end))
+}
+
+#[server(endpoint = "add_comment", input = server_fn::codec::Json)]
+pub async fn add_comment(
+    canister_id: Principal,
+    post_id: u64,
+    parent_id: Option<String>,
+    text: String,
+) -> Result<Comment, ServerFnError> {
+    use crate::profile::follow::server::follow_profile;
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+    use yral_canisters_common::Canisters;
+
+    let Some(author) = extract_principal_impl().await? else {
+        return Err(CommentError::LoginRequired.into());
+    };
+    let text = validate_comment(&text)?;
+    server::moderate(&text)?;
+
+    let kv: KVStoreImpl = expect_context();
+    // replies to replies are attached to the top level comment
+    let parent_id = match parent_id {
+        Some(parent_id) => {
+            let parent = server::expect_comment(&kv, &parent_id).await?;
+            if parent.canister_id != canister_id || parent.post_id != post_id {
+                return Err(CommentError::NotFound.into());
+            }
+            Some(parent.parent_id.unwrap_or(parent.id))
+        }
+        None => None,
+    };
+
+    let cans: Canisters<false> = use_context().unwrap_or_default();
+    let profile = follow_profile(&cans, &kv, author).await?;
+    let comment = server::StoredComment {
+        id: uuid::Uuid::new_v4().simple().to_string(),
+        canister_id,
+        post_id,
+        parent_id,
+        author,
+        author_name: profile.display_name,
+        author_pic: profile.profile_pic,
+        text,
+        created_at: server::now_secs(),
+        deleted: false,
+        hidden: false,
+    };
+    server::write_comment(&kv, &comment).await?;
+    server::attach(&kv, &comment).await?;
+
+    comment.to_comment(&kv, Some(author)).await
+}
+
+/// Returns whether the caller now likes the comment and its like count
+#[server(endpoint = "toggle_comment_like", input = server_fn::codec::Json)]
+pub async fn toggle_comment_like(comment_id: String) -> Result<(bool, usize), ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let Some(caller) = extract_principal_impl().await? else {
+        return Err(CommentError::LoginRequired.into());
+    };
+    let kv: KVStoreImpl = expect_context();
+    server::expect_comment(&kv, &comment_id).await?;
+    server::toggle_like(&kv, &comment_id, caller).await
+}
+
+#[server(endpoint = "delete_comment", input = server_fn::codec::Json)]
+pub async fn delete_comment(comment_id: String) -> Result<(), ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let kv: KVStoreImpl = expect_context();
+    let mut comment = server::expect_comment(&kv, &comment_id).await?;
+    if extract_principal_impl().await? != Some(comment.author) {
+        return Err(CommentError::NotAuthor.into());
+    }
+
+    if server::reply_count(&kv, &comment.id).await? == 0 {
+        server::detach(&kv, &comment).await?;
+    }
+    comment.deleted = true;
+    comment.text.clear();
+    server::write_comment(&kv, &comment).await?;
+    Ok(())
+}
+
+/// `reason` is one of [ReportOption]
+/// Anonymous users can report, but only reports from users
+/// logged in with OAuth count towards hiding the comment
+#[server(endpoint = "report_comment", input = server_fn::codec::Json)]
+pub async fn report_comment(comment_id: String, reason: String) -> Result<(), ServerFnError> {
+    use auth::server_impl::{extract_principal_with_login_impl, store::KVStoreImpl};
+
+    let Some((reporter, logged_in)) = extract_principal_with_login_impl().await? else {
+        return Err(CommentError::LoginRequired.into());
+    };
+    if !ReportOption::all()
+        .iter()
+        .any(|o| o.as_str().to_string() == reason)
+    {
+        return Err(CommentError::InvalidReason.into());
+    }
+
+    let kv: KVStoreImpl = expect_context();
+    let mut comment = server::expect_comment(&kv, &comment_id).await?;
+    let report = server::CommentReport {
+        reporter,
+        reason,
+        reported_at: server::now_secs(),
+        logged_in,
+    };
+    let reports = server::add_report(&kv, &comment.id, &report).await?;
+
+    if !comment.hidden && reports >= REPORTS_TO_HIDE {
+        log::info!("hiding reported comment {}", comment.id);
+        comment.hidden = true;
+        server::detach(&kv, &comment).await?;
+        server::write_comment(&kv, &comment).await?;
+    }
+    Ok(())
+}
+
+#[derive(Clone)]
+pub struct CommentsProvider {
+    canister_id: Principal,
+    post_id: u64,
+    parent_id: Option<String>,
+}
+
+impl CommentsProvider {
+    pub fn new(canister_id: Principal, post_id: u64, parent_id: Option<String>) -> Self {
+        Self {
+            canister_id,
+            post_id,
+            parent_id,
+        }
+    }
+}
+
+impl CursoredDataProvider for CommentsProvider {
+    type Data = Comment;
+    type Error = ServerFnError;
+
+    async fn get_by_cursor_inner(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Result<PageEntry<Comment>, ServerFnError> {
+        let (data, end) = get_comments(
+            self.canister_id,
+            self.post_id,
+            self.parent_id.clone(),
+            start,
+            end,
+        )
+        .await?;
+        Ok(PageEntry { data, end })
+    }
+}
+
+/// State shared by the comments of an open sheet
+#[derive(Clone, Copy)]
+struct SheetState {
+    canister_id: Principal,
+    post_id: u64,
+    viewer: Option<Principal>,
+    replying_to: RwSignal<Option<Comment>>,
+    /// comments posted while the sheet is open
+    posted: RwSignal<Vec<Comment>>,
+}
+
+#[component]
+fn CommentReportForm(comment_id: String, show: RwSignal<bool>) -> impl IntoView {
+    let reason = RwSignal::new(ReportOption::Spam.as_str().to_string());
+    let reported = RwSignal::new(false);
+    let submit = Action::new(move |&()| {
+        let comment_id = comment_id.clone();
+        send_wrap(async move {
+            match report_comment(comment_id, reason.get_untracked()).await {
+                Ok(()) => reported.set(true),
+                Err(e) => log::warn!("failed to report comment {e}"),
+            }
+        })
+    });
+
+    view! {
+        <Show
+            when=move || !reported()
+            fallback=|| view! { <span class="text-xs text-neutral-400">Thanks for reporting</span> }
+        >
+            <div class="flex flex-row gap-2 items-center pt-1">
+                <select
+                    class="p-1 text-xs text-black rounded-md"
+                    on:change=move |ev| reason.set(event_target_value(&ev))
+                >
+                    {ReportOption::all()
+                        .into_iter()
+                        .map(|o| {
+                            let o = o.as_str().to_string();
+                            let selected = o == reason.get_untracked();
+                            view! {
+                                <option value=o.clone() selected=selected>
+                                    {o}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <button
+                    class="text-xs font-semibold text-primary-500 disabled:opacity-50"
+                    disabled=move || submit.pending().get()
+                    on:click=move |_| {
+                        submit.dispatch(());
+                    }
+                >
+                    Report
+                </button>
+                <button class="text-xs text-neutral-400" on:click=move |_| show.set(false)>
+                    Cancel
+                </button>
+            </div>
+        </Show>
+    }
+}
+
+#[component]
+fn CommentItem(
+    comment: Comment,
+    sheet: SheetState,
+    #[prop(optional)] _ref: Option<NodeRef<html::Div>>,
+) -> AnyView {
+    let is_reply = comment.parent_id.is_some();
+    let is_author = sheet.viewer == Some(comment.author);
+    let deleted = RwSignal::new(comment.deleted);
+    let removed = RwSignal::new(false);
+    let likes = RwSignal::new(comment.likes);
+    let liked = RwSignal::new(comment.liked);
+    let show_replies = RwSignal::new(false);
+    let show_report = RwSignal::new(false);
+    let comment_id = StoredValue::new(comment.id.clone());
+    let reply_count = comment.reply_count;
+
+    let toggle_like = Action::new(move |&()| {
+        let was_liked = liked.get_untracked();
+        liked.set(!was_liked);
+        likes.update(|l| {
+            *l = if was_liked {
+                l.saturating_sub(1)
+            } else {
+                *l + 1
+            }
+        });
+        send_wrap(async move {
+            match toggle_comment_like(comment_id.get_value()).await {
+                Ok((now_liked, count)) => {
+                    liked.set(now_liked);
+                    likes.set(count);
+                }
+                Err(e) => {
+                    log::warn!("failed to toggle comment like {e}");
+                    liked.set(was_liked);
+                    likes.update(|l| {
+                        *l = if was_liked {
+                            *l + 1
+                        } else {
+                            l.saturating_sub(1)
+                        }
+                    });
+                }
+            }
+        })
+    });
+
+    let delete = Action::new(move |&()| {
+        send_wrap(async move {
+            match delete_comment(comment_id.get_value()).await {
+                Ok(()) if reply_count > 0 => deleted.set(true),
+                Ok(()) => removed.set(true),
+                Err(e) => log::warn!("failed to delete comment {e}"),
+            }
+        })
+    });
+
+    let reply_target = comment.clone();
+    let text = StoredValue::new(comment.text.clone());
+    let avatar_class = if is_reply {
+        "object-cover w-6 h-6 rounded-full"
+    } else {
+        "object-cover w-8 h-8 rounded-full"
+    };
+    let profile_url = format!("/profile/{}/posts", comment.author);
+    let posted_replies = move || {
+        sheet.posted.with(|posted| {
+            posted
+                .iter()
+                .filter(|c| c.parent_id.as_deref() == Some(comment_id.get_value().as_str()))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    };
+
+    view! {
+        <div node_ref=_ref.unwrap_or_default() class="flex flex-col w-full" class:hidden=removed>
+            <div class="flex flex-row gap-3 py-2 w-full">
+                <a href=profile_url.clone() class="shrink-0">
+                    <img class=avatar_class src=comment.author_pic />
+                </a>
+                <div class="flex flex-col flex-1 gap-0.5 min-w-0">
+                    <div class="flex flex-row gap-2 items-center text-xs text-neutral-400">
+                        <a href=profile_url class="font-semibold truncate text-neutral-200">
+                            {comment.author_name}
+                        </a>
+                        <span>{time_ago(comment.created_at, current_epoch().as_secs())}</span>
+                    </div>
+                    <Show
+                        when=move || !deleted()
+                        fallback=|| {
+                            view! {
+                                <span class="text-sm italic text-neutral-500">Comment deleted</span>
+                            }
+                        }
+                    >
+                        <p class="text-sm break-words whitespace-pre-line">
+                            {move || {
+                                text.with_value(|t| {
+                                    text_entities::parse(t)
+                                        .into_iter()
+                                        .map(entity_view)
+                                        .collect_view()
+                                })
+                            }}
+                        </p>
+                    </Show>
+                    <Show when=move || !deleted()>
+                        <div class="flex flex-row gap-4 items-center pt-1 text-xs text-neutral-400">
+                            <button on:click={
+                                let reply_target = reply_target.clone();
+                                move |_| sheet.replying_to.set(Some(reply_target.clone()))
+                            }>Reply</button>
+                            {if is_author {
+                                view! {
+                                    <button
+                                        disabled=move || delete.pending().get()
+                                        on:click=move |_| {
+                                            delete.dispatch(());
+                                        }
+                                    >
+                                        Delete
+                                    </button>
+                                }
+                                    .into_any()
+                            } else {
+                                view! {
+                                    <button on:click=move |_| show_report.set(true)>Report</button>
+                                }
+                                    .into_any()
+                            }}
+                        </div>
+                    </Show>
+                    <Show when=show_report>
+                        <CommentReportForm comment_id=comment_id.get_value() show=show_report />
+                    </Show>
+                </div>
+                <button
+                    class="flex flex-col items-center text-xs shrink-0 text-neutral-400"
+                    on:click=move |_| {
+                        toggle_like.dispatch(());
+                    }
+                >
+                    <Icon
+                        attr:class="text-base"
+                        icon=Signal::derive(move || {
+                            if liked() { icondata::AiHeartFilled } else { icondata::AiHeartOutlined }
+                        })
+                    />
+                    {likes}
+                </button>
+            </div>
+            {(!is_reply)
+                .then(|| {
+                    view! {
+                        <div class="flex flex-col pl-11">
+                            <For
+                                each=posted_replies
+                                key=|c| c.id.clone()
+                                children=move |reply| view! { <CommentItem comment=reply sheet /> }
+                            />
+                            <Show when=move || reply_count > 0 && !show_replies()>
+                                <button
+                                    class="self-start py-1 text-xs font-semibold text-neutral-400"
+                                    on:click=move |_| show_replies.set(true)
+                                >
+                                    {format!(
+                                        "View {reply_count} {}",
+                                        if reply_count == 1 { "reply" } else { "replies" },
+                                    )}
+                                </button>
+                            </Show>
+                            <Show when=show_replies>
+                                <InfiniteScroller
+                                    provider=CommentsProvider::new(
+                                        sheet.canister_id,
+                                        sheet.post_id,
+                                        Some(comment_id.get_value()),
+                                    )
+                                    fetch_count=REPLIES_CHUNK_SZ
+                                    children=move |reply, _ref| {
+                                        view! { <CommentItem comment=reply sheet _ref=_ref.unwrap_or_default() /> }
+                                    }
+                                />
+                            </Show>
+                        </div>
+                    }
+                })}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn CommentInput(sheet: SheetState) -> impl IntoView {
+    let text = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+
+    let submit = Action::new(move |&()| {
+        let parent_id = sheet.replying_to.get_untracked().map(|c| c.id);
+        let body = text.get_untracked();
+        send_wrap(async move {
+            let res = match validate_comment(&body) {
+                Ok(body) => add_comment(sheet.canister_id, sheet.post_id, parent_id, body)
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            match res {
+                Ok(comment) => {
+                    sheet.posted.update(|p| p.insert(0, comment));
+                    sheet.replying_to.set(None);
+                    text.set(String::new());
+                    error.set(None);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        })
+    });
+
+    view! {
+        <div class="flex flex-col gap-1 px-4 pt-2 pb-4 border-t border-neutral-800">
+            <Show when=move || sheet.replying_to.with(|r| r.is_some())>
+                <div class="flex flex-row justify-between items-center text-xs text-neutral-400">
+                    <span class="truncate">
+                        {move || {
+                            sheet.replying_to
+                                .with(|r| {
+                                    r.as_ref()
+                                        .map(|c| format!("Replying to {}", c.author_name))
+                                        .unwrap_or_default()
+                                })
+                        }}
+                    </span>
+                    <button on:click=move |_| sheet.replying_to.set(None)>
+                        <Icon icon=icondata::ChCross />
+                    </button>
+                </div>
+            </Show>
+            <form
+                class="flex flex-row gap-2 items-center"
+                on:submit=move |ev| {
+                    ev.prevent_default();
+                    submit.dispatch(());
+                }
+            >
+                <input
+                    class="flex-1 py-2 px-3 text-sm text-white rounded-full outline-none bg-neutral-800"
+                    placeholder="Add a comment..."
+                    maxlength=MAX_COMMENT_LEN
+                    prop:value=text
+                    on:input=move |ev| text.set(event_target_value(&ev))
+                />
+                <button
+                    type="submit"
+                    class="text-sm font-semibold text-primary-500 disabled:opacity-50"
+                    disabled=move || submit.pending().get() || text.with(|t| t.trim().is_empty())
+                >
+                    Post
+                </button>
+            </form>
+            <Show when=move || error.with(|e| e.is_some())>
+                <span class="text-xs text-red-500">{move || error.get().unwrap_or_default()}</span>
+            </Show>
+        </div>
+    }
+}
+
+#[component]
+fn CommentList(sheet: SheetState) -> impl IntoView {
+    let posted_top_level = move || {
+        sheet.posted.with(|posted| {
+            posted
+                .iter()
+                .filter(|c| c.parent_id.is_none())
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    };
+
+    view! {
+        <div class="overflow-y-auto flex-1 px-4">
+            <For
+                each=posted_top_level
+                key=|c| c.id.clone()
+                children=move |comment| view! { <CommentItem comment sheet /> }
+            />
+            <InfiniteScroller
+                provider=CommentsProvider::new(sheet.canister_id, sheet.post_id, None)
+                fetch_count=COMMENTS_CHUNK_SZ
+                children=move |comment, _ref| {
+                    view! { <CommentItem comment sheet _ref=_ref.unwrap_or_default() /> }
+                }
+                empty_content=move || {
+                    view! {
+                        <Show when=move || sheet.posted.with(|p| p.is_empty())>
+                            <div class="flex justify-center items-center py-12 text-sm text-neutral-400">
+                                No comments yet, start the conversation
+                            </div>
+                        </Show>
+                    }
+                }
+            />
+        </div>
+    }
+}
+
+/// Bottom sheet with the comments of a post
+#[component]
+pub fn CommentSheet(show: RwSignal<bool>, canister_id: Principal, post_id: u64) -> impl IntoView {
+    let auth = auth_state();
+
+    view! {
+        <Show when=show>
+            <div
+                class="flex fixed inset-0 flex-col justify-end cursor-pointer z-[50] bg-black/50"
+                on:click=move |_| show.set(false)
+            >
+                <div
+                    class="flex flex-col mx-auto w-full max-w-lg text-white cursor-auto rounded-t-2xl h-[70dvh] bg-neutral-900"
+                    on:click=|ev| ev.stop_propagation()
+                >
+                    <div class="flex relative justify-center items-center py-3 border-b border-neutral-800">
+                        <span class="font-semibold">Comments</span>
+                        <button
+                            class="absolute right-4 p-1 text-lg rounded-full bg-neutral-700"
+                            on:click=move |_| show.set(false)
+                        >
+                            <Icon icon=icondata::ChCross />
+                        </button>
+                    </div>
+                    <Suspense>
+                        {move || Suspend::new(async move {
+                            let sheet = SheetState {
+                                canister_id,
+                                post_id,
+                                viewer: auth.user_principal.await.ok(),
+                                replying_to: RwSignal::new(None),
+                                posted: RwSignal::new(vec![]),
+                            };
+                            view! {
+                                <CommentList sheet />
+                                <CommentInput sheet />
+                            }
+                        })}
+                    </Suspense>
+                </div>
+            </div>
+        </Show>
+    }
+}
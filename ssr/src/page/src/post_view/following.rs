@@ -0,0 +1,214 @@
+use candid::Principal;
+use component::{block_list::use_block_list, spinner::FullScreenSpinner};
+use consts::MAX_VIDEO_ELEMENTS_FOR_FEED;
+use futures::{stream::FuturesOrdered, StreamExt};
+use leptos::prelude::*;
+use leptos_meta::*;
+use leptos_router::components::Redirect;
+use leptos_use::use_debounce_fn;
+use state::{
+    app_state::AppState,
+    canisters::{auth_state, unauth_canisters},
+};
+use utils::try_or_redirect;
+use yral_canisters_common::{utils::posts::PostDetails, Canisters};
+
+use super::PostViewCtx;
+use crate::profile::{
+    follow::{get_follows, FollowList},
+    profile_iter::{FixedFetchCursor, ProfVideoStream, ProfileVideoStream},
+};
+use crate::scrolling_post_view::ScrollingPostView;
+
+/// Followed creators whose posts are fetched per batch
+const CREATORS_PER_FETCH: usize = 5;
+/// Most recent posts taken from every followed creator
+const POSTS_PER_CREATOR: u64 = 5;
+type CreatorVidStream = ProfileVideoStream<POSTS_PER_CREATOR>;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FeedTab {
+    ForYou,
+    Following,
+}
+
+#[component]
+pub fn FeedTabs(active: FeedTab) -> impl IntoView {
+    let tab_class = move |tab: FeedTab| {
+        if tab == active {
+            "text-white border-b-2 border-white pb-0.5"
+        } else {
+            "text-white/60 pb-0.5"
+        }
+    };
+
+    view! {
+        <div class="flex fixed top-1 left-1/2 z-20 flex-row gap-4 text-sm font-semibold -translate-x-1/2 pointer-events-auto">
+            <a class=tab_class(FeedTab::ForYou) href="/">
+                For You
+            </a>
+            <a class=tab_class(FeedTab::Following) href="/following">
+                Following
+            </a>
+        </div>
+    }
+}
+
+/// Round robin over the creators so a single prolific creator doesn't fill the feed
+fn interleave(mut per_creator: Vec<Vec<PostDetails>>) -> Vec<PostDetails> {
+    per_creator.iter_mut().for_each(|posts| posts.reverse());
+    let mut mixed = vec![];
+    while per_creator.iter().any(|posts| !posts.is_empty()) {
+        mixed.extend(per_creator.iter_mut().filter_map(|posts| posts.pop()));
+    }
+    mixed
+}
+
+async fn recent_posts(cans: &Canisters<false>, creator: Principal) -> Vec<PostDetails> {
+    let user_canister = match cans
+        .get_individual_canister_by_user_principal(creator)
+        .await
+    {
+        Ok(Some(canister)) => canister,
+        Ok(None) => return vec![],
+        Err(e) => {
+            log::warn!("failed to get canister of {creator}: {e}");
+            return vec![];
+        }
+    };
+    let cursor = FixedFetchCursor::<POSTS_PER_CREATOR> {
+        start: 0,
+        limit: POSTS_PER_CREATOR,
+    };
+    match CreatorVidStream::fetch_next_posts(cursor, cans, user_canister).await {
+        Ok(res) => res.posts,
+        Err(e) => {
+            log::warn!("failed to get posts of {creator}: {e}");
+            vec![]
+        }
+    }
+}
+
+#[component]
+fn FollowingFeedInner(user_principal: Principal) -> impl IntoView {
+    let PostViewCtx {
+        video_queue,
+        video_queue_for_feed,
+        current_idx,
+        queue_end,
+        ..
+    } = PostViewCtx::new();
+    let creator_cursor = RwSignal::new(0usize);
+    let recovering_state = RwSignal::new(false);
+    let hard_refresh_target = RwSignal::new("/following".to_string());
+    let block_list = use_block_list();
+
+    let fetch_video_action: Action<_, _> = Action::new_unsync(move |_| async move {
+        let cans = unauth_canisters();
+        // skip over batches of creators without any posts
+        loop {
+            let start = creator_cursor.get_untracked();
+            let (creators, end) = try_or_redirect!(
+                get_follows(
+                    user_principal,
+                    FollowList::Following,
+                    start,
+                    start + CREATORS_PER_FETCH,
+                )
+                .await
+            );
+            creator_cursor.set(start + CREATORS_PER_FETCH);
+
+            let per_creator = creators
+                .into_iter()
+                .filter(|creator| !block_list.is_blocked_untracked(*creator))
+                .map(|creator| recent_posts(&cans, creator))
+                .collect::<FuturesOrdered<_>>()
+                .collect::<Vec<_>>()
+                .await;
+            let posts = interleave(per_creator);
+            let found = !posts.is_empty();
+
+            posts.into_iter().for_each(|p| {
+                video_queue.update(|q| {
+                    if q.insert(p.clone()) {
+                        let len_vq = q.len();
+                        if len_vq > MAX_VIDEO_ELEMENTS_FOR_FEED {
+                            return;
+                        }
+                        video_queue_for_feed.update(|vqf| {
+                            vqf[len_vq - 1].value.set(Some(p.clone()));
+                        });
+                    }
+                });
+            });
+
+            if end {
+                queue_end.set(true);
+            }
+            if found || end {
+                break;
+            }
+        }
+    });
+
+    Effect::new(move || {
+        fetch_video_action.dispatch(());
+    });
+
+    let fetch_next_videos = use_debounce_fn(
+        move || {
+            if !fetch_video_action.pending().get_untracked() && !queue_end.get_untracked() {
+                fetch_video_action.dispatch(());
+            }
+        },
+        200.0,
+    );
+
+    let no_posts = move || queue_end() && video_queue.with(|q| q.is_empty());
+
+    view! {
+        <ScrollingPostView
+            video_queue
+            video_queue_for_feed
+            current_idx
+            queue_end
+            recovering_state
+            fetch_next_videos
+            overlay=|| view! { <FeedTabs active=FeedTab::Following /> }
+            threshold_trigger_fetch=10
+            hard_refresh_target
+        />
+        <Show when=no_posts>
+            <div class="flex fixed inset-0 z-30 flex-col gap-4 justify-center items-center px-8 text-center text-white bg-black">
+                <FeedTabs active=FeedTab::Following />
+                <span class="text-lg font-semibold">No videos from creators you follow</span>
+                <span class="text-sm text-neutral-400">
+                    Follow creators from their profile or from the feed to see their latest videos here
+                </span>
+            </div>
+        </Show>
+    }
+    .into_any()
+}
+
+#[component]
+pub fn FollowingFeed() -> impl IntoView {
+    let app_state = use_context::<AppState>();
+    let page_title = app_state.unwrap().name.to_owned() + " - Following";
+    let auth = auth_state();
+
+    view! {
+        <Title text=page_title />
+        <Suspense fallback=FullScreenSpinner>
+            {move || Suspend::new(async move {
+                match auth.user_principal.await {
+                    Ok(user_principal) => {
+                        view! { <FollowingFeedInner user_principal /> }.into_any()
+                    }
+                    Err(_) => view! { <Redirect path="/" /> }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
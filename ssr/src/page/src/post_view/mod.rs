@@ -1,18 +1,26 @@
-mod bet;
+pub mod bet;
+pub mod comments;
 pub mod error;
+pub mod following;
+pub mod not_interested;
 pub mod overlay;
 pub mod single_post;
+pub mod tags;
 pub mod video_iter;
 pub mod video_loader;
+use crate::admin::moderation::hidden_among;
 use crate::scrolling_post_view::ScrollingPostView;
+use component::block_list::use_block_list;
 use component::buttons::HighlightedButton;
 use component::overlay::ShadowOverlay;
 use component::spinner::FullScreenSpinner;
 use consts::{
     UserOnboardingStore, MAX_VIDEO_ELEMENTS_FOR_FEED, NSFW_TOGGLE_STORE, USER_ONBOARDING_STORE_KEY,
 };
+use following::{FeedTab, FeedTabs};
 use indexmap::IndexSet;
 use leptos_icons::*;
+use not_interested::use_not_interested;
 use priority_queue::DoublePriorityQueue;
 use state::canisters::{auth_state, unauth_canisters};
 use std::{cmp::Reverse, collections::HashMap};
@@ -74,6 +82,38 @@ impl PostViewCtx {
             ..Default::default()
         }
     }
+
+    /// Drops `post` and upcoming posts similar to it, already watched posts are kept
+    pub fn remove_similar(&self, post: &PostDetails) {
+        let cur_idx = self.current_idx.get_untracked();
+        let dropped = |p: &PostDetails| {
+            (p.canister_id, p.post_id) == (post.canister_id, post.post_id)
+                || not_interested::is_similar(post, p)
+        };
+
+        self.priority_q.update(|pq| {
+            *pq = pq
+                .iter()
+                .filter(|(p, _)| !dropped(p))
+                .map(|(p, prio)| (p.clone(), *prio))
+                .collect();
+        });
+
+        self.video_queue.update(|vq| {
+            *vq = vq
+                .iter()
+                .enumerate()
+                .filter(|(idx, p)| *idx < cur_idx || !dropped(p))
+                .map(|(_, p)| p.clone())
+                .collect();
+            // slots are positional, shift the upcoming ones into place
+            self.video_queue_for_feed.with_untracked(|vqf| {
+                for (idx, slot) in vqf.iter().enumerate().skip(cur_idx) {
+                    slot.value.set(vq.get_index(idx).cloned());
+                }
+            });
+        });
+    }
 }
 
 #[derive(Clone, Default)]
@@ -173,6 +213,7 @@ pub fn CommonPostViewWithUpdates(
             recovering_state
             fetch_next_videos=next_videos
             queue_end
+            overlay=|| view! { <FeedTabs active=FeedTab::ForYou /> }
             threshold_trigger_fetch
             hard_refresh_target
         />
@@ -194,6 +235,8 @@ pub fn PostViewWithUpdatesMLFeed(initial_post: Option<PostDetails>) -> impl Into
     } = expect_context();
 
     let auth = auth_state();
+    let block_list = use_block_list();
+    let not_interested = use_not_interested();
 
     let fetch_video_action = Action::new(move |_| {
         let (nsfw_enabled, _, _) = use_local_storage::<bool, FromToStringCodec>(NSFW_TOGGLE_STORE);
@@ -208,6 +251,10 @@ pub fn PostViewWithUpdatesMLFeed(initial_post: Option<PostDetails>) -> impl Into
                 let mut prio_q = priority_q.write();
                 let mut cnt = 0;
                 while let Some((next, _)) = prio_q.pop_max() {
+                    // the creator may have been blocked since the post was queued
+                    if block_list.is_blocked_untracked(next.poster_principal) {
+                        continue;
+                    }
                     video_queue.update(|vq| {
                         if vq.insert(next.clone()) {
                             let len_vq = vq.len();
@@ -244,15 +291,16 @@ pub fn PostViewWithUpdatesMLFeed(initial_post: Option<PostDetails>) -> impl Into
                 let cans_true = auth.auth_cans_if_available(cans_false.clone());
 
                 let video_queue_c = video_queue.get_untracked().iter().cloned().collect();
+                let not_interested_c = not_interested.filter_items_untracked();
                 let chunks = if let Some(cans_true) = cans_true.as_ref() {
                     let mut fetch_stream = new_video_fetch_stream_auth(cans_true, auth, cursor);
                     fetch_stream
-                        .fetch_post_uids_hybrid(3, nsfw_enabled, video_queue_c)
+                        .fetch_post_uids_hybrid(3, nsfw_enabled, video_queue_c, not_interested_c)
                         .await
                 } else {
                     let mut fetch_stream = new_video_fetch_stream(&cans_false, auth, cursor);
                     fetch_stream
-                        .fetch_post_uids_hybrid(3, nsfw_enabled, video_queue_c)
+                        .fetch_post_uids_hybrid(3, nsfw_enabled, video_queue_c, not_interested_c)
                         .await
                 };
 
@@ -260,8 +308,22 @@ pub fn PostViewWithUpdatesMLFeed(initial_post: Option<PostDetails>) -> impl Into
                 let mut chunks = res.posts_stream;
                 let mut cnt = 0usize;
                 while let Some(chunk) = chunks.next().await {
+                    let mut posts = Vec::with_capacity(chunk.len());
                     for uid in chunk {
-                        let post_detail = try_or_redirect!(uid);
+                        posts.push(try_or_redirect!(uid));
+                    }
+                    // content hidden by moderators never enters the queues
+                    let hidden = hidden_among(&posts).await;
+                    for post_detail in posts {
+                        if block_list.is_blocked_untracked(post_detail.poster_principal)
+                            || hidden.is_hidden(
+                                post_detail.canister_id,
+                                post_detail.post_id,
+                                post_detail.poster_principal,
+                            )
+                        {
+                            continue;
+                        }
                         if video_queue
                             .with_untracked(|vq| vq.len())
                             .saturating_sub(current_idx.get_untracked())
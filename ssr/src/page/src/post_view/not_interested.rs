@@ -0,0 +1,88 @@
+use candid::Principal;
+use codee::string::JsonSerdeCodec;
+use consts::NOT_INTERESTED_STORE;
+use leptos::prelude::*;
+use leptos_use::storage::use_local_storage;
+use serde::{Deserialize, Serialize};
+use yral_canisters_common::utils::posts::PostDetails;
+use yral_types::post::PostItem;
+
+/// Oldest entries are dropped beyond this, every feed request carries the whole list
+pub const MAX_NOT_INTERESTED: usize = 200;
+
+/// A post marked as "Not interested"
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NotInterestedPost {
+    pub canister_id: Principal,
+    pub post_id: u64,
+    pub video_id: String,
+    pub nsfw_probability: f32,
+}
+
+impl From<&PostDetails> for NotInterestedPost {
+    fn from(post: &PostDetails) -> Self {
+        Self {
+            canister_id: post.canister_id,
+            post_id: post.post_id,
+            video_id: post.uid.clone(),
+            nsfw_probability: post.nsfw_probability,
+        }
+    }
+}
+
+impl From<NotInterestedPost> for PostItem {
+    fn from(post: NotInterestedPost) -> Self {
+        PostItem {
+            post_id: post.post_id,
+            canister_id: post.canister_id,
+            video_id: post.video_id,
+            nsfw_probability: post.nsfw_probability,
+        }
+    }
+}
+
+/// Posts by the same creator or sharing at least two hashtags
+pub fn is_similar(post: &PostDetails, other: &PostDetails) -> bool {
+    if post.poster_principal == other.poster_principal {
+        return true;
+    }
+    let shared = other
+        .hastags
+        .iter()
+        .filter(|tag| post.hastags.contains(tag))
+        .count();
+    shared >= 2
+}
+
+/// "Not interested" posts, kept in local storage across sessions
+#[derive(Clone, Copy)]
+pub struct NotInterested {
+    posts: Signal<Vec<NotInterestedPost>>,
+    set_posts: WriteSignal<Vec<NotInterestedPost>>,
+}
+
+pub fn use_not_interested() -> NotInterested {
+    let (posts, set_posts, _) =
+        use_local_storage::<Vec<NotInterestedPost>, JsonSerdeCodec>(NOT_INTERESTED_STORE);
+    NotInterested { posts, set_posts }
+}
+
+impl NotInterested {
+    pub fn mark(&self, post: &PostDetails) {
+        let entry = NotInterestedPost::from(post);
+        let mut posts = self.posts.get_untracked();
+        posts.retain(|p| (p.canister_id, p.post_id) != (entry.canister_id, entry.post_id));
+        posts.insert(0, entry);
+        posts.truncate(MAX_NOT_INTERESTED);
+        self.set_posts.set(posts);
+    }
+
+    /// Extra entries for the ML feed's `filter_results`
+    pub fn filter_items_untracked(&self) -> Vec<PostItem> {
+        self.posts
+            .get_untracked()
+            .into_iter()
+            .map(PostItem::from)
+            .collect()
+    }
+}
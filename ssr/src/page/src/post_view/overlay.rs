@@ -1,3 +1,4 @@
+use candid::Principal;
 use codee::string::{FromToStringCodec, JsonSerdeCodec};
 use component::buttons::HighlightedButton;
 use component::overlay::ShadowOverlay;
@@ -14,9 +15,10 @@ use leptos_use::use_window;
 use state::canisters::{auth_state, unauth_canisters};
 use utils::host::show_nsfw_content;
 use utils::{
-    event_streaming::events::{LikeVideo, ShareVideo},
+    analytics::{dispatch, AnalyticsEvent, VideoProps},
     report::ReportOption,
     send_wrap,
+    text_entities::{self, TextSegment},
     web::{copy_to_clipboard, share_url},
 };
 
@@ -24,7 +26,12 @@ use yral_canisters_common::utils::posts::PostDetails;
 
 use utils::mixpanel::mixpanel_events::*;
 
-use super::bet::HNGameOverlay;
+use super::bet::{history::ScoreChartPanel, HNGameOverlay};
+use super::comments::CommentSheet;
+use super::not_interested::use_not_interested;
+use super::tags::tag_url;
+use super::PostViewCtx;
+use crate::profile::follow::FollowButton;
 
 #[component]
 fn LikeAndAuthCanLoader(post: PostDetails) -> impl IntoView {
@@ -44,12 +51,10 @@ fn LikeAndAuthCanLoader(post: PostDetails) -> impl IntoView {
     let initial_liked = (post.liked_by_user, post.likes);
 
     let auth: state::canisters::AuthState = auth_state();
-    let is_logged_in = auth.is_logged_in_with_oauth();
     let ev_ctx = auth.event_ctx();
 
     let like_toggle = Action::new(move |&()| {
-        let post_details = post.clone();
-        let video_id = post.uid.clone();
+        let video = VideoProps::from(&post);
         send_wrap(async move {
             let Ok(canisters) = auth.auth_cans(unauth_canisters()).await else {
                 log::warn!("Trying to toggle like without auth");
@@ -65,26 +70,10 @@ fn LikeAndAuthCanLoader(post: PostDetails) -> impl IntoView {
 
             if should_like {
                 likes.update(|l| *l += 1);
-                LikeVideo.send_event(ev_ctx, post_details.clone(), likes);
-
-                let is_logged_in = is_logged_in.get_untracked();
-                let global = MixpanelGlobalProps::try_get(&canisters, is_logged_in);
-                let is_hot_or_not = true;
-                MixPanelEvent::track_video_clicked(MixpanelVideoClickedProps {
-                    user_id: global.user_id,
-                    visitor_id: global.visitor_id,
-                    is_logged_in: global.is_logged_in,
-                    canister_id: global.canister_id,
-                    is_nsfw_enabled: global.is_nsfw_enabled,
-                    is_nsfw: post.is_nsfw,
-                    is_game_enabled: is_hot_or_not,
-                    publisher_user_id: post.poster_principal.to_text(),
-                    game_type: MixpanelPostGameType::HotOrNot,
-                    cta_type: MixpanelVideoClickedCTAType::Like,
-                    video_id,
-                    view_count: post.views,
-                    like_count: post.likes,
-                });
+                dispatch(
+                    ev_ctx,
+                    AnalyticsEvent::VideoLiked(video.with_likes(likes.get_untracked())),
+                );
             } else {
                 likes.update(|l| *l -= 1);
             }
@@ -156,6 +145,9 @@ pub fn VideoDetailsOverlay(
 ) -> impl IntoView {
     let show_share = RwSignal::new(false);
     let show_report = RwSignal::new(false);
+    let show_comments = RwSignal::new(false);
+    let (comments_canister_id, comments_post_id) = (post.canister_id, post.post_id);
+    let show_score_chart = RwSignal::new(false);
     let show_nsfw_permission = RwSignal::new(false);
     let report_option = RwSignal::new(ReportOption::Nudity.as_str().to_string());
     let show_copied_popup = RwSignal::new(false);
@@ -173,71 +165,45 @@ pub fn VideoDetailsOverlay(
     let auth = auth_state();
     let ev_ctx = auth.event_ctx();
 
-    let post_details_share = post.clone();
-    let track_video_id = post.uid.clone();
-    let track_video_id_for_impressions = post.uid.clone();
+    let score_chart_video_uid = post.uid.clone();
+    let video_props = VideoProps::from(&post);
+    let impression_video = video_props.clone();
     Effect::new(move |_| {
         // To trigger the effect on initial render
         let _ = use_location().pathname.get();
-        let track_video_id_for_impressions = track_video_id_for_impressions.clone();
-        if let Some(global) = MixpanelGlobalProps::from_ev_ctx(ev_ctx) {
-            if Some(video_url()) == window().location().href().ok() {
-                MixPanelEvent::track_video_impression(MixpanelVideoViewedProps {
-                    user_id: global.user_id,
-                    visitor_id: global.visitor_id,
-                    is_logged_in: global.is_logged_in,
-                    canister_id: global.canister_id,
-                    is_nsfw_enabled: global.is_nsfw_enabled,
-                    publisher_user_id: post.poster_principal.to_text(),
-                    video_id: track_video_id_for_impressions,
-                    is_nsfw: post.is_nsfw,
-                    game_type: MixpanelPostGameType::HotOrNot,
-                    like_count: post.likes,
-                    view_count: post.views,
-                    is_game_enabled: true,
-                });
-            }
+        if Some(video_url()) == window().location().href().ok() {
+            dispatch(
+                ev_ctx,
+                AnalyticsEvent::VideoImpression(impression_video.clone()),
+            );
         }
     });
 
-    let track_video_clicked = move |cta_type: MixpanelVideoClickedCTAType| {
-        let video_id = track_video_id.clone();
-        let Some(global) = MixpanelGlobalProps::from_ev_ctx(ev_ctx) else {
-            return;
-        };
-        let is_hot_or_not = true;
-        MixPanelEvent::track_video_clicked(MixpanelVideoClickedProps {
-            user_id: global.user_id,
-            visitor_id: global.visitor_id,
-            is_logged_in: global.is_logged_in,
-            is_nsfw: post.is_nsfw,
-            canister_id: global.canister_id,
-            is_nsfw_enabled: global.is_nsfw_enabled,
-            is_game_enabled: is_hot_or_not,
-            publisher_user_id: post.poster_principal.to_text(),
-            game_type: MixpanelPostGameType::HotOrNot,
-            cta_type,
-            video_id,
-            view_count: post.views,
-            like_count: post.likes,
-        });
+    let track_video_clicked = {
+        let video = video_props.clone();
+        move |cta_type: MixpanelVideoClickedCTAType| {
+            dispatch(
+                ev_ctx,
+                AnalyticsEvent::VideoClicked {
+                    video: video.clone(),
+                    cta_type,
+                },
+            );
+        }
     };
-    let track_video_share = track_video_clicked.clone();
-    let track_video_share = move || track_video_share(MixpanelVideoClickedCTAType::Share);
     let track_video_refer = track_video_clicked.clone();
     let track_video_refer = move || track_video_refer(MixpanelVideoClickedCTAType::ReferAndEarn);
     let track_video_report = track_video_clicked.clone();
     let track_video_report = move || track_video_report(MixpanelVideoClickedCTAType::Report);
 
+    let share_video = video_props.clone();
     let share = move || {
-        let post_details = post_details_share.clone();
         let url = video_url();
-        track_video_share();
+        dispatch(ev_ctx, AnalyticsEvent::VideoShared(share_video.clone()));
         if share_url(&url).is_some() {
             return;
         }
         show_share.set(true);
-        ShareVideo.send_event(ev_ctx, post_details);
     };
 
     let profile_url = format!("/profile/{}/tokens", post.poster_principal.to_text());
@@ -249,23 +215,44 @@ pub fn VideoDetailsOverlay(
         Timeout::new(1200, move || show_copied_popup.set(false)).forget();
     };
 
+    // removes the post and similar ones from the session queue right away,
+    // the ML feed skips it in later requests
+    let not_interested = use_not_interested();
+    let post_view_ctx = use_context::<PostViewCtx>();
+    let post_not_interested = post.clone();
+    let click_not_interested = move || {
+        let post = post_not_interested.clone();
+        not_interested.mark(&post);
+        if let Some(ctx) = post_view_ctx.as_ref() {
+            ctx.remove_similar(&post);
+        }
+        dispatch(
+            ev_ctx,
+            AnalyticsEvent::VideoNotInterested(VideoProps::from(&post)),
+        );
+    };
+
     let post_details_report = post.clone();
-    let profile_click_video_id = post.uid.clone();
-    let report_video_click_id = post.uid.clone();
+    let report_video = video_props.clone();
     let click_report = Action::new(move |()| {
-        if let Some(global) = MixpanelGlobalProps::from_ev_ctx(ev_ctx) {
-            MixPanelEvent::track_video_reported(MixpanelVideoReportedProps {
-                user_id: global.user_id,
-                visitor_id: global.visitor_id,
-                is_logged_in: global.is_logged_in,
-                canister_id: global.canister_id,
-                is_nsfw_enabled: global.is_nsfw_enabled,
-                publisher_user_id: post.poster_principal.to_text(),
-                video_id: report_video_click_id.clone(),
-                is_nsfw: post.is_nsfw,
-                is_game_enabled: true,
-                game_type: MixpanelPostGameType::HotOrNot,
+        dispatch(
+            ev_ctx,
+            AnalyticsEvent::VideoReported {
+                video: report_video.clone(),
                 report_reason: report_option.get_untracked(),
+            },
+        );
+        {
+            use crate::admin::moderation::record_post_report;
+
+            let post_details = post_details_report.clone();
+            let reason = report_option.get_untracked();
+            spawn_local(async move {
+                if let Err(e) =
+                    record_post_report(post_details.canister_id, post_details.post_id, reason).await
+                {
+                    log::warn!("failed to record report {e}");
+                }
             });
         }
         #[cfg(feature = "ga4")]
@@ -305,8 +292,11 @@ pub fn VideoDetailsOverlay(
             nsfw_enabled()
         }
     });
+    let track_nsfw_toggle = track_video_clicked.clone();
+    let nsfw_video = video_props.clone();
     let click_nsfw = Action::new(move |()| {
-        let video_id = post.uid.clone();
+        let track_nsfw_toggle = track_nsfw_toggle.clone();
+        let nsfw_video = nsfw_video.clone();
         async move {
             if show_nsfw_content() {
                 return;
@@ -314,65 +304,15 @@ pub fn VideoDetailsOverlay(
 
             if !nsfw_enabled() && !show_nsfw_permission() {
                 show_nsfw_permission.set(true);
-                if let Some(global) = MixpanelGlobalProps::from_ev_ctx_with_nsfw_info(ev_ctx, false)
-                {
-                    let is_hot_or_not = true;
-                    MixPanelEvent::track_video_clicked(MixpanelVideoClickedProps {
-                        user_id: global.user_id,
-                        visitor_id: global.visitor_id,
-                        is_logged_in: global.is_logged_in,
-                        is_nsfw: post.is_nsfw,
-                        canister_id: global.canister_id,
-                        is_nsfw_enabled: global.is_nsfw_enabled,
-                        is_game_enabled: is_hot_or_not,
-                        publisher_user_id: post.poster_principal.to_text(),
-                        game_type: MixpanelPostGameType::HotOrNot,
-                        cta_type: MixpanelVideoClickedCTAType::NsfwToggle,
-                        video_id,
-                        view_count: post.views,
-                        like_count: post.likes,
-                    });
-                }
+                track_nsfw_toggle(MixpanelVideoClickedCTAType::NsfwToggle);
             } else {
                 if !nsfw_enabled() && show_nsfw_permission() {
                     show_nsfw_permission.set(false);
-                    if let Some(global) =
-                        MixpanelGlobalProps::from_ev_ctx_with_nsfw_info(ev_ctx, false)
-                    {
-                        MixPanelEvent::track_nsfw_true(MixpanelNsfwToggleProps {
-                            user_id: global.user_id,
-                            visitor_id: global.visitor_id,
-                            is_logged_in: global.is_logged_in,
-                            canister_id: global.canister_id,
-                            is_nsfw_enabled: global.is_nsfw_enabled,
-                            publisher_user_id: post.poster_principal.to_text(),
-                            video_id,
-                            is_nsfw: post.is_nsfw,
-                        });
-                    }
+                    dispatch(ev_ctx, AnalyticsEvent::NsfwEnabled(nsfw_video));
                     set_nsfw_enabled(true);
                 } else {
                     set_nsfw_enabled(false);
-                    if let Some(global) =
-                        MixpanelGlobalProps::from_ev_ctx_with_nsfw_info(ev_ctx, false)
-                    {
-                        let is_hot_or_not = true;
-                        MixPanelEvent::track_video_clicked(MixpanelVideoClickedProps {
-                            user_id: global.user_id,
-                            visitor_id: global.visitor_id,
-                            is_logged_in: global.is_logged_in,
-                            is_nsfw: post.is_nsfw,
-                            canister_id: global.canister_id,
-                            is_nsfw_enabled: global.is_nsfw_enabled,
-                            is_game_enabled: is_hot_or_not,
-                            publisher_user_id: post.poster_principal.to_text(),
-                            game_type: MixpanelPostGameType::HotOrNot,
-                            cta_type: MixpanelVideoClickedCTAType::NsfwToggle,
-                            video_id,
-                            view_count: post.views,
-                            like_count: post.likes,
-                        });
-                    }
+                    track_nsfw_toggle(MixpanelVideoClickedCTAType::NsfwToggle);
                 }
                 // using set_href to hard reload the page
                 let window = window();
@@ -383,28 +323,9 @@ pub fn VideoDetailsOverlay(
         }
     });
 
-    let mixpanel_track_profile_click = move || {
-        let video_id = profile_click_video_id.clone();
-        let Some(global) = MixpanelGlobalProps::from_ev_ctx(ev_ctx) else {
-            return;
-        };
-        let is_hot_or_not = true;
-        MixPanelEvent::track_video_clicked(MixpanelVideoClickedProps {
-            user_id: global.user_id,
-            visitor_id: global.visitor_id,
-            is_logged_in: global.is_logged_in,
-            is_nsfw: post.is_nsfw,
-            canister_id: global.canister_id,
-            is_nsfw_enabled: global.is_nsfw_enabled,
-            is_game_enabled: is_hot_or_not,
-            publisher_user_id: post.poster_principal.to_text(),
-            game_type: MixpanelPostGameType::HotOrNot,
-            cta_type: MixpanelVideoClickedCTAType::CreatorProfile,
-            video_id,
-            view_count: post.views,
-            like_count: post.likes,
-        });
-    };
+    let track_profile_click = track_video_clicked;
+    let mixpanel_track_profile_click =
+        move || track_profile_click(MixpanelVideoClickedCTAType::CreatorProfile);
 
     let show_tutorial: RwSignal<bool> = RwSignal::new(false);
 
@@ -448,6 +369,7 @@ pub fn VideoDetailsOverlay(
                                 />
                                 {post.views}
                             </span>
+                            <FollowButton user_principal=post.poster_principal compact=true />
                         </div>
                         <ExpandableText clone:post description=post.description />
                     </div>
@@ -471,16 +393,25 @@ pub fn VideoDetailsOverlay(
             </div>
             <div class="flex flex-col gap-2 w-full">
                 <div class="flex flex-col gap-6 items-end self-end text-2xl pointer-events-auto md:text-3xl lg:text-4xl">
+                    <button on:click=move |_| show_score_chart.set(true)>
+                        <Icon attr:class="drop-shadow-lg" icon=icondata::AiLineChartOutlined />
+                    </button>
                     <button on:click=move |_| {
                         track_video_report();
                         show_report.set(true);
                     }>
                         <Icon attr:class="drop-shadow-lg" icon=icondata::TbMessageReport />
                     </button>
+                    <button aria-label="Not interested" on:click=move |_| click_not_interested()>
+                        <Icon attr:class="drop-shadow-lg" icon=icondata::AiEyeInvisibleOutlined />
+                    </button>
                     <a on:click=move |_| track_video_refer() href="/refer-earn">
                         <Icon attr:class="drop-shadow-lg" icon=icondata::AiGiftFilled />
                     </a>
                     <LikeAndAuthCanLoader post=post_c.clone() />
+                    <button on:click=move |_| show_comments.set(true)>
+                        <Icon attr:class="drop-shadow-lg" icon=icondata::AiMessageOutlined />
+                    </button>
                     <button on:click=move |_| share()>
                         <Icon attr:class="drop-shadow-lg" icon=HomeFeedShareIcon />
                     </button>
@@ -490,6 +421,7 @@ pub fn VideoDetailsOverlay(
                 </div>
             </div>
         </div>
+        <CommentSheet show=show_comments canister_id=comments_canister_id post_id=comments_post_id />
         <Modal show=show_share>
             <div class="flex flex-col gap-4 justify-center items-center text-white">
                 <span class="text-lg">Share</span>
@@ -511,6 +443,11 @@ pub fn VideoDetailsOverlay(
                 </div>
             </Show>
         </Modal>
+        <Modal show=show_score_chart>
+            <Show when=show_score_chart>
+                <ScoreChartPanel video_uid=score_chart_video_uid.clone() />
+            </Show>
+        </Modal>
         <Modal show=show_report>
             <div class="flex flex-col gap-4 justify-center items-center text-white">
                 <span class="text-lg">Report Post</span>
@@ -585,9 +522,52 @@ pub fn VideoDetailsOverlay(
     }.into_any()
 }
 
+pub(crate) fn entity_view(segment: TextSegment) -> AnyView {
+    const ENTITY_CLASS: &str = "font-semibold text-primary-500";
+    // links shouldn't toggle the truncation
+    let stop = |ev: leptos::ev::MouseEvent| ev.stop_propagation();
+
+    match segment {
+        TextSegment::Text(text) => text.to_string().into_any(),
+        TextSegment::Url(url) => {
+            let href = if url.to_ascii_lowercase().starts_with("www.") {
+                format!("https://{url}")
+            } else {
+                url.to_string()
+            };
+            view! {
+                <a href=href target="_blank" rel="noopener noreferrer nofollow" class="underline" on:click=stop>
+                    {url.to_string()}
+                </a>
+            }
+            .into_any()
+        }
+        // usernames can't be resolved yet, only principals are linked
+        TextSegment::Mention(name) => match Principal::from_text(name) {
+            Ok(principal) => view! {
+                <a href=format!("/profile/{principal}/posts") class=ENTITY_CLASS on:click=stop>
+                    {format!("@{name}")}
+                </a>
+            }
+            .into_any(),
+            Err(_) => view! { <span class=ENTITY_CLASS>{format!("@{name}")}</span> }.into_any(),
+        },
+        TextSegment::Hashtag(tag) => view! {
+            <a href=tag_url(&tag.to_lowercase()) class=ENTITY_CLASS on:click=stop>
+                {format!("#{tag}")}
+            </a>
+        }
+        .into_any(),
+    }
+}
+
 #[component]
 fn ExpandableText(description: String) -> impl IntoView {
     let truncated = RwSignal::new(true);
+    let segments = text_entities::parse(&description)
+        .into_iter()
+        .map(entity_view)
+        .collect_view();
 
     view! {
         <span
@@ -596,7 +576,7 @@ fn ExpandableText(description: String) -> impl IntoView {
 
             on:click=move |_| truncated.update(|e| *e = !*e)
         >
-            {description}
+            {segments}
         </span>
     }
 }
@@ -4,13 +4,14 @@ use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use super::{overlay::VideoDetailsOverlay, video_loader::VideoView};
-use crate::scrolling_post_view::MuteIconOverlay;
+use crate::{admin::moderation::is_post_hidden, scrolling_post_view::MuteIconOverlay};
 use component::{back_btn::go_back_or_fallback, spinner::FullScreenSpinner};
 use leptos_router::{components::Redirect, hooks::use_params, params::Params};
 use state::{
     audio_state::AudioState,
     canisters::{auth_state, unauth_canisters},
 };
+use utils::app_error::error_path;
 use utils::{bg_url, send_wrap};
 use yral_canisters_common::utils::posts::PostDetails;
 #[derive(Params, PartialEq, Clone, Copy)]
@@ -96,9 +97,17 @@ pub fn SinglePost() -> impl IntoView {
                     .get_post_details(params.canister_id, params.post_id)
                     .await
             };
-            post_uid
+            let post = post_uid
                 .map_err(|e| PostFetchError::GetUid(e.to_string()))
-                .and_then(|post| post.ok_or(PostFetchError::Unavailable))
+                .and_then(|post| post.ok_or(PostFetchError::Unavailable))?;
+            let hidden = is_post_hidden(post.canister_id, post.post_id, post.poster_principal)
+                .await
+                .inspect_err(|e| log::warn!("failed to check if post is hidden {e}"))
+                .unwrap_or_default();
+            if hidden {
+                return Err(PostFetchError::Unavailable);
+            }
+            Ok(post)
         })
     });
 
@@ -112,7 +121,7 @@ pub fn SinglePost() -> impl IntoView {
                         Err(PostFetchError::Invalid) => view! { <Redirect path="/" /> }.into_any(),
                         Err(PostFetchError::Unavailable) => view! { <UnavailablePost /> }.into_any(),
                         Err(PostFetchError::GetUid(e)) => {
-                            view! { <Redirect path=format!("/error?err={e}") /> }.into_any()
+                            view! { <Redirect path=error_path(e) /> }.into_any()
                         }
                     })
             }}
@@ -0,0 +1,260 @@
+//! Posts sharing a hashtag, linked from hashtags in post descriptions
+//!
+//! Posts are indexed under their hashtags on the server when they're published
+//! and again whenever a vote is placed on them, so a tag lists the posts that
+//! were published or played most recently
+
+use candid::Principal;
+use component::{back_btn::BackButton, spinner::FullScreenSpinner};
+use leptos::prelude::*;
+use leptos_meta::*;
+use leptos_router::{hooks::use_params, params::Params};
+use serde::{Deserialize, Serialize};
+use utils::{bg_url, send_wrap};
+
+/// Posts fetched per page of a tag
+pub const TAG_PAGE_SIZE: usize = 24;
+
+pub fn tag_url(tag: &str) -> String {
+    format!("/tag/{tag}")
+}
+
+/// A post listed under a tag
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TaggedPost {
+    pub canister_id: Principal,
+    pub post_id: u64,
+    pub uid: String,
+    pub views: u64,
+}
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use std::collections::BTreeSet;
+
+    use auth::server_impl::store::{KVStore, KVStoreImpl};
+    use utils::text_entities::normalize_hashtag;
+    use yral_canisters_client::individual_user_template::Result6;
+    use yral_canisters_common::{
+        utils::{posts::PostDetails, time::current_epoch},
+        Canisters,
+    };
+
+    use super::*;
+
+    /// Older posts are dropped from a tag beyond this
+    const MAX_POSTS_PER_TAG: usize = 1_000;
+    /// Latest posts of a user searched for a freshly published video
+    const PUBLISHED_LOOKUP_LIMIT: u64 = 10;
+
+    /// `{canister_id}:{post_id}` of the posts with the tag, by last indexed time
+    fn tag_key(tag: &str) -> String {
+        format!("hashtag-posts:{tag}")
+    }
+
+    pub async fn index_post(kv: &KVStoreImpl, post: &PostDetails) -> Result<(), ServerFnError> {
+        let tags = post
+            .hastags
+            .iter()
+            .filter_map(|tag| normalize_hashtag(tag).ok())
+            .collect::<BTreeSet<_>>();
+        let member = format!("{}:{}", post.canister_id, post.post_id);
+        let now = current_epoch().as_secs() as f64;
+        for tag in tags {
+            kv.index_add(tag_key(&tag), member.clone(), now).await?;
+            kv.index_trim(tag_key(&tag), MAX_POSTS_PER_TAG).await?;
+        }
+        Ok(())
+    }
+
+    /// Indexes the post `user` just published for `video_uid`
+    pub async fn index_published_post(
+        kv: &KVStoreImpl,
+        cans: &Canisters<false>,
+        user: Principal,
+        video_uid: &str,
+    ) -> Result<(), ServerFnError> {
+        let Some(canister_id) = cans.get_individual_canister_by_user_principal(user).await? else {
+            return Ok(());
+        };
+        let posts = cans
+            .individual_user(canister_id)
+            .await
+            .get_posts_of_this_user_profile_with_pagination_cursor(0, PUBLISHED_LOOKUP_LIMIT)
+            .await?;
+        let Result6::Ok(posts) = posts else {
+            return Ok(());
+        };
+        let Some(post) = posts
+            .into_iter()
+            .map(|details| PostDetails::from_canister_post(false, canister_id, details))
+            .find(|post| post.uid == video_uid)
+        else {
+            log::warn!("published post {video_uid} of {user} not found");
+            return Ok(());
+        };
+        index_post(kv, &post).await
+    }
+
+    /// Most recently indexed posts of the tag, as (canister_id, post_id)
+    pub async fn tagged_posts(
+        kv: &KVStoreImpl,
+        tag: &str,
+        offset: usize,
+    ) -> Result<Vec<(Principal, u64)>, ServerFnError> {
+        let Ok(tag) = normalize_hashtag(tag) else {
+            return Ok(vec![]);
+        };
+        let members = kv
+            .index_range(tag_key(&tag), offset, TAG_PAGE_SIZE, true)
+            .await?;
+        Ok(members
+            .iter()
+            .filter_map(|member| {
+                let (canister_id, post_id) = member.split_once(':')?;
+                Some((
+                    Principal::from_text(canister_id).ok()?,
+                    post_id.parse().ok()?,
+                ))
+            })
+            .collect())
+    }
+}
+
+/// Lists a post under its hashtags right after `user` published it, only
+/// posts which exist on the user's canister are indexed
+#[server(endpoint = "index_published_post", input = server_fn::codec::Json)]
+pub async fn index_published_post(user: Principal, video_uid: String) -> Result<(), ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+    use yral_canisters_common::Canisters;
+
+    let kv: KVStoreImpl = expect_context();
+    let cans: Canisters<false> = expect_context();
+    server::index_published_post(&kv, &cans, user, &video_uid).await
+}
+
+#[server(endpoint = "tagged_posts", input = server_fn::codec::Json)]
+pub async fn fetch_tagged_posts(
+    tag: String,
+    offset: usize,
+) -> Result<Vec<TaggedPost>, ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+    use futures::future::join_all;
+    use yral_canisters_common::Canisters;
+
+    let kv: KVStoreImpl = expect_context();
+    let cans: Canisters<false> = expect_context();
+    let posts = server::tagged_posts(&kv, &tag, offset).await?;
+    let details = join_all(
+        posts
+            .into_iter()
+            .map(|(canister_id, post_id)| cans.get_post_details(canister_id, post_id)),
+    )
+    .await;
+
+    Ok(details
+        .into_iter()
+        .filter_map(|res| res.ok().flatten())
+        .map(|post| TaggedPost {
+            canister_id: post.canister_id,
+            post_id: post.post_id,
+            uid: post.uid,
+            views: post.views,
+        })
+        .collect())
+}
+
+#[derive(Params, PartialEq, Clone)]
+struct TagParams {
+    tag: String,
+}
+
+#[component]
+fn TaggedPostCard(post: TaggedPost) -> impl IntoView {
+    view! {
+        <div class="relative w-full basis-1/3 md:basis-1/4 xl:basis-1/5">
+            <a
+                class="block relative m-2 rounded-md border aspect-9/16 border-white/20"
+                href=format!("/hot-or-not/{}/{}", post.canister_id, post.post_id)
+            >
+                <img class="object-cover w-full h-full rounded-md" src=bg_url(post.uid) />
+                <span class="absolute right-2 bottom-1 text-xs text-white">
+                    {format!("{} views", post.views)}
+                </span>
+            </a>
+        </div>
+    }
+}
+
+#[component]
+pub fn TagFeed() -> impl IntoView {
+    let params = use_params::<TagParams>();
+    let tag = Memo::new(move |_| {
+        params
+            .get()
+            .map(|p| p.tag.trim_start_matches('#').to_lowercase())
+            .unwrap_or_default()
+    });
+    let posts = RwSignal::new(Vec::<TaggedPost>::new());
+    let exhausted = RwSignal::new(false);
+
+    let load_more = Action::new(move |&()| {
+        let tag = tag.get_untracked();
+        let offset = posts.with_untracked(|p| p.len());
+        send_wrap(async move {
+            match fetch_tagged_posts(tag, offset).await {
+                Ok(page) => {
+                    if page.len() < TAG_PAGE_SIZE {
+                        exhausted.set(true);
+                    }
+                    posts.update(|p| p.extend(page));
+                }
+                Err(e) => {
+                    log::warn!("failed to load tagged posts {e}");
+                    exhausted.set(true);
+                }
+            }
+        })
+    });
+    Effect::new(move || {
+        tag.track();
+        posts.set(vec![]);
+        exhausted.set(false);
+        load_more.dispatch(());
+    });
+
+    view! {
+        <Title text=move || format!("YRAL - #{}", tag.get()) />
+        <div class="flex flex-col items-center pt-4 pb-12 w-dvw min-h-dvh bg-black text-white">
+            <div class="flex relative flex-row justify-center items-center px-4 w-full">
+                <div class="absolute left-4">
+                    <BackButton fallback="/".to_string() />
+                </div>
+                <span class="text-lg font-bold">{move || format!("#{}", tag.get())}</span>
+            </div>
+            <div class="flex flex-row flex-wrap justify-center px-2 mt-4 w-full max-w-5xl">
+                <For each=move || posts.get() key=|p| (p.canister_id, p.post_id) let:post>
+                    <TaggedPostCard post />
+                </For>
+            </div>
+            <Show when=move || load_more.pending().get()>
+                <FullScreenSpinner />
+            </Show>
+            <Show when=move || {
+                exhausted.get() && !load_more.pending().get() && posts.with(|p| p.is_empty())
+            }>
+                <span class="mt-8 text-sm text-neutral-400">No posts with this tag yet</span>
+            </Show>
+            <Show when=move || !exhausted.get() && !load_more.pending().get()>
+                <button
+                    class="py-2 px-6 mt-6 text-sm rounded-full bg-white/10"
+                    on:click=move |_| {
+                        load_more.dispatch(());
+                    }
+                >
+                    Load more
+                </button>
+            </Show>
+        </div>
+    }
+}
@@ -6,14 +6,23 @@ use leptos::prelude::*;
 
 use state::canisters::AuthState;
 use utils::{
+    consent::{self, ConsentCategory},
     host::show_nsfw_content,
     ml_feed::{
         get_ml_feed_clean, get_ml_feed_coldstart_clean, get_ml_feed_coldstart_nsfw,
-        get_ml_feed_nsfw,
+        get_ml_feed_nsfw, post_details_to_post_item,
     },
     posts::FetchCursor,
 };
 use yral_canisters_common::{utils::posts::PostDetails, Canisters, Error as CanistersError};
+use yral_types::post::PostItem;
+
+/// Posts the ML feed must not return: the session queue and "Not interested" posts
+fn feed_filter(video_queue: Vec<PostDetails>, not_interested: Vec<PostItem>) -> Vec<PostItem> {
+    let mut filter = post_details_to_post_item(video_queue);
+    filter.extend(not_interested);
+    filter
+}
 
 type PostsStream<'a> = Pin<Box<dyn Stream<Item = Vec<Result<PostDetails, CanistersError>>> + 'a>>;
 
@@ -110,26 +119,32 @@ impl<
         chunks: usize,
         allow_nsfw: bool,
         video_queue: Vec<PostDetails>,
+        not_interested: Vec<PostItem>,
     ) -> Result<FetchVideosRes<'a>, ServerFnError> {
+        // the coldstart feed doesn't use the user's history
+        if !consent::current().allows(ConsentCategory::Personalization) {
+            return self
+                .fetch_post_uids_mlfeed_cache_chunked(
+                    chunks,
+                    allow_nsfw,
+                    video_queue,
+                    not_interested,
+                )
+                .await;
+        }
+
         let user_canister_id = self.user_canister().await?;
+        let filter_results = feed_filter(video_queue, not_interested);
 
         let show_nsfw = allow_nsfw || show_nsfw_content();
         let top_posts = if show_nsfw {
-            get_ml_feed_nsfw(
-                user_canister_id,
-                self.cursor.limit as u32,
-                video_queue.clone(),
-            )
-            .await
-            .map_err(|e| ServerFnError::new(format!("Error fetching ml feed: {e:?}")))?
+            get_ml_feed_nsfw(user_canister_id, self.cursor.limit as u32, filter_results)
+                .await
+                .map_err(|e| ServerFnError::new(format!("Error fetching ml feed: {e:?}")))?
         } else {
-            get_ml_feed_clean(
-                user_canister_id,
-                self.cursor.limit as u32,
-                video_queue.clone(),
-            )
-            .await
-            .map_err(|e| ServerFnError::new(format!("Error fetching ml feed: {e:?}")))?
+            get_ml_feed_clean(user_canister_id, self.cursor.limit as u32, filter_results)
+                .await
+                .map_err(|e| ServerFnError::new(format!("Error fetching ml feed: {e:?}")))?
         };
 
         let end = false;
@@ -158,26 +173,20 @@ impl<
         chunks: usize,
         allow_nsfw: bool,
         video_queue: Vec<PostDetails>,
+        not_interested: Vec<PostItem>,
     ) -> Result<FetchVideosRes<'a>, ServerFnError> {
         let user_canister_id = self.user_canister().await?;
+        let filter_results = feed_filter(video_queue, not_interested);
 
         let show_nsfw = allow_nsfw || show_nsfw_content();
         let top_posts = if show_nsfw {
-            get_ml_feed_coldstart_nsfw(
-                user_canister_id,
-                self.cursor.limit as u32,
-                video_queue.clone(),
-            )
-            .await
-            .map_err(|e| ServerFnError::new(format!("Error fetching ml feed: {e:?}")))?
+            get_ml_feed_coldstart_nsfw(user_canister_id, self.cursor.limit as u32, filter_results)
+                .await
+                .map_err(|e| ServerFnError::new(format!("Error fetching ml feed: {e:?}")))?
         } else {
-            get_ml_feed_coldstart_clean(
-                user_canister_id,
-                self.cursor.limit as u32,
-                video_queue.clone(),
-            )
-            .await
-            .map_err(|e| ServerFnError::new(format!("Error fetching ml feed: {e:?}")))?
+            get_ml_feed_coldstart_clean(user_canister_id, self.cursor.limit as u32, filter_results)
+                .await
+                .map_err(|e| ServerFnError::new(format!("Error fetching ml feed: {e:?}")))?
         };
 
         let end = false;
@@ -206,22 +215,38 @@ impl<
         chunks: usize,
         allow_nsfw: bool,
         video_queue: Vec<PostDetails>,
+        not_interested: Vec<PostItem>,
     ) -> Result<FetchVideosRes<'a>, ServerFnError> {
         if video_queue.len() < 10 {
             self.cursor.set_limit(30);
-            self.fetch_post_uids_mlfeed_cache_chunked(chunks, allow_nsfw, video_queue)
-                .await
+            self.fetch_post_uids_mlfeed_cache_chunked(
+                chunks,
+                allow_nsfw,
+                video_queue,
+                not_interested,
+            )
+            .await
         } else {
             let res = self
-                .fetch_post_uids_ml_feed_chunked(chunks, allow_nsfw, video_queue.clone())
+                .fetch_post_uids_ml_feed_chunked(
+                    chunks,
+                    allow_nsfw,
+                    video_queue.clone(),
+                    not_interested.clone(),
+                )
                 .await;
 
             match res {
                 Ok(res) => Ok(res),
                 Err(_) => {
                     self.cursor.set_limit(50);
-                    self.fetch_post_uids_mlfeed_cache_chunked(chunks, allow_nsfw, video_queue)
-                        .await
+                    self.fetch_post_uids_mlfeed_cache_chunked(
+                        chunks,
+                        allow_nsfw,
+                        video_queue,
+                        not_interested,
+                    )
+                    .await
                 }
             }
         }
@@ -0,0 +1,158 @@
+use candid::Principal;
+use component::{block_list::use_block_list, modal::Modal, option::SelectOption};
+use leptos::prelude::*;
+use leptos_icons::*;
+use state::canisters::auth_state;
+use utils::{report::ReportOption, send_wrap};
+
+use crate::admin::moderation::record_user_report;
+
+/// Report and block buttons, hidden on the user's own profile
+#[component]
+pub fn ProfileActions(user_principal: Principal) -> impl IntoView {
+    let auth = auth_state();
+    let block_list = use_block_list();
+    let show_report = RwSignal::new(false);
+    let report_option = RwSignal::new(ReportOption::Nudity.as_str().to_string());
+    let reported = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    let toggle_block = Action::new(move |&()| {
+        let blocked = !block_list.is_blocked_untracked(user_principal);
+        let sync = auth.is_logged_in_with_oauth().get_untracked();
+        send_wrap(async move {
+            if let Err(e) = block_list.set(user_principal, blocked, sync).await {
+                log::warn!("failed to sync block {e}");
+            }
+        })
+    });
+
+    let submit_report = Action::new(move |&()| {
+        let reason = report_option.get_untracked();
+        send_wrap(async move {
+            match record_user_report(user_principal, reason).await {
+                Ok(()) => {
+                    error.set(None);
+                    reported.set(true);
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        })
+    });
+
+    let is_blocked = move || block_list.is_blocked(user_principal);
+
+    view! {
+        <Suspense>
+            {move || Suspend::new(async move {
+                let own_profile = auth.user_principal.await.ok() == Some(user_principal);
+                (!own_profile)
+                    .then(|| {
+                        view! {
+                            <div class="flex flex-row gap-4 justify-center pt-2 text-xs text-neutral-400">
+                                <button
+                                    class="flex flex-row gap-1 items-center"
+                                    disabled=move || toggle_block.pending().get()
+                                    on:click=move |_| {
+                                        toggle_block.dispatch(());
+                                    }
+                                >
+                                    <Icon icon=icondata::AiStopOutlined />
+                                    {move || if is_blocked() { "Unblock" } else { "Block" }}
+                                </button>
+                                <button
+                                    class="flex flex-row gap-1 items-center"
+                                    on:click=move |_| show_report.set(true)
+                                >
+                                    <Icon icon=icondata::TbMessageReport />
+                                    Report
+                                </button>
+                            </div>
+                        }
+                    })
+            })}
+        </Suspense>
+        <Modal show=show_report>
+            <div class="flex flex-col gap-4 justify-center items-center text-white">
+                <span class="text-lg">Report User</span>
+                <Show
+                    when=move || !reported()
+                    fallback=|| view! { <span class="text-md">Thanks, our moderators will take a look</span> }
+                >
+                    <span class="text-lg">Please select a reason:</span>
+                    <div class="max-w-full text-black text-md">
+                        <select
+                            class="block p-2 w-full text-sm rounded-lg"
+                            on:change=move |ev| report_option.set(event_target_value(&ev))
+                        >
+                            {ReportOption::all()
+                                .into_iter()
+                                .map(|option| {
+                                    view! {
+                                        <SelectOption
+                                            value=report_option.read_only()
+                                            is=option.as_str().to_string()
+                                        />
+                                    }
+                                })
+                                .collect_view()}
+                        </select>
+                    </div>
+                    <Show when=move || error.with(|e| e.is_some())>
+                        <span class="text-xs text-red-500">{move || error.get().unwrap_or_default()}</span>
+                    </Show>
+                    <button
+                        disabled=move || submit_report.pending().get()
+                        on:click=move |_| {
+                            submit_report.dispatch(());
+                        }
+                    >
+                        <div class="p-1 bg-pink-500 rounded-lg">Submit</div>
+                    </button>
+                </Show>
+            </div>
+        </Modal>
+    }
+}
+
+/// Shown in place of a blocked user's posts
+#[component]
+pub fn BlockedNotice(user_principal: Principal) -> impl IntoView {
+    let auth = auth_state();
+    let block_list = use_block_list();
+    let unblock = Action::new(move |&()| {
+        let sync = auth.is_logged_in_with_oauth().get_untracked();
+        send_wrap(async move {
+            if let Err(e) = block_list.set(user_principal, false, sync).await {
+                log::warn!("failed to sync unblock {e}");
+            }
+        })
+    });
+
+    view! {
+        <div class="flex flex-col gap-3 items-center py-12 text-neutral-400">
+            <Icon attr:class="text-5xl" icon=icondata::AiStopOutlined />
+            <span>You blocked this user</span>
+            <button
+                class="py-1.5 px-6 text-sm font-semibold text-white rounded-full border border-white/40"
+                disabled=move || unblock.pending().get()
+                on:click=move |_| {
+                    unblock.dispatch(());
+                }
+            >
+                Unblock
+            </button>
+        </div>
+    }
+}
+
+/// Shown in place of the posts of a creator hidden by moderators
+#[component]
+pub fn HiddenCreatorNotice() -> impl IntoView {
+    view! {
+        <div class="flex flex-col gap-3 items-center py-12 text-neutral-400">
+            <Icon attr:class="text-5xl" icon=icondata::AiEyeInvisibleOutlined />
+            <span>"This user's posts are unavailable"</span>
+        </div>
+    }
+}
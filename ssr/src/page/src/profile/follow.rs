@@ -0,0 +1,486 @@
+use candid::Principal;
+use component::block_list::use_block_list;
+use leptos::html;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use state::canisters::auth_state;
+use utils::send_wrap;
+use yral_canisters_common::cursored_data::{CursoredDataProvider, KeyedData, PageEntry};
+
+use super::ic::ProfileStream;
+
+/// Maximum number of creators a single user can follow
+pub const MAX_FOLLOWING: usize = 2000;
+/// Most entries returned for a single page of a follow list
+pub const MAX_FOLLOW_PAGE: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+pub enum FollowError {
+    #[error("Login required to follow creators")]
+    LoginRequired,
+    #[error("You can't follow yourself")]
+    SelfFollow,
+    #[error("You can follow at most {MAX_FOLLOWING} creators")]
+    TooManyFollowing,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FollowList {
+    Followers,
+    Following,
+}
+
+impl FollowList {
+    /// Profile tab the list is shown in
+    pub fn tab(&self) -> &'static str {
+        match self {
+            Self::Followers => "followers",
+            Self::Following => "following",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub struct FollowStatus {
+    pub followers: usize,
+    pub following: usize,
+    /// Whether the caller follows this user
+    pub is_following: bool,
+}
+
+/// Entry of a followers or following list
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FollowProfile {
+    pub principal: Principal,
+    pub username_or_principal: String,
+    pub display_name: String,
+    pub profile_pic: String,
+}
+
+impl KeyedData for FollowProfile {
+    type Key = Principal;
+
+    fn key(&self) -> Self::Key {
+        self.principal
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use auth::server_impl::store::{KVStore, KVStoreImpl};
+    use candid::Principal;
+    use leptos::prelude::ServerFnError;
+    use yral_canisters_common::{utils::profile::ProfileDetails, Canisters};
+
+    use super::*;
+
+    /// Principals in the list of `user`, by when the edge was added
+    pub(super) fn list_key(user: Principal, list: FollowList) -> String {
+        format!("follow-edges-{}:{user}", list.tab())
+    }
+
+    /// `len` entries of the list from `start`, newest first
+    pub async fn follow_page(
+        kv: &KVStoreImpl,
+        user: Principal,
+        list: FollowList,
+        start: usize,
+        len: usize,
+    ) -> Result<Vec<Principal>, ServerFnError> {
+        let members = kv
+            .index_range(list_key(user, list), start, len, true)
+            .await?;
+        Ok(members
+            .iter()
+            .filter_map(|p| Principal::from_text(p).ok())
+            .collect())
+    }
+
+    /// The whole list, newest first
+    pub async fn follow_list(
+        kv: &KVStoreImpl,
+        user: Principal,
+        list: FollowList,
+    ) -> Result<Vec<Principal>, ServerFnError> {
+        let len = kv.index_len(list_key(user, list)).await?;
+        follow_page(kv, user, list, 0, len).await
+    }
+
+    /// Updates both sides of the follow edge, a no-op if it's already in the requested state
+    ///
+    /// Both sides are idempotent set operations, so a retry repairs an edge
+    /// left half written by a failure
+    pub async fn set_following(
+        kv: &KVStoreImpl,
+        follower: Principal,
+        target: Principal,
+        follow: bool,
+    ) -> Result<(), ServerFnError> {
+        use yral_canisters_common::utils::time::current_epoch;
+
+        if follower == target {
+            return Err(FollowError::SelfFollow.into());
+        }
+        let following_key = list_key(follower, FollowList::Following);
+        let followers_key = list_key(target, FollowList::Followers);
+
+        if !follow {
+            kv.index_remove(following_key, target.to_text()).await?;
+            kv.index_remove(followers_key, follower.to_text()).await?;
+            return Ok(());
+        }
+
+        let already_following = kv
+            .index_score(following_key.clone(), target.to_text())
+            .await?
+            .is_some();
+        if !already_following && kv.index_len(following_key.clone()).await? >= MAX_FOLLOWING {
+            return Err(FollowError::TooManyFollowing.into());
+        }
+        let now = current_epoch().as_millis() as f64;
+        kv.index_add(following_key.clone(), target.to_text(), now)
+            .await?;
+        if let Err(e) = kv.index_add(followers_key, follower.to_text(), now).await {
+            if !already_following {
+                _ = kv.index_remove(following_key, target.to_text()).await;
+            }
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Removes the user from both sides of every follow edge
+    pub async fn delete_user_data(kv: &KVStoreImpl, user: Principal) -> Result<(), ServerFnError> {
+        for target in follow_list(kv, user, FollowList::Following).await? {
+            kv.index_remove(list_key(target, FollowList::Followers), user.to_text())
+                .await?;
+        }
+        for follower in follow_list(kv, user, FollowList::Followers).await? {
+            kv.index_remove(list_key(follower, FollowList::Following), user.to_text())
+                .await?;
+        }
+        kv.index_delete(list_key(user, FollowList::Following))
+            .await?;
+        kv.index_delete(list_key(user, FollowList::Followers))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn follow_status(
+        kv: &KVStoreImpl,
+        user: Principal,
+        caller: Option<Principal>,
+    ) -> Result<FollowStatus, ServerFnError> {
+        let is_following = match caller {
+            Some(caller) => kv
+                .index_score(list_key(user, FollowList::Followers), caller.to_text())
+                .await?
+                .is_some(),
+            None => false,
+        };
+        Ok(FollowStatus {
+            followers: kv.index_len(list_key(user, FollowList::Followers)).await?,
+            following: kv.index_len(list_key(user, FollowList::Following)).await?,
+            is_following,
+        })
+    }
+
+    pub async fn follow_profile(
+        cans: &Canisters<false>,
+        kv: &KVStoreImpl,
+        principal: Principal,
+    ) -> Result<FollowProfile, ServerFnError> {
+        let user_canister = cans
+            .get_individual_canister_by_user_principal(principal)
+            .await?
+            .ok_or_else(|| ServerFnError::new("User canister not found"))?;
+        let user = cans.individual_user(user_canister).await;
+        let mut details = ProfileDetails::from(user.get_profile_details().await?);
+        crate::settings::profile::server::profile_edits(kv, principal)
+            .await?
+            .apply(&mut details);
+
+        Ok(FollowProfile {
+            principal,
+            username_or_principal: details.username_or_principal(),
+            display_name: details.display_name_or_fallback(),
+            profile_pic: details.profile_pic_or_random(),
+        })
+    }
+}
+
+/// Follows or unfollows `target` as the caller, returns the new status of `target`
+#[server(endpoint = "follow_user", input = server_fn::codec::Json)]
+pub async fn follow_user(target: Principal, follow: bool) -> Result<FollowStatus, ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let Some(caller) = extract_principal_impl().await? else {
+        return Err(FollowError::LoginRequired.into());
+    };
+    let kv: KVStoreImpl = expect_context();
+    server::set_following(&kv, caller, target, follow).await?;
+    server::follow_status(&kv, target, Some(caller)).await
+}
+
+#[server(endpoint = "get_follow_status", input = server_fn::codec::Json)]
+pub async fn get_follow_status(user: Principal) -> Result<FollowStatus, ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let kv: KVStoreImpl = expect_context();
+    let caller = extract_principal_impl().await?;
+    server::follow_status(&kv, user, caller).await
+}
+
+/// Page of principals in `user`'s list, `true` if it's the last page
+///
+/// Pages are cut to [MAX_FOLLOW_PAGE] entries
+#[server(endpoint = "get_follows", input = server_fn::codec::Json)]
+pub async fn get_follows(
+    user: Principal,
+    list: FollowList,
+    start: usize,
+    end: usize,
+) -> Result<(Vec<Principal>, bool), ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+
+    use auth::server_impl::store::KVStore;
+
+    let end = end.min(start.saturating_add(MAX_FOLLOW_PAGE));
+    let kv: KVStoreImpl = expect_context();
+    let page = server::follow_page(&kv, user, list, start, end.saturating_sub(start)).await?;
+    let list_end = end >= kv.index_len(server::list_key(user, list)).await?;
+    Ok((page, list_end))
+}
+
+#[server(endpoint = "get_follow_profiles", input = server_fn::codec::Json)]
+pub async fn get_follow_profiles(
+    user: Principal,
+    list: FollowList,
+    start: usize,
+    end: usize,
+) -> Result<(Vec<FollowProfile>, bool), ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+    use futures::{stream::FuturesOrdered, StreamExt};
+    use yral_canisters_common::Canisters;
+
+    let (principals, list_end) = get_follows(user, list, start, end).await?;
+    let kv: KVStoreImpl = expect_context();
+    let cans: Canisters<false> = use_context().unwrap_or_default();
+    let profiles = principals
+        .into_iter()
+        .map(|p| server::follow_profile(&cans, &kv, p))
+        .collect::<FuturesOrdered<_>>()
+        .filter_map(|res| async move {
+            res.inspect_err(|e| log::warn!("failed to load follow profile {e}"))
+                .ok()
+        })
+        .collect()
+        .await;
+    Ok((profiles, list_end))
+}
+
+#[derive(Clone, Copy)]
+pub struct FollowsProvider {
+    user: Principal,
+    list: FollowList,
+}
+
+impl FollowsProvider {
+    pub fn new(user: Principal, list: FollowList) -> Self {
+        Self { user, list }
+    }
+}
+
+impl CursoredDataProvider for FollowsProvider {
+    type Data = FollowProfile;
+    type Error = ServerFnError;
+
+    async fn get_by_cursor_inner(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Result<PageEntry<FollowProfile>, ServerFnError> {
+        let (data, end) = get_follow_profiles(self.user, self.list, start, end).await?;
+        Ok(PageEntry { data, end })
+    }
+}
+
+/// Follow/unfollow toggle for `user_principal`, hidden on the user's own content
+///
+/// `status` is loaded by the button and can be shared with a [FollowCounts]
+#[component]
+pub fn FollowButton(
+    user_principal: Principal,
+    #[prop(optional)] status: RwSignal<Option<FollowStatus>>,
+    #[prop(optional)] compact: bool,
+) -> impl IntoView {
+    let auth = auth_state();
+    let error = RwSignal::new(None::<String>);
+
+    let status_res = Resource::new(|| (), move |_| send_wrap(get_follow_status(user_principal)));
+
+    let toggle_follow = Action::new(move |&()| {
+        let follow = !status.with_untracked(|s| s.is_some_and(|s| s.is_following));
+        send_wrap(async move {
+            match follow_user(user_principal, follow).await {
+                Ok(new_status) => {
+                    error.set(None);
+                    status.set(Some(new_status));
+                }
+                Err(e) => {
+                    log::warn!("failed to update follow {e}");
+                    error.set(Some(e.to_string()));
+                }
+            }
+        })
+    });
+
+    let is_following = move || status.with(|s| s.is_some_and(|s| s.is_following));
+    let label = move || {
+        if is_following() {
+            "Following"
+        } else {
+            "Follow"
+        }
+    };
+    let class = move || {
+        let size = if compact {
+            "px-2 py-0.5 text-xs"
+        } else {
+            "px-6 py-1.5 text-sm"
+        };
+        let color = if is_following() {
+            "border border-white/40 text-white"
+        } else {
+            "bg-primary-600 text-white"
+        };
+        format!("{size} {color} rounded-full font-semibold disabled:opacity-50")
+    };
+
+    view! {
+        <Suspense>
+            {move || Suspend::new(async move {
+                let own_profile = auth.user_principal.await.ok() == Some(user_principal);
+                match status_res.await {
+                    Ok(s) => status.set(Some(s)),
+                    Err(e) => log::warn!("failed to load follow status {e}"),
+                }
+                (!own_profile)
+                    .then(|| {
+                        view! {
+                            <div class="flex flex-col items-center">
+                                <button
+                                    class=class
+                                    disabled=move || toggle_follow.pending().get()
+                                    on:click=move |ev| {
+                                        ev.prevent_default();
+                                        ev.stop_propagation();
+                                        toggle_follow.dispatch(());
+                                    }
+                                >
+                                    {label}
+                                </button>
+                                <Show when=move || !compact && error.with(|e| e.is_some())>
+                                    <span class="pt-1 text-xs text-red-500">
+                                        {move || error.get().unwrap_or_default()}
+                                    </span>
+                                </Show>
+                            </div>
+                        }
+                    })
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+pub fn FollowCounts(
+    user_principal: Principal,
+    status: RwSignal<Option<FollowStatus>>,
+) -> impl IntoView {
+    let count = move |list: FollowList| {
+        status.with(|s| {
+            s.map(|s| match list {
+                FollowList::Followers => s.followers,
+                FollowList::Following => s.following,
+            })
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "-".to_string())
+        })
+    };
+
+    view! {
+        <div class="flex flex-row gap-6 justify-center pt-3 text-sm">
+            <a class="flex flex-col items-center" href=format!("/profile/{user_principal}/followers")>
+                <span class="font-bold text-white">{move || count(FollowList::Followers)}</span>
+                <span class="text-neutral-400">Followers</span>
+            </a>
+            <a class="flex flex-col items-center" href=format!("/profile/{user_principal}/following")>
+                <span class="font-bold text-white">{move || count(FollowList::Following)}</span>
+                <span class="text-neutral-400">Following</span>
+            </a>
+        </div>
+    }
+}
+
+#[component]
+fn FollowRow(profile: FollowProfile, _ref: NodeRef<html::Div>) -> impl IntoView {
+    let block_list = use_block_list();
+    let user = profile.principal;
+    let profile = StoredValue::new(profile);
+
+    // blocked users render as an empty row, the stream still needs
+    // every row for its cursor and scroll trigger
+    view! {
+        <div node_ref=_ref class="w-full">
+            <Show when=move || !block_list.is_blocked(user)>
+                {move || {
+                    let profile = profile.get_value();
+                    view! {
+                        <div class="flex flex-row gap-3 justify-between items-center py-2 w-full">
+                            <a
+                                class="flex flex-row flex-1 gap-3 items-center min-w-0"
+                                href=format!("/profile/{}/posts", profile.principal)
+                            >
+                                <img
+                                    class="object-cover w-10 h-10 rounded-full"
+                                    alt=profile.username_or_principal.clone()
+                                    src=profile.profile_pic
+                                />
+                                <div class="flex flex-col min-w-0">
+                                    <span class="font-semibold text-white truncate">
+                                        {profile.display_name}
+                                    </span>
+                                    <span class="text-xs truncate text-neutral-400">
+                                        {profile.username_or_principal}
+                                    </span>
+                                </div>
+                            </a>
+                            <FollowButton user_principal=profile.principal compact=true />
+                        </div>
+                    }
+                }}
+            </Show>
+        </div>
+    }
+}
+
+#[component]
+pub fn FollowListView(user_principal: Principal, list: FollowList) -> impl IntoView {
+    let provider = FollowsProvider::new(user_principal, list);
+    let empty_text = match list {
+        FollowList::Followers => "No followers yet",
+        FollowList::Following => "Not following anyone yet",
+    };
+
+    view! {
+        <ProfileStream
+            provider
+            empty_graphic=icondata::AiUserOutlined
+            empty_text
+            children=move |profile, _ref| {
+                view! { <FollowRow profile _ref=_ref.unwrap_or_default() /> }
+            }
+        />
+    }
+}
@@ -1,13 +1,17 @@
+mod block;
+pub mod follow;
 mod ic;
 pub mod overlay;
 mod posts;
-mod profile_iter;
+pub(crate) mod profile_iter;
 pub mod profile_post;
 mod speculation;
 
+use block::{BlockedNotice, HiddenCreatorNotice, ProfileActions};
 use candid::Principal;
-use component::{connect::ConnectLogin, spinner::FullScreenSpinner};
+use component::{block_list::use_block_list, connect::ConnectLogin, spinner::FullScreenSpinner};
 use consts::MAX_VIDEO_ELEMENTS_FOR_FEED;
+use follow::{FollowButton, FollowCounts, FollowList, FollowListView};
 use indexmap::IndexSet;
 use leptos::prelude::*;
 use leptos_icons::*;
@@ -21,6 +25,8 @@ use state::{
 };
 
 use utils::{mixpanel::mixpanel_events::*, posts::FeedPostCtx, send_wrap};
+
+use crate::{admin::moderation::use_creator_hidden, settings::profile::get_profile_edits};
 use yral_canisters_common::utils::{posts::PostDetails, profile::ProfileDetails};
 
 #[derive(Clone)]
@@ -101,9 +107,15 @@ fn ListSwitcher1(user_canister: Principal, user_principal: Principal) -> impl In
         });
     }
 
+    let block_list = use_block_list();
+    let is_blocked = move || block_list.is_blocked(user_principal);
+    let is_hidden = use_creator_hidden(user_principal);
+
     let current_tab = Memo::new(move |_| match tab.get().as_str() {
         "posts" => 0,
         "stakes" => 1,
+        "followers" => 2,
+        "following" => 3,
         _ => 0,
     });
 
@@ -126,17 +138,31 @@ fn ListSwitcher1(user_canister: Principal, user_principal: Principal) -> impl In
 
         <div class="flex flex-col gap-y-12 justify-center pb-12 w-11/12 sm:w-7/12">
             <Show when=move || current_tab() == 0>
-                <ProfilePosts user_canister />
+                <Show when=move || !is_blocked() fallback=move || view! { <BlockedNotice user_principal /> }>
+                    <Show when=move || !is_hidden() fallback=HiddenCreatorNotice>
+                        <ProfilePosts user_canister />
+                    </Show>
+                </Show>
             </Show>
             <Show when=move || current_tab() == 1>
                 <ProfileSpeculations user_canister user_principal />
             </Show>
+            <Show when=move || current_tab() == 2>
+                <FollowListView user_principal list=FollowList::Followers />
+            </Show>
+            <Show when=move || current_tab() == 3>
+                <FollowListView user_principal list=FollowList::Following />
+            </Show>
         </div>
     }
 }
 
 #[component]
-fn ProfileViewInner(user: ProfileDetails, user_canister: Principal) -> impl IntoView {
+fn ProfileViewInner(
+    user: ProfileDetails,
+    user_canister: Principal,
+    bio: Option<String>,
+) -> impl IntoView {
     let user_principal = user.principal;
     let username_or_principal = user.username_or_principal();
     let profile_pic = user.profile_pic_or_random();
@@ -145,6 +171,7 @@ fn ProfileViewInner(user: ProfileDetails, user_canister: Principal) -> impl Into
 
     let auth = auth_state();
     let is_connected = auth.is_logged_in_with_oauth();
+    let follow_status = RwSignal::new(None);
 
     view! {
         <div class="overflow-y-auto pt-10 pb-12 min-h-screen text-white bg-black">
@@ -165,6 +192,19 @@ fn ProfileViewInner(user: ProfileDetails, user_canister: Principal) -> impl Into
                             >
                                 {display_name}
                             </span>
+                            {bio
+                                .map(|bio| {
+                                    view! {
+                                        <p class="pt-2 max-w-xs text-sm whitespace-pre-line text-neutral-300">
+                                            {bio}
+                                        </p>
+                                    }
+                                })}
+                            <FollowCounts user_principal status=follow_status />
+                            <div class="pt-3">
+                                <FollowButton user_principal status=follow_status />
+                            </div>
+                            <ProfileActions user_principal />
                             <Suspense>
                                 {move || {
                                     auth.user_principal
@@ -252,24 +292,29 @@ pub fn ProfileView() -> impl IntoView {
         send_wrap(async move {
             let profile_principal =
                 profile_principal.ok_or_else(|| ServerFnError::new("Invalid principal"))?;
-            if let Some(user_can) = auth
+            let (mut user_details, user_canister) = if let Some(user_can) = auth
                 .auth_cans_if_available(cans.clone())
                 .filter(|can| can.user_principal() == profile_principal)
             {
-                return Ok::<_, ServerFnError>((
-                    user_can.profile_details(),
-                    user_can.user_canister(),
-                ));
-            }
+                (user_can.profile_details(), user_can.user_canister())
+            } else {
+                let user_canister = cans
+                    .get_individual_canister_by_user_principal(profile_principal)
+                    .await?
+                    .ok_or_else(|| ServerFnError::new("Failed to get user canister"))?;
+                let user = cans.individual_user(user_canister).await;
+                let user_details = user.get_profile_details().await?;
+                (ProfileDetails::from(user_details), user_canister)
+            };
 
-            let user_canister = cans
-                .get_individual_canister_by_user_principal(profile_principal)
-                .await?
-                .ok_or_else(|| ServerFnError::new("Failed to get user canister"))?;
-            let user = cans.individual_user(user_canister).await;
-            let user_details = user.get_profile_details().await?;
+            // edits are optional, the profile is still shown if they fail to load
+            let edits = get_profile_edits(profile_principal)
+                .await
+                .inspect_err(|e| log::warn!("failed to load profile edits {e}"))
+                .unwrap_or_default();
+            edits.apply(&mut user_details);
 
-            Ok::<_, ServerFnError>((ProfileDetails::from(user_details), user_canister))
+            Ok::<_, ServerFnError>((user_details, user_canister, edits.bio))
         })
     });
 
@@ -279,8 +324,8 @@ pub fn ProfileView() -> impl IntoView {
             {move || Suspend::new(async move {
                 let res = user_details.await;
                 match res {
-                    Ok((user, user_canister)) => {
-                        view! { <ProfileComponent user user_canister /> }.into_any()
+                    Ok((user, user_canister, bio)) => {
+                        view! { <ProfileComponent user user_canister bio /> }.into_any()
                     }
                     _ => view! { <Redirect path="/" /> }.into_any(),
                 }
@@ -291,7 +336,11 @@ pub fn ProfileView() -> impl IntoView {
 }
 
 #[component]
-pub fn ProfileComponent(user: ProfileDetails, user_canister: Principal) -> impl IntoView {
+pub fn ProfileComponent(
+    user: ProfileDetails,
+    user_canister: Principal,
+    #[prop(optional_no_strip)] bio: Option<String>,
+) -> impl IntoView {
     let ProfilePostsContext {
         video_queue,
         start_index,
@@ -305,5 +354,5 @@ pub fn ProfileComponent(user: ProfileDetails, user_canister: Principal) -> impl
         *idx = 0;
     });
 
-    view! { <ProfileViewInner user user_canister /> }
+    view! { <ProfileViewInner user user_canister bio /> }
 }
@@ -6,12 +6,14 @@ use candid::Principal;
 use component::profile_placeholders::NoMorePostsGraphic;
 use state::canisters::{auth_state, unauth_canisters};
 use utils::{
-    bg_url, event_streaming::events::ProfileViewVideo, mixpanel::mixpanel_events::*,
+    analytics::{dispatch, AnalyticsEvent, VideoProps},
+    bg_url,
     profile::PostsProvider,
 };
 
 use super::ic::ProfileStream;
 use super::ProfilePostsContext;
+use crate::admin::moderation::use_post_hidden;
 use leptos::html;
 use yral_canisters_common::utils::posts::PostDetails;
 
@@ -21,6 +23,9 @@ fn Post(
     user_canister: Principal,
     _ref: NodeRef<html::Div>,
     post_index: Option<usize>,
+    /// hidden by moderators, shown as unavailable
+    #[prop(into)]
+    hidden: Signal<bool>,
 ) -> impl IntoView {
     let image_error = RwSignal::new(false);
 
@@ -31,37 +36,31 @@ fn Post(
 
     let auth = auth_state();
     let ev_ctx = auth.event_ctx();
-    let post_details = details.clone();
+    let video = VideoProps::from(&details);
     let video_click = move || {
-        ProfileViewVideo.send_event(ev_ctx, post_details.clone());
-        if let Some(global) = MixpanelGlobalProps::from_ev_ctx(ev_ctx) {
-            MixPanelEvent::track_video_clicked_profile(MixpanelVideoClickedProfileProps {
-                user_id: global.user_id,
-                visitor_id: global.visitor_id,
-                is_logged_in: global.is_logged_in,
-                canister_id: global.canister_id.clone(),
-                is_nsfw_enabled: global.is_nsfw_enabled,
-                publisher_user_id: post_details.poster_principal.to_text(),
-                like_count: post_details.likes,
-                view_count: post_details.views,
-                is_game_enabled: true,
-                video_id: post_details.uid.clone(),
-                game_type: MixpanelPostGameType::HotOrNot,
-                cta_type: MixpanelVideoClickedCTAType::VideoPlay,
+        let is_own_profile = ev_ctx
+            .user_details()
+            .is_some_and(|user| user.canister_id == user_canister);
+        dispatch(
+            ev_ctx,
+            AnalyticsEvent::ProfileVideoClicked {
+                video: video.clone(),
                 position: post_index.map(|i| i as u64 + 1),
-                is_own_profile: user_canister.to_text() == global.canister_id,
-                is_nsfw: post_details.is_nsfw,
-                page_name: "profile".to_string(),
-            });
-        }
+                is_own_profile,
+            },
+        );
     };
 
     view! {
         <div node_ref=_ref class="relative w-full basis-1/3 md:basis-1/4 xl:basis-1/5">
             <div class="relative m-2 h-full rounded-md border aspect-9/16 border-white/20">
-                <a class="w-full h-full" href=profile_post_url on:click=move |_| video_click()>
+                <a
+                    class="w-full h-full"
+                    href=move || (!hidden()).then(|| profile_post_url.clone())
+                    on:click=move |_| video_click()
+                >
                     <Show
-                        when=image_error
+                        when=move || image_error() || hidden()
                         fallback=move || {
                             view! {
                                 <img
@@ -115,8 +114,12 @@ pub fn ProfilePosts(user_canister: Principal) -> impl IntoView {
             empty_graphic=NoMorePostsGraphic
             empty_text="No Videos Uploaded yet"
             children=move |details, _ref| {
+                let (canister_id, post_id, creator) =
+                    (details.canister_id, details.post_id, details.poster_principal);
+                let hidden = use_post_hidden(canister_id, post_id, creator);
                 view! {
                     <Post
+                        hidden
                         post_index=video_queue.get_untracked().get_index_of(&details)
                         details=details
                         user_canister=user_canister
@@ -11,7 +11,7 @@ use leptos_use::use_debounce_fn;
 use state::canisters::{auth_state, unauth_canisters};
 use utils::{route::failure_redirect, send_wrap, try_or_redirect};
 
-use crate::scrolling_post_view::ScrollingPostView;
+use crate::{admin::moderation::hidden_among, scrolling_post_view::ScrollingPostView};
 
 use super::{
     overlay::YourProfileOverlay,
@@ -96,7 +96,12 @@ pub fn PostViewWithUpdatesProfile(
             let res = try_or_redirect!(posts_res);
 
             queue_end.set(res.end);
-            res.posts.into_iter().for_each(|p| {
+            let hidden = hidden_among(&res.posts).await;
+            let visible = res
+                .posts
+                .into_iter()
+                .filter(|p| !hidden.is_hidden(p.canister_id, p.post_id, p.poster_principal));
+            visible.for_each(|p| {
                 video_queue.try_update(|q| {
                     if q.insert(p.clone()) {
                         let len_vq = q.len();
@@ -13,6 +13,7 @@ use http::StatusCode;
 use leptos::prelude::*;
 use leptos_router::hooks::use_navigate;
 use log;
+use state::balance_store::{BalanceKey, BalanceStore};
 use state::canisters::auth_state;
 use utils::{mixpanel::mixpanel_events::*, send_wrap, try_or_redirect_opt};
 use yral_canisters_common::utils::token::balance::TokenBalance;
@@ -190,6 +191,7 @@ pub fn PndWithdrawal() -> impl IntoView {
     });
     let is_claiming = send_claim.pending();
     let claim_res = send_claim.value();
+    let balance_store = BalanceStore::use_store();
 
     Effect::new(move |_| {
         if let Some(res) = claim_res.get() {
@@ -197,6 +199,7 @@ pub fn PndWithdrawal() -> impl IntoView {
             match res {
                 Ok(_) => {
                     let cents = cents().e8s;
+                    balance_store.debit(BalanceKey::Cents, cents.clone());
                     nav(
                         &format!("/pnd/withdraw/success?cents={cents}"),
                         Default::default(),
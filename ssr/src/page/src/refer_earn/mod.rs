@@ -13,6 +13,7 @@ use component::connect::ConnectLogin;
 use component::{back_btn::BackButton, buttons::HighlightedButton, title::TitleText};
 use state::app_state::AppState;
 use state::canisters::auth_state;
+use utils::app_error::error_path;
 use utils::event_streaming::events::{Refer, ReferShareLink};
 use utils::mixpanel::mixpanel_events::*;
 use utils::web::copy_to_clipboard;
@@ -162,7 +163,7 @@ fn ReferCode() -> impl IntoView {
                 let res = auth.user_principal.await;
                 match res {
                     Ok(user_principal) => Either::Left(view! { <ReferLoaded user_principal /> }),
-                    Err(e) => Either::Right(view! { <Redirect path=format!("/error?err={e}") /> }),
+                    Err(e) => Either::Right(view! { <Redirect path=error_path(e) /> }),
                 }
             })}
         </Suspense>
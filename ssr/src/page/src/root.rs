@@ -8,6 +8,7 @@ use leptos_meta::*;
 use leptos_router::components::Redirect;
 use leptos_router::hooks::use_query_map;
 use leptos_use::storage::use_local_storage;
+use utils::app_error::{error_path, report_error, AppErrorCode};
 use utils::host::show_nsfw_content;
 use utils::ml_feed::{get_ml_feed_coldstart_clean, get_ml_feed_coldstart_nsfw};
 use yral_types::post::PostItem;
@@ -102,8 +103,8 @@ pub fn YralRootPage() -> impl IntoView {
                                 });
                             format!("/hot-or-not/{canister_id}/{post_id}")
                         }
-                        Ok(None) => "/error?err=No Posts Found".to_string(),
-                        Err(e) => format!("/error?err={e}"),
+                        Ok(None) => report_error(AppErrorCode::NoPostsFound, "No posts found"),
+                        Err(e) => error_path(e),
                     };
                     if let Some(user_refer) = user_refer {
                         url.push_str(&format!("?user_refer={user_refer}"));
@@ -0,0 +1,469 @@
+use component::{back_btn::BackButton, spinner::FullScreenSpinner, title::TitleText};
+use leptos::either::Either;
+use leptos::prelude::*;
+use leptos_icons::*;
+use leptos_meta::*;
+use leptos_router::{hooks::use_params, params::Params};
+use serde::{Deserialize, Serialize};
+use utils::notifications::get_device_registeration_token;
+use yral_canisters_common::Canisters;
+use yral_metadata_client::MetadataClient;
+use yral_metadata_types::error::ApiError;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeletionStep {
+    /// FCM tokens of this device, revoked by the browser
+    NotificationTokens,
+    /// the account on the off-chain service
+    OffChainAccount,
+    /// everything keyed by the user in the KV store
+    ServerData,
+    AirdropRecords,
+    /// cookies, local storage and in-memory caches, cleared by the browser
+    DeviceData,
+}
+
+impl DeletionStep {
+    pub fn all() -> [Self; 5] {
+        [
+            Self::NotificationTokens,
+            Self::OffChainAccount,
+            Self::ServerData,
+            Self::AirdropRecords,
+            Self::DeviceData,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::NotificationTokens => "Notification tokens",
+            Self::OffChainAccount => "Account",
+            Self::ServerData => "Profile, follows and settings",
+            Self::AirdropRecords => "Airdrop records",
+            Self::DeviceData => "Data on this device",
+        }
+    }
+
+    /// Steps run by the browser and reported back to the job
+    pub fn is_client_side(&self) -> bool {
+        matches!(self, Self::NotificationTokens | Self::DeviceData)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum StepStatus {
+    Pending,
+    Done,
+    Failed(String),
+}
+
+impl<E: std::fmt::Display> From<Result<(), E>> for StepStatus {
+    fn from(res: Result<(), E>) -> Self {
+        match res {
+            Ok(()) => Self::Done,
+            Err(e) => Self::Failed(e.to_string()),
+        }
+    }
+}
+
+/// A tracked account deletion, the id is only known to the deleting browser
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DeletionJob {
+    pub id: String,
+    /// epoch secs
+    pub started_at: u64,
+    pub steps: Vec<(DeletionStep, StepStatus)>,
+}
+
+impl DeletionJob {
+    pub fn status(&self, step: DeletionStep) -> StepStatus {
+        self.steps
+            .iter()
+            .find(|(s, _)| *s == step)
+            .map(|(_, status)| status.clone())
+            .unwrap_or(StepStatus::Pending)
+    }
+
+    fn set_status(&mut self, step: DeletionStep, status: StepStatus) {
+        match self.steps.iter_mut().find(|(s, _)| *s == step) {
+            Some((_, current)) => *current = status,
+            None => self.steps.push((step, status)),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        DeletionStep::all()
+            .into_iter()
+            .all(|step| self.status(step) == StepStatus::Done)
+    }
+
+    /// Nothing is removed if the off-chain account couldn't be deleted
+    pub fn account_deleted(&self) -> bool {
+        self.status(DeletionStep::OffChainAccount) == StepStatus::Done
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use std::time::Duration;
+
+    use auth::server_impl::store::{KVStore, KVStoreImpl};
+    use candid::Principal;
+
+    use super::*;
+
+    /// Browser steps are reported within this
+    const REPORT_TOKEN_TTL: Duration = Duration::from_secs(60 * 60);
+
+    fn job_key(id: &str) -> String {
+        format!("account-deletion:{id}")
+    }
+
+    fn report_token_key(id: &str) -> String {
+        format!("account-deletion-token:{id}")
+    }
+
+    pub async fn deletion_job(
+        kv: &KVStoreImpl,
+        id: &str,
+    ) -> Result<Option<DeletionJob>, ServerFnError> {
+        match kv.read(job_key(id)).await? {
+            Some(raw) if !raw.is_empty() => Ok(Some(serde_json::from_str(&raw)?)),
+            _ => Ok(None),
+        }
+    }
+
+    pub async fn write_job(kv: &KVStoreImpl, job: &DeletionJob) -> Result<(), ServerFnError> {
+        kv.write(job_key(&job.id), serde_json::to_string(job)?)
+            .await?;
+        Ok(())
+    }
+
+    /// A failed write is only logged so the remaining steps still run,
+    /// the job is written once more when all steps are done
+    pub async fn update_step(
+        kv: &KVStoreImpl,
+        job: &mut DeletionJob,
+        step: DeletionStep,
+        status: StepStatus,
+    ) {
+        if let StepStatus::Failed(e) = &status {
+            log::warn!("account deletion {}: {step:?} failed: {e}", job.id);
+        }
+        job.set_status(step, status);
+        if let Err(e) = write_job(kv, job).await {
+            log::warn!("account deletion {}: failed to save {step:?}: {e}", job.id);
+        }
+    }
+
+    /// Secret handed to the deleting browser to report its own steps,
+    /// its session is gone by then
+    pub async fn issue_report_token(
+        kv: &KVStoreImpl,
+        job_id: &str,
+    ) -> Result<String, ServerFnError> {
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        kv.write(report_token_key(job_id), token.clone()).await?;
+        kv.expire(report_token_key(job_id), REPORT_TOKEN_TTL)
+            .await?;
+        Ok(token)
+    }
+
+    pub async fn verify_report_token(
+        kv: &KVStoreImpl,
+        job_id: &str,
+        token: &str,
+    ) -> Result<bool, ServerFnError> {
+        let stored = kv.read(report_token_key(job_id)).await?;
+        Ok(stored.is_some_and(|stored| !stored.is_empty() && stored == token))
+    }
+
+    /// Every KV entry keyed by the user and what they left on others' content,
+    /// all parts run even if some fail
+    pub async fn delete_server_data(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<(), ServerFnError> {
+        let results = [
+            (
+                "profile",
+                crate::settings::profile::server::delete_user_data(kv, user).await,
+            ),
+            (
+                "follows",
+                crate::profile::follow::server::delete_user_data(kv, user).await,
+            ),
+            (
+                "blocked users",
+                component::block_list::server::delete_blocked_users(kv, user).await,
+            ),
+            (
+                "bet config",
+                crate::post_view::bet::config::server::delete_user_data(kv, user).await,
+            ),
+            (
+                "score history",
+                crate::post_view::bet::history::server::delete_user_data(kv, user).await,
+            ),
+            (
+                "comments",
+                crate::post_view::comments::server::delete_user_data(kv, user).await,
+            ),
+            (
+                "reports",
+                crate::admin::moderation::server::delete_user_data(kv, user).await,
+            ),
+            (
+                "upload drafts",
+                crate::upload::drafts::server::delete_user_data(kv, user).await,
+            ),
+            (
+                "exports",
+                crate::settings::export::server::delete_user_data(kv, user).await,
+            ),
+            // identity of logged out sessions, the browser logs out right after
+            (
+                "session",
+                kv.delete(user.to_text()).await.map_err(ServerFnError::from),
+            ),
+        ];
+
+        let failed: Vec<_> = results
+            .into_iter()
+            .filter_map(|(part, res)| res.err().map(|e| format!("{part}: {e}")))
+            .collect();
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(ServerFnError::new(failed.join("; ")))
+        }
+    }
+}
+
+/// A started deletion, `report_token` authorizes the browser's steps
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StartedDeletion {
+    pub job: DeletionJob,
+    pub report_token: String,
+}
+
+/// Deletes the caller's account and everything stored for it on the server,
+/// `notification_tokens` is the result of the browser revoking its FCM token
+#[server(endpoint = "start_account_deletion", input = server_fn::codec::Json)]
+pub async fn start_account_deletion(
+    notification_tokens: StepStatus,
+) -> Result<StartedDeletion, ServerFnError> {
+    use auth::server_impl::{extract_identity_impl, store::KVStoreImpl};
+    use candid::Principal;
+    use yral_canisters_common::utils::time::current_epoch;
+
+    use crate::settings::delete_user::initiate_delete_user;
+    use crate::wallet::airdrop::dolr_airdrop::delete_airdrop_records;
+
+    let Some(identity) = extract_identity_impl().await? else {
+        return Err(ServerFnError::new("Login required to delete your account"));
+    };
+    let user = Principal::self_authenticating(&identity.from_key);
+    let kv: KVStoreImpl = expect_context();
+
+    let mut job = DeletionJob {
+        id: uuid::Uuid::new_v4().simple().to_string(),
+        started_at: current_epoch().as_secs(),
+        steps: DeletionStep::all()
+            .into_iter()
+            .map(|step| (step, StepStatus::Pending))
+            .collect(),
+    };
+    server::write_job(&kv, &job).await?;
+    let report_token = server::issue_report_token(&kv, &job.id).await?;
+    server::update_step(
+        &kv,
+        &mut job,
+        DeletionStep::NotificationTokens,
+        notification_tokens,
+    )
+    .await;
+
+    let res = initiate_delete_user(identity).await;
+    server::update_step(&kv, &mut job, DeletionStep::OffChainAccount, res.into()).await;
+    if job.account_deleted() {
+        let res = server::delete_server_data(&kv, user).await;
+        server::update_step(&kv, &mut job, DeletionStep::ServerData, res.into()).await;
+
+        let res = delete_airdrop_records(user).await;
+        server::update_step(&kv, &mut job, DeletionStep::AirdropRecords, res.into()).await;
+        log::info!("account deletion {} finished server side steps", job.id);
+    }
+
+    server::write_job(&kv, &job).await?;
+    Ok(StartedDeletion { job, report_token })
+}
+
+/// Records the result of a step run by the browser,
+/// `report_token` is the one returned when the deletion started
+#[server(endpoint = "report_deletion_step", input = server_fn::codec::Json)]
+pub async fn report_deletion_step(
+    job_id: String,
+    report_token: String,
+    step: DeletionStep,
+    status: StepStatus,
+) -> Result<(), ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+
+    if !step.is_client_side() {
+        return Err(ServerFnError::new("Step is run by the server"));
+    }
+    let kv: KVStoreImpl = expect_context();
+    if !server::verify_report_token(&kv, &job_id, &report_token).await? {
+        return Err(ServerFnError::new("Not allowed to update this deletion"));
+    }
+    let Some(mut job) = server::deletion_job(&kv, &job_id).await? else {
+        return Err(ServerFnError::new("Deletion not found"));
+    };
+    server::update_step(&kv, &mut job, step, status).await;
+    server::write_job(&kv, &job).await
+}
+
+#[server(endpoint = "get_deletion_job", input = server_fn::codec::Json)]
+pub async fn get_deletion_job(job_id: String) -> Result<Option<DeletionJob>, ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+
+    let kv: KVStoreImpl = expect_context();
+    server::deletion_job(&kv, &job_id).await
+}
+
+/// Unregisters this device from push notifications,
+/// nothing to revoke if they were never enabled
+pub async fn revoke_notification_token(cans: &Canisters<true>, notifs_enabled: bool) -> StepStatus {
+    if !notifs_enabled {
+        return StepStatus::Done;
+    }
+    let token = match get_device_registeration_token().await {
+        Ok(token) => token,
+        Err(e) => return StepStatus::Failed(e.to_string()),
+    };
+    match MetadataClient::<false>::default()
+        .unregister_device(cans.identity(), token)
+        .await
+    {
+        Ok(_) | Err(yral_metadata_client::Error::Api(ApiError::DeviceNotFound)) => StepStatus::Done,
+        Err(e) => StepStatus::Failed(format!("{e:?}")),
+    }
+}
+
+/// Wipes everything this site kept in the browser's local storage
+pub fn clear_local_storage() -> StepStatus {
+    window()
+        .local_storage()
+        .and_then(|storage| storage.map(|s| s.clear()).unwrap_or(Ok(())))
+        .map_err(|e| format!("{e:?}"))
+        .into()
+}
+
+#[derive(Params, PartialEq, Clone)]
+struct DeletionParams {
+    job_id: String,
+}
+
+#[component]
+fn StepRow(step: DeletionStep, status: StepStatus) -> impl IntoView {
+    let (icon, class, detail) = match status {
+        StepStatus::Pending => (icondata::AiClockCircleOutlined, "text-neutral-400", None),
+        StepStatus::Done => (icondata::AiCheckCircleFilled, "text-green-500", None),
+        StepStatus::Failed(e) => (icondata::AiCloseCircleFilled, "text-red-500", Some(e)),
+    };
+
+    view! {
+        <div class="flex flex-row gap-3 items-start w-full">
+            <Icon attr:class=format!("text-xl shrink-0 {class}") icon=icon />
+            <div class="flex flex-col">
+                <span>{step.label()}</span>
+                {detail.map(|e| view! { <span class="text-xs text-neutral-500 break-all">{e}</span> })}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn DeletionJobView(job: DeletionJob) -> impl IntoView {
+    let summary = if job.is_complete() {
+        "Your account and all data linked to it have been deleted."
+    } else if job.account_deleted() {
+        "Your account has been deleted, some data could not be removed yet. Contact support with the reference below."
+    } else {
+        "We couldn't delete your account, nothing has been removed. Please try again."
+    };
+    let retry = !job.account_deleted();
+
+    view! {
+        <div class="flex flex-col gap-6 py-8 px-8 w-full max-w-md">
+            <p class="text-sm text-neutral-300">{summary}</p>
+            <div class="flex flex-col gap-4">
+                {DeletionStep::all()
+                    .into_iter()
+                    .map(|step| view! { <StepRow step status=job.status(step) /> })
+                    .collect_view()}
+            </div>
+            <span class="text-xs text-neutral-500">"Reference: " {job.id.clone()}</span>
+            <Show when=move || retry>
+                <a
+                    class="py-2 px-4 text-sm text-center rounded-md bg-neutral-700"
+                    href="/settings/delete"
+                >
+                    Try again
+                </a>
+            </Show>
+            <a class="py-2 px-4 text-sm text-center rounded-md bg-primary-600" href="/">
+                Go home
+            </a>
+        </div>
+    }
+}
+
+#[component]
+pub fn AccountDeletionStatus() -> impl IntoView {
+    let params = use_params::<DeletionParams>();
+    let job = Resource::new(
+        move || params.with(|p| p.as_ref().map(|p| p.job_id.clone()).ok()),
+        |job_id| async move {
+            let Some(job_id) = job_id else {
+                return Ok(None);
+            };
+            get_deletion_job(job_id).await
+        },
+    );
+
+    view! {
+        <Title text="YRAL - Account deletion" />
+        <div class="flex flex-col items-center pt-2 pb-12 w-full min-h-screen text-white bg-black">
+            <TitleText justify_center=false>
+                <div class="flex flex-row justify-between">
+                    <BackButton fallback="/".to_string() />
+                    <span class="text-2xl font-bold">Account deletion</span>
+                    <div></div>
+                </div>
+            </TitleText>
+            <Suspense fallback=FullScreenSpinner>
+                {move || Suspend::new(async move {
+                    match job.await {
+                        Ok(Some(job)) => Either::Left(view! { <DeletionJobView job /> }),
+                        Ok(None) => {
+                            Either::Right(
+                                view! {
+                                    <span class="py-12 text-neutral-400">Deletion not found</span>
+                                },
+                            )
+                        }
+                        Err(e) => {
+                            Either::Right(
+                                view! {
+                                    <span class="py-12 text-red-500">{e.to_string()}</span>
+                                },
+                            )
+                        }
+                    }
+                })}
+            </Suspense>
+        </div>
+    }
+}
@@ -0,0 +1,147 @@
+use component::{back_btn::BackButton, spinner::FullScreenSpinner, title::TitleText};
+use leptos::prelude::*;
+use leptos_meta::*;
+use state::app_state::AppState;
+use utils::send_wrap;
+use yral_canisters_common::utils::token::balance::TokenBalance;
+
+use crate::post_view::bet::config::{update_bet_limits, BetConfigCtx, UserBetConfig};
+
+const COOLING_OFF_OPTIONS: [(u64, &str); 4] = [
+    (0, "No break"),
+    (1, "24 hours"),
+    (7, "7 days"),
+    (30, "30 days"),
+];
+
+#[component]
+fn BetLimitsForm(user_config: UserBetConfig) -> impl IntoView {
+    let bet_config = BetConfigCtx::use_ctx();
+    let limits = user_config.limits.clone();
+    let cooling_off = limits.cooling_off_active(user_config.now_secs);
+
+    let now = user_config.now_secs;
+    let pending_cap = limits
+        .pending_cap
+        .clone()
+        .filter(|pending| pending.effective_at > now);
+    let shown_cap = match &pending_cap {
+        Some(pending) => pending.daily_wager_cap,
+        None => limits.daily_cap(now),
+    };
+    let daily_cap = RwSignal::new(shown_cap.map(|cap| cap.to_string()).unwrap_or_default());
+    let pending_text = pending_cap.map(|pending| {
+        let hours = (pending.effective_at - now).div_ceil(60 * 60);
+        let limit = pending
+            .daily_wager_cap
+            .map(|cap| format!("limit of {cap} SATS"))
+            .unwrap_or_else(|| "removal of your limit".to_string());
+        format!("The {limit} applies in about {hours}h")
+    });
+    let cooling_off_days = RwSignal::new(0u64);
+    let error = RwSignal::new(None::<String>);
+
+    let save_action = Action::new(move |&()| {
+        let daily_wager_cap = daily_cap.get_untracked().trim().parse::<u64>().ok();
+        let cooling_off_days = Some(cooling_off_days.get_untracked());
+        send_wrap(async move {
+            match update_bet_limits(daily_wager_cap, cooling_off_days).await {
+                Ok(_) => {
+                    error.set(None);
+                    bet_config.refetch();
+                }
+                Err(e) => {
+                    log::warn!("failed to update bet limits {e}");
+                    error.set(Some("Failed to save your limits, please try again".into()));
+                }
+            }
+        })
+    });
+    let saving = save_action.pending();
+
+    let wagered = TokenBalance::new(user_config.wagered_today.into(), 0).humanize();
+
+    view! {
+        <div class="flex flex-col gap-8 py-12 px-8 w-full text-lg">
+            <p class="text-sm text-neutral-400">
+                "Set limits to keep your Hot or Not games fun. Lower limits apply right away, raising or removing a limit takes 24 hours."
+            </p>
+            <div class="flex flex-col gap-2">
+                <span class="font-semibold">Daily limit (SATS)</span>
+                <input
+                    type="number"
+                    min="0"
+                    placeholder="No limit"
+                    class="py-2 px-3 w-full text-white rounded-md outline-none bg-neutral-900"
+                    bind:value=daily_cap
+                />
+                <span class="text-sm text-neutral-400">"Wagered today: " {wagered} " SATS"</span>
+                {pending_text
+                    .map(|text| view! { <span class="text-sm text-primary-600">{text}</span> })}
+            </div>
+            <div class="flex flex-col gap-2">
+                <span class="font-semibold">Take a break</span>
+                <Show when=move || cooling_off>
+                    <span class="text-sm text-primary-600">
+                        "You're on a break. Breaks can be extended but not shortened."
+                    </span>
+                </Show>
+                <div class="flex flex-row flex-wrap gap-2">
+                    {COOLING_OFF_OPTIONS
+                        .into_iter()
+                        .map(|(days, label)| {
+                            view! {
+                                <button
+                                    class="py-2 px-3 text-sm font-semibold rounded-full"
+                                    class=("bg-primary-600", move || cooling_off_days() == days)
+                                    class=("bg-neutral-900", move || cooling_off_days() != days)
+                                    on:click=move |_| cooling_off_days.set(days)
+                                >
+                                    {label}
+                                </button>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            </div>
+            <Show when=move || error.with(|e| e.is_some())>
+                <span class="text-sm text-red-500">{move || error.get().unwrap_or_default()}</span>
+            </Show>
+            <button
+                class="py-3 w-full font-bold text-white rounded-md bg-primary-600 disabled:opacity-50"
+                disabled=saving
+                on:click=move |_| {
+                    save_action.dispatch(());
+                }
+            >
+                {move || if saving() { "Saving..." } else { "Save limits" }}
+            </button>
+        </div>
+    }
+}
+
+#[component]
+pub fn BetLimitsSettings() -> impl IntoView {
+    let app_state = use_context::<AppState>();
+    let page_title = app_state.unwrap().name.to_owned() + " - Betting Limits";
+    let bet_config = BetConfigCtx::use_ctx();
+
+    view! {
+        <Title text=page_title />
+        <div class="flex flex-col items-center pt-2 pb-12 w-full min-h-screen text-white bg-black">
+            <TitleText justify_center=false>
+                <div class="flex flex-row justify-between">
+                    <BackButton fallback="/settings".to_string() />
+                    <span class="text-2xl font-bold">Betting Limits</span>
+                    <div></div>
+                </div>
+            </TitleText>
+            <Suspense fallback=FullScreenSpinner>
+                {move || Suspend::new(async move {
+                    let user_config = bet_config.config.await;
+                    view! { <BetLimitsForm user_config /> }
+                })}
+            </Suspense>
+        </div>
+    }
+}
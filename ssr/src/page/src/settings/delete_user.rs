@@ -2,6 +2,7 @@ use consts::OFF_CHAIN_AGENT_URL;
 use leptos::prelude::ServerFnError;
 use reqwest::Client;
 use serde_json::json;
+use utils::http_client::HttpClientError;
 use yral_types::delegated_identity::DelegatedIdentityWire;
 
 pub async fn initiate_delete_user(identity: DelegatedIdentityWire) -> Result<(), ServerFnError> {
@@ -12,14 +13,13 @@ pub async fn initiate_delete_user(identity: DelegatedIdentityWire) -> Result<(),
 
     let url = OFF_CHAIN_AGENT_URL.join("api/v1/user").unwrap();
 
-    let response = client.delete(url).json(&body).send().await?;
+    let response = client
+        .delete(url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(HttpClientError::from)?;
+    HttpClientError::check(response).await?;
 
-    if response.status().is_success() {
-        Ok(())
-    } else {
-        Err(ServerFnError::ServerError(format!(
-            "Delete user failed with status: {}",
-            response.status()
-        )))
-    }
+    Ok(())
 }
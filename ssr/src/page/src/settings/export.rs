@@ -0,0 +1,768 @@
+use candid::Principal;
+use component::{back_btn::BackButton, spinner::FullScreenSpinner, title::TitleText};
+use hon_worker_common::GameRes;
+use leptos::either::Either;
+use leptos::prelude::*;
+use leptos_meta::*;
+use leptos_use::{use_interval, UseIntervalReturn};
+use serde::{Deserialize, Serialize};
+use server_fn::ServerFn;
+use state::hn_bet_state::VideoComparisonRecord;
+use utils::{send_wrap, time::parse_ns_to_datetime};
+use yral_canisters_common::utils::{
+    posts::PostDetails,
+    profile::ProfileDetails,
+    transaction::{TxnInfoType, TxnInfoWallet},
+};
+
+use crate::post_view::bet::config::BetLimits;
+use crate::settings::profile::ProfileEdits;
+use crate::upload::drafts::PostDraft;
+
+/// Everything this server keeps about the user, downloaded as JSON
+///
+/// Comments, likes and reports are capped to the latest `MAX_ARCHIVE_ITEMS` of each
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UserDataExport {
+    pub principal: Principal,
+    /// epoch secs
+    pub exported_at: u64,
+    /// `None` if the user's canister couldn't be reached
+    pub profile: Option<ProfileDetails>,
+    pub profile_edits: ProfileEdits,
+    pub following: Vec<Principal>,
+    pub followers: Vec<Principal>,
+    pub blocked_users: Vec<Principal>,
+    pub bet_limits: BetLimits,
+    pub upload_drafts: Vec<PostDraft>,
+    /// Latest hot or not comparisons, oldest first
+    pub score_history: Vec<VideoComparisonRecord>,
+    pub comments: Vec<ExportedComment>,
+    pub liked_comments: Vec<ExportedComment>,
+    /// Reports of posts, users and comments made by the user
+    pub reports: Vec<ExportedReport>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExportedComment {
+    pub id: String,
+    pub canister_id: Principal,
+    pub post_id: u64,
+    pub parent_id: Option<String>,
+    /// empty for deleted comments
+    pub text: String,
+    /// epoch secs
+    pub created_at: u64,
+    pub deleted: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExportedReport {
+    /// `post:{canister_id}:{post_id}`, `user:{principal}` or `comment:{id}`
+    pub subject: String,
+    pub reason: String,
+    /// epoch secs
+    pub reported_at: u64,
+}
+
+/// Archives still preparing after this long are assumed to be lost, e.g. to a restart
+pub const EXPORT_TIMEOUT_SECS: u64 = 30 * 60;
+/// Finished archives and their status are dropped after this
+pub const ARCHIVE_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+/// A new archive can be requested this long after the last successful one
+pub const EXPORT_COOLDOWN_SECS: u64 = 24 * 60 * 60;
+/// Upper bound on the bets, transactions per token, posts, comments, likes and reports in an archive
+pub const MAX_ARCHIVE_ITEMS: usize = 10_000;
+const STATUS_POLL_INTERVAL_MS: u64 = 5000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExportedTxn {
+    pub id: u64,
+    pub timestamp: u64,
+    pub kind: String,
+    pub from: Option<Principal>,
+    pub to: Option<Principal>,
+    pub amount: String,
+}
+
+impl From<TxnInfoWallet> for ExportedTxn {
+    fn from(info: TxnInfoWallet) -> Self {
+        let (from, to) = match info.tag {
+            TxnInfoType::Mint { to } => (None, Some(to)),
+            TxnInfoType::Burn { from } => (Some(from), None),
+            TxnInfoType::Received { from } => (Some(from), None),
+            TxnInfoType::Sent { to } => (None, Some(to)),
+            TxnInfoType::Transfer { from, to } => (Some(from), Some(to)),
+        };
+        Self {
+            id: info.id,
+            timestamp: info.timestamp,
+            kind: info.tag.to_text().to_string(),
+            from,
+            to,
+            amount: info.amount.humanize_float_truncate_to_dp(8),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TokenHistory {
+    pub symbol: String,
+    pub transactions: Vec<ExportedTxn>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExportedPost {
+    pub canister_id: Principal,
+    pub post_id: u64,
+    pub video_uid: String,
+    pub description: String,
+    pub hashtags: Vec<String>,
+    pub views: u64,
+    pub likes: u64,
+    pub is_nsfw: bool,
+}
+
+impl From<PostDetails> for ExportedPost {
+    fn from(post: PostDetails) -> Self {
+        Self {
+            canister_id: post.canister_id,
+            post_id: post.post_id,
+            video_uid: post.uid,
+            description: post.description,
+            hashtags: post.hastags,
+            views: post.views,
+            likes: post.likes,
+            is_nsfw: post.is_nsfw,
+        }
+    }
+}
+
+/// Epoch millis of the last claims, `None` if never claimed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct AirdropClaims {
+    pub sats_last_claimed_at: Option<u64>,
+    pub dolr_last_claimed_at: Option<u64>,
+}
+
+/// The full archive from `/settings/export`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DataArchive {
+    #[serde(flatten)]
+    pub account: UserDataExport,
+    pub bets: Vec<GameRes>,
+    pub wallet: Vec<TokenHistory>,
+    pub posts: Vec<ExportedPost>,
+    pub airdrop_claims: AirdropClaims,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ExportState {
+    Preparing,
+    Ready,
+    Failed(String),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExportJob {
+    /// epoch secs
+    pub requested_at: u64,
+    pub state: ExportState,
+}
+
+impl ExportJob {
+    pub fn is_stale(&self, now_secs: u64) -> bool {
+        self.state == ExportState::Preparing && now_secs > self.requested_at + EXPORT_TIMEOUT_SECS
+    }
+
+    /// Epoch secs after which another archive can be requested, failed exports can be retried right away
+    pub fn next_request_at(&self) -> u64 {
+        match self.state {
+            ExportState::Ready => self.requested_at + EXPORT_COOLDOWN_SECS,
+            ExportState::Preparing | ExportState::Failed(_) => self.requested_at,
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use std::{str::FromStr, time::Duration};
+
+    use auth::server_impl::store::{KVStore, KVStoreImpl};
+    use candid::Principal;
+    use consts::DOLR_AI_ROOT_CANISTER;
+    use yral_canisters_common::{
+        cursored_data::{
+            transaction::{IndexOrLedger, TxnHistory},
+            vote::VotesWithSatsProviderV3,
+            CursoredDataProvider,
+        },
+        utils::{time::current_epoch, token::RootType},
+        Canisters,
+    };
+    use yral_metadata_client::MetadataClient;
+
+    use super::*;
+    use crate::admin::moderation::server as moderation;
+    use crate::post_view::comments::server::{self as comments, StoredComment};
+    use crate::profile::follow::{server as follow, FollowList};
+    use crate::profile::profile_iter::{FixedFetchCursor, ProfVideoStream, ProfileVideoStream};
+
+    const ARCHIVE_PAGE_SIZE: usize = 50;
+
+    async fn profile_details(
+        cans: &Canisters<false>,
+        user: Principal,
+    ) -> Result<ProfileDetails, ServerFnError> {
+        let user_canister = cans
+            .get_individual_canister_by_user_principal(user)
+            .await?
+            .ok_or_else(|| ServerFnError::new("User canister not found"))?;
+        let individual = cans.individual_user(user_canister).await;
+        Ok(ProfileDetails::from(
+            individual.get_profile_details().await?,
+        ))
+    }
+
+    impl From<StoredComment> for ExportedComment {
+        fn from(comment: StoredComment) -> Self {
+            Self {
+                id: comment.id,
+                canister_id: comment.canister_id,
+                post_id: comment.post_id,
+                parent_id: comment.parent_id,
+                text: if comment.deleted {
+                    String::new()
+                } else {
+                    comment.text
+                },
+                created_at: comment.created_at,
+                deleted: comment.deleted,
+            }
+        }
+    }
+
+    async fn reports(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<Vec<ExportedReport>, ServerFnError> {
+        let mut reports: Vec<_> = moderation::reports_by(kv, user, MAX_ARCHIVE_ITEMS)
+            .await?
+            .into_iter()
+            .map(|(subject, r)| ExportedReport {
+                subject,
+                reason: r.reason,
+                reported_at: r.reported_at,
+            })
+            .collect();
+        reports.extend(
+            comments::reports_by(kv, user, MAX_ARCHIVE_ITEMS)
+                .await?
+                .into_iter()
+                .map(|(id, r)| ExportedReport {
+                    subject: format!("comment:{id}"),
+                    reason: r.reason,
+                    reported_at: r.reported_at,
+                }),
+        );
+        reports.sort_by(|a, b| b.reported_at.cmp(&a.reported_at));
+        Ok(reports)
+    }
+
+    pub async fn user_data(
+        cans: &Canisters<false>,
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<UserDataExport, ServerFnError> {
+        let profile = profile_details(cans, user)
+            .await
+            .inspect_err(|e| log::warn!("export: failed to load profile of {user}: {e}"))
+            .ok();
+
+        Ok(UserDataExport {
+            principal: user,
+            exported_at: current_epoch().as_secs(),
+            profile,
+            profile_edits: crate::settings::profile::server::profile_edits(kv, user).await?,
+            following: follow::follow_list(kv, user, FollowList::Following).await?,
+            followers: follow::follow_list(kv, user, FollowList::Followers).await?,
+            blocked_users: component::block_list::server::blocked_users(kv, user)
+                .await?
+                .unwrap_or_default(),
+            bet_limits: crate::post_view::bet::config::server::bet_limits(kv, user).await?,
+            upload_drafts: crate::upload::drafts::server::drafts(kv, user).await?,
+            score_history: crate::post_view::bet::history::server::score_history(kv, user).await?,
+            comments: comments::authored_comments(kv, user, MAX_ARCHIVE_ITEMS)
+                .await?
+                .into_iter()
+                .map(ExportedComment::from)
+                .collect(),
+            liked_comments: comments::liked_comments(kv, user, MAX_ARCHIVE_ITEMS)
+                .await?
+                .into_iter()
+                .map(ExportedComment::from)
+                .collect(),
+            reports: reports(kv, user).await?,
+        })
+    }
+
+    fn job_key(user: Principal) -> String {
+        format!("data-export:{user}")
+    }
+
+    fn archive_key(user: Principal) -> String {
+        format!("data-export-archive:{user}")
+    }
+
+    pub fn now_secs() -> u64 {
+        current_epoch().as_secs()
+    }
+
+    /// Sends the server function's response as a file download
+    pub fn attachment(filename: &str) -> Result<(), ServerFnError> {
+        use http::header::{HeaderValue, CACHE_CONTROL, CONTENT_DISPOSITION};
+        use leptos_axum::ResponseOptions;
+
+        let resp: ResponseOptions = expect_context();
+        let disposition = HeaderValue::from_str(&format!("attachment; filename=\"{filename}\""))
+            .map_err(ServerFnError::new)?;
+        resp.insert_header(CONTENT_DISPOSITION, disposition);
+        resp.insert_header(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+        Ok(())
+    }
+
+    pub async fn export_job(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<Option<ExportJob>, ServerFnError> {
+        match kv.read(job_key(user)).await? {
+            Some(raw) if !raw.is_empty() => Ok(Some(serde_json::from_str(&raw)?)),
+            _ => Ok(None),
+        }
+    }
+
+    pub async fn write_export_job(
+        kv: &KVStoreImpl,
+        user: Principal,
+        job: &ExportJob,
+    ) -> Result<(), ServerFnError> {
+        kv.write(job_key(user), serde_json::to_string(job)?).await?;
+        Ok(())
+    }
+
+    pub async fn archive(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<Option<DataArchive>, ServerFnError> {
+        match kv.read(archive_key(user)).await? {
+            Some(raw) if !raw.is_empty() => Ok(Some(serde_json::from_str(&raw)?)),
+            _ => Ok(None),
+        }
+    }
+
+    pub async fn delete_user_data(kv: &KVStoreImpl, user: Principal) -> Result<(), ServerFnError> {
+        kv.delete(job_key(user)).await?;
+        kv.delete(archive_key(user)).await?;
+        Ok(())
+    }
+
+    /// Every page of the provider, up to `MAX_ARCHIVE_ITEMS`
+    async fn collect_all<P: CursoredDataProvider>(
+        provider: &P,
+    ) -> Result<Vec<P::Data>, ServerFnError>
+    where
+        P::Error: std::fmt::Debug,
+    {
+        let mut items = vec![];
+        loop {
+            let start = items.len();
+            let page = provider
+                .get_by_cursor(start, start + ARCHIVE_PAGE_SIZE)
+                .await
+                .map_err(|e| ServerFnError::new(format!("{e:?}")))?;
+            items.extend(page.data);
+            if page.end || items.len() >= MAX_ARCHIVE_ITEMS {
+                break;
+            }
+        }
+        items.truncate(MAX_ARCHIVE_ITEMS);
+        Ok(items)
+    }
+
+    async fn wallet_history(
+        cans: &Canisters<false>,
+        user: Principal,
+    ) -> Result<Vec<TokenHistory>, ServerFnError> {
+        let mut wallet = vec![];
+        for root in ["btc", "usdc", DOLR_AI_ROOT_CANISTER] {
+            let root =
+                RootType::from_str(root).map_err(|e| ServerFnError::new(format!("{e:?}")))?;
+            let Some(meta) = cans.token_metadata_by_root_type(Some(user), root).await? else {
+                continue;
+            };
+            let provider = TxnHistory {
+                canisters: cans.clone(),
+                source: IndexOrLedger::Index {
+                    key_principal: user,
+                    index: meta.index,
+                },
+                decimals: meta.decimals,
+            };
+            let transactions = collect_all(&provider)
+                .await?
+                .into_iter()
+                .map(ExportedTxn::from)
+                .collect();
+            wallet.push(TokenHistory {
+                symbol: meta.symbol,
+                transactions,
+            });
+        }
+        Ok(wallet)
+    }
+
+    async fn uploaded_posts(
+        cans: &Canisters<false>,
+        user: Principal,
+    ) -> Result<Vec<ExportedPost>, ServerFnError> {
+        type ArchiveVidStream = ProfileVideoStream<{ ARCHIVE_PAGE_SIZE as u64 }>;
+
+        let Some(user_canister) = cans.get_individual_canister_by_user_principal(user).await?
+        else {
+            return Ok(vec![]);
+        };
+        let mut cursor = FixedFetchCursor::<{ ARCHIVE_PAGE_SIZE as u64 }> {
+            start: 0,
+            limit: ARCHIVE_PAGE_SIZE as u64,
+        };
+        let mut posts = vec![];
+        loop {
+            let res = ArchiveVidStream::fetch_next_posts(cursor, cans, user_canister).await?;
+            posts.extend(res.posts.into_iter().map(ExportedPost::from));
+            if res.end || posts.len() >= MAX_ARCHIVE_ITEMS {
+                break;
+            }
+            cursor.advance();
+        }
+        posts.truncate(MAX_ARCHIVE_ITEMS);
+        Ok(posts)
+    }
+
+    async fn build_archive(
+        cans: &Canisters<false>,
+        kv: &KVStoreImpl,
+        user: Principal,
+        airdrop_claims: AirdropClaims,
+    ) -> Result<DataArchive, ServerFnError> {
+        let bets = collect_all(&VotesWithSatsProviderV3::new(
+            user,
+            MetadataClient::default(),
+        ))
+        .await?;
+        Ok(DataArchive {
+            account: user_data(cans, kv, user).await?,
+            bets,
+            wallet: wallet_history(cans, user).await?,
+            posts: uploaded_posts(cans, user).await?,
+            airdrop_claims,
+        })
+    }
+
+    /// Builds and stores the archive, run in the background
+    pub async fn run_export(
+        cans: Canisters<false>,
+        kv: KVStoreImpl,
+        user: Principal,
+        mut job: ExportJob,
+        airdrop_claims: AirdropClaims,
+    ) {
+        let ttl = Duration::from_secs(ARCHIVE_TTL_SECS);
+        let res = async {
+            let archive = build_archive(&cans, &kv, user, airdrop_claims).await?;
+            kv.write(archive_key(user), serde_json::to_string(&archive)?)
+                .await?;
+            kv.expire(archive_key(user), ttl).await?;
+            Ok::<_, ServerFnError>(())
+        }
+        .await;
+
+        job.state = match res {
+            Ok(()) => ExportState::Ready,
+            Err(e) => {
+                log::warn!("data export of {user} failed: {e}");
+                ExportState::Failed(e.to_string())
+            }
+        };
+        let saved = async {
+            write_export_job(&kv, user, &job).await?;
+            kv.expire(job_key(user), ttl).await?;
+            Ok::<_, ServerFnError>(())
+        };
+        if let Err(e) = saved.await {
+            log::warn!("failed to save data export state of {user}: {e}");
+        }
+    }
+}
+
+/// Downloaded as `yral-data.json` by navigating to it
+#[server(
+    endpoint = "export_user_data",
+    input = server_fn::codec::GetUrl,
+    output = server_fn::codec::Json
+)]
+pub async fn export_user_data() -> Result<UserDataExport, ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+    use yral_canisters_common::Canisters;
+
+    let Some(user) = extract_principal_impl().await? else {
+        return Err(ServerFnError::new("Login required to export your data"));
+    };
+    let kv: KVStoreImpl = expect_context();
+    let cans: Canisters<false> = use_context().unwrap_or_default();
+    let data = server::user_data(&cans, &kv, user).await?;
+    server::attachment("yral-data.json")?;
+    Ok(data)
+}
+
+/// Starts building the archive in the background, returns the running export if there is one
+#[server(endpoint = "request_data_export", input = server_fn::codec::Json)]
+pub async fn request_data_export() -> Result<ExportJob, ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+    use yral_canisters_common::Canisters;
+
+    use crate::wallet::airdrop::{dolr_airdrop, last_airdrop_claimed_at};
+
+    let Some(user) = extract_principal_impl().await? else {
+        return Err(ServerFnError::new("Login required to export your data"));
+    };
+    let kv: KVStoreImpl = expect_context();
+    let now = server::now_secs();
+    if let Some(job) = server::export_job(&kv, user).await? {
+        if job.state == ExportState::Preparing && !job.is_stale(now) {
+            return Ok(job);
+        }
+        let next = job.next_request_at();
+        if now < next {
+            let hours = (next - now).div_ceil(60 * 60);
+            return Err(ServerFnError::new(format!(
+                "You can request a new archive in {hours} hour(s)"
+            )));
+        }
+    }
+
+    // the DOLR airdrop db is only reachable from the request context
+    let airdrop_claims = AirdropClaims {
+        sats_last_claimed_at: last_airdrop_claimed_at(user).await?,
+        dolr_last_claimed_at: dolr_airdrop::last_airdrop_claimed_at(user).await?,
+    };
+    let job = ExportJob {
+        requested_at: now,
+        state: ExportState::Preparing,
+    };
+    server::write_export_job(&kv, user, &job).await?;
+
+    let cans: Canisters<false> = use_context().unwrap_or_default();
+    tokio::spawn(server::run_export(
+        cans,
+        kv,
+        user,
+        job.clone(),
+        airdrop_claims,
+    ));
+    Ok(job)
+}
+
+#[server(endpoint = "data_export_status", input = server_fn::codec::Json)]
+pub async fn data_export_status() -> Result<Option<ExportJob>, ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let Some(user) = extract_principal_impl().await? else {
+        return Ok(None);
+    };
+    let kv: KVStoreImpl = expect_context();
+    let Some(mut job) = server::export_job(&kv, user).await? else {
+        return Ok(None);
+    };
+    if job.is_stale(server::now_secs()) {
+        job.state = ExportState::Failed("The export timed out".into());
+        server::write_export_job(&kv, user, &job).await?;
+    }
+    Ok(Some(job))
+}
+
+/// The caller's archive, downloaded as `yral-archive.json` by navigating to it
+#[server(
+    endpoint = "download_data_export",
+    input = server_fn::codec::GetUrl,
+    output = server_fn::codec::Json
+)]
+pub async fn download_data_export() -> Result<DataArchive, ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let Some(user) = extract_principal_impl().await? else {
+        return Err(ServerFnError::new("Login required to export your data"));
+    };
+    let kv: KVStoreImpl = expect_context();
+    let archive = server::archive(&kv, user)
+        .await?
+        .ok_or_else(|| ServerFnError::new("Your archive is not ready yet"))?;
+    server::attachment("yral-archive.json")?;
+    Ok(archive)
+}
+
+/// Offers the caller's data as a file download, prepared by the server on click
+#[component]
+pub fn ExportDataButton() -> impl IntoView {
+    view! {
+        <a
+            class="py-2 px-4 w-full text-sm text-center rounded-md border border-white/30"
+            href=<ExportUserData as ServerFn>::PATH
+            download="yral-data.json"
+            rel="external"
+        >
+            "Download my data first"
+        </a>
+    }
+}
+
+#[component]
+fn DownloadArchive() -> impl IntoView {
+    view! {
+        <a
+            class="py-2 px-4 w-full text-sm font-semibold text-center rounded-md bg-primary-600"
+            href=<DownloadDataExport as ServerFn>::PATH
+            download="yral-archive.json"
+            rel="external"
+        >
+            "Save yral-archive.json"
+        </a>
+    }
+}
+
+#[component]
+pub fn DataExport() -> impl IntoView {
+    let UseIntervalReturn {
+        counter,
+        pause,
+        resume,
+        ..
+    } = use_interval(STATUS_POLL_INTERVAL_MS);
+    let status = Resource::new(move || counter.get(), |_| send_wrap(data_export_status()));
+
+    let request_error = RwSignal::new(None::<String>);
+    let request = Action::new(move |&()| {
+        send_wrap(async move {
+            match request_data_export().await {
+                Ok(_) => request_error.set(None),
+                Err(e) => request_error.set(Some(e.to_string())),
+            }
+            status.refetch();
+        })
+    });
+
+    // poll only while the archive is being prepared
+    Effect::new(move |_| {
+        let Some(Ok(job)) = status.get() else {
+            return;
+        };
+        if job.is_some_and(|j| j.state == ExportState::Preparing) {
+            resume();
+        } else {
+            pause();
+        }
+    });
+
+    let request_button = move |label: &'static str| {
+        view! {
+            <button
+                class="py-2 px-4 w-full text-sm rounded-md border border-white/30 disabled:opacity-50"
+                disabled=move || request.pending().get()
+                on:click=move |_| {
+                    request.dispatch(());
+                }
+            >
+                {label}
+            </button>
+        }
+    };
+
+    view! {
+        <Title text="YRAL - Export your data" />
+        <div class="flex flex-col items-center pt-2 pb-12 w-full min-h-screen text-white bg-black">
+            <TitleText justify_center=false>
+                <div class="flex flex-row justify-between">
+                    <BackButton fallback="/settings".to_string() />
+                    <span class="text-2xl font-bold">Export your data</span>
+                    <div></div>
+                </div>
+            </TitleText>
+            <div class="flex flex-col gap-6 py-8 px-8 w-full max-w-md">
+                <p class="text-sm text-neutral-300">
+                    "Download an archive with your profile, bets, wallet history, uploaded posts and airdrop claims. It's prepared in the background, you can leave this page and come back later. Archives are kept for 7 days and can be requested once a day."
+                </p>
+                <Suspense fallback=FullScreenSpinner>
+                    {move || Suspend::new(async move {
+                        let job = match status.await {
+                            Ok(job) => job,
+                            Err(e) => {
+                                return Either::Left(
+                                    view! { <span class="text-sm text-red-500">{e.to_string()}</span> },
+                                );
+                            }
+                        };
+                        let requested_at = job
+                            .as_ref()
+                            .and_then(|j| parse_ns_to_datetime(j.requested_at * 1_000_000_000).ok());
+                        Either::Right(
+                            view! {
+                                <div class="flex flex-col gap-3 w-full">
+                                    {requested_at
+                                        .map(|at| {
+                                            view! {
+                                                <span class="text-xs text-neutral-500">
+                                                    "Requested " {at}
+                                                </span>
+                                            }
+                                        })}
+                                    {match job.map(|j| j.state) {
+                                        None => request_button("Request my archive").into_any(),
+                                        Some(ExportState::Preparing) => {
+                                            view! {
+                                                <div class="flex flex-row gap-2 items-center text-sm">
+                                                    <div class="w-4 h-4 rounded-full border-2 border-white border-solid animate-spin border-t-transparent"></div>
+                                                    "Preparing your archive..."
+                                                </div>
+                                            }
+                                                .into_any()
+                                        }
+                                        Some(ExportState::Ready) => {
+                                            view! {
+                                                <DownloadArchive />
+                                                {request_button("Request a new archive")}
+                                            }
+                                                .into_any()
+                                        }
+                                        Some(ExportState::Failed(e)) => {
+                                            view! {
+                                                <span class="text-sm text-red-500">
+                                                    "Your archive couldn't be prepared: " {e}
+                                                </span>
+                                                {request_button("Try again")}
+                                            }
+                                                .into_any()
+                                        }
+                                    }}
+                                </div>
+                            },
+                        )
+                    })}
+                </Suspense>
+                <Show when=move || request_error.with(|e| e.is_some())>
+                    <span class="text-xs text-red-500">
+                        {move || request_error.get().unwrap_or_default()}
+                    </span>
+                </Show>
+            </div>
+        </div>
+    }
+}
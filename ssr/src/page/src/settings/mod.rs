@@ -1,3 +1,4 @@
+use auth::logout_identity;
 use codee::string::FromToStringCodec;
 use component::back_btn::BackButton;
 use component::login_modal::LoginModal;
@@ -5,7 +6,7 @@ use component::overlay::ShadowOverlay;
 use component::spinner::FullScreenSpinner;
 use component::title::TitleText;
 use component::{social::*, toggle::Toggle};
-use consts::NOTIFICATIONS_ENABLED_STORE;
+use consts::{DEVICE_ID, NOTIFICATIONS_ENABLED_STORE};
 use leptos::either::Either;
 use leptos::html::Input;
 use leptos::web_sys::{Notification, NotificationPermission};
@@ -17,16 +18,36 @@ use leptos_router::{hooks::use_params, params::Params};
 use leptos_use::storage::use_local_storage;
 use leptos_use::use_event_listener;
 use state::canisters::auth_state;
+use state::hn_bet_state::HnBetState;
+use utils::app_error::error_path;
 use utils::mixpanel::mixpanel_events::*;
 use utils::notifications::{
     get_device_registeration_token, get_fcm_token, notification_permission_granted,
 };
 use utils::send_wrap;
 use yral_canisters_common::utils::profile::ProfileDetails;
+use yral_canisters_common::Canisters;
 use yral_metadata_client::MetadataClient;
 use yral_metadata_types::error::ApiError;
 
+pub mod account_deletion;
+mod bet_limits;
+#[cfg(feature = "ssr")]
 mod delete_user;
+pub mod export;
+mod privacy;
+pub mod profile;
+
+pub use account_deletion::AccountDeletionStatus;
+use account_deletion::{
+    clear_local_storage, report_deletion_step, revoke_notification_token, start_account_deletion,
+    DeletionStep, StartedDeletion, StepStatus,
+};
+pub use bet_limits::BetLimitsSettings;
+pub use export::DataExport;
+use export::ExportDataButton;
+pub use privacy::PrivacySettings;
+pub use profile::ProfileSettings;
 
 #[derive(Params, PartialEq, Clone)]
 struct SettingsParams {
@@ -34,7 +55,6 @@ struct SettingsParams {
 }
 
 #[component]
-#[allow(dead_code)]
 fn MenuItem(
     #[prop(into)] _text: String,
     #[prop(into)] _href: String,
@@ -132,7 +152,7 @@ fn ProfileInfo() -> impl IntoView {
                         let user_details = cans.profile_details;
                         Either::Left(view! { <ProfileLoaded user_details /> })
                     }
-                    Err(e) => Either::Right(view! { <Redirect path=format!("/error?err={e}") /> }),
+                    Err(e) => Either::Right(view! { <Redirect path=error_path(e) /> }),
                 }
             })}
         </Suspense>
@@ -259,32 +279,65 @@ fn DeleteAccountPopup(show_delete_popup: RwSignal<bool>) -> impl IntoView {
         page_name: "settings".into(),
     });
 
+    let (notifs_enabled, set_notifs_enabled, _) =
+        use_local_storage::<bool, FromToStringCodec>(NOTIFICATIONS_ENABLED_STORE);
+    let (_, set_device_id, _) = use_local_storage::<String, FromToStringCodec>(DEVICE_ID);
+    let bet_state = use_context::<HnBetState>();
+    let base_cans: Canisters<false> = use_context().unwrap_or_default();
+
     let handle_delete = Action::new(move |&()| {
         set_is_deleting(true);
         if let Some(props) = analytics_delete_account_props.clone() {
             MixPanelEvent::track_delete_account_confirmed(props);
         }
         let navigate = navigate.clone();
+        let base_cans = base_cans.clone();
+        let bet_state = bet_state.clone();
+        let notifs_enabled = notifs_enabled.get_untracked();
 
         let value = analytics_delete_account_props.clone();
         send_wrap(async move {
-            let value = value.clone();
-            match auth.user_identity.await {
-                Ok(identity_wire) => match delete_user::initiate_delete_user(identity_wire).await {
-                    Ok(_) => {
-                        if let Some(props) = value.clone() {
-                            MixPanelEvent::track_account_deleted(props);
-                        }
-                        navigate("/logout", Default::default());
-                    }
-                    Err(e) => {
-                        leptos::logging::error!("Failed to delete account: {e:?}");
-                        navigate(&format!("/error?err={e}"), Default::default());
+            let res = async {
+                // the FCM token is only known to the browser and needs the identity being deleted
+                let cans = auth.auth_cans(base_cans).await?;
+                let notification_tokens = revoke_notification_token(&cans, notifs_enabled).await;
+                let StartedDeletion { job, report_token } =
+                    start_account_deletion(notification_tokens).await?;
+                if !job.account_deleted() {
+                    return Ok(job.id);
+                }
+                if let Some(props) = value {
+                    MixPanelEvent::track_account_deleted(props);
+                }
+
+                let mut device_data = clear_local_storage();
+                if let Some(bet_state) = bet_state {
+                    bet_state.clear();
+                }
+                match logout_identity().await {
+                    Ok(id) => {
+                        auth.set_new_identity(id, false);
+                        set_notifs_enabled(false);
+                        set_device_id(uuid::Uuid::new_v4().to_string());
                     }
-                },
+                    Err(e) => device_data = StepStatus::Failed(e.to_string()),
+                }
+                report_deletion_step(
+                    job.id.clone(),
+                    report_token,
+                    DeletionStep::DeviceData,
+                    device_data,
+                )
+                .await?;
+                Ok::<_, ServerFnError>(job.id)
+            }
+            .await;
+
+            match res {
+                Ok(job_id) => navigate(&format!("/account-deletion/{job_id}"), Default::default()),
                 Err(e) => {
-                    leptos::logging::error!("Failed to get auth canisters: {e:?}");
-                    navigate(&format!("/error?err={e}"), Default::default());
+                    leptos::logging::error!("Failed to delete account: {e:?}");
+                    navigate(&error_path(e), Default::default());
                 }
             }
         })
@@ -312,6 +365,10 @@ fn DeleteAccountPopup(show_delete_popup: RwSignal<bool>) -> impl IntoView {
                         "All your data — including your Bitcoin and token balances — will be permanently removed from the platform."
                     </p>
 
+                    <div class="mb-6">
+                        <ExportDataButton />
+                    </div>
+
                     <div class="flex justify-center gap-4">
                         <button
                             class="flex-1 px-4 py-2 rounded-md bg-neutral-700 hover:bg-neutral-600 text-white text-sm disabled:opacity-50"
@@ -434,6 +491,31 @@ pub fn Settings() -> impl IntoView {
                         </div>
                         <div class="flex flex-col gap-8 py-12 px-8 w-full text-lg">
                             <EnableNotifications />
+                            <MenuItem
+                                _text="Edit profile"
+                                _href="/settings/profile"
+                                _icon=icondata::AiUserOutlined
+                            />
+                            <MenuItem
+                                _text="My uploads"
+                                _href="/my-uploads"
+                                _icon=icondata::AiCloudUploadOutlined
+                            />
+                            <MenuItem
+                                _text="Privacy"
+                                _href="/settings/privacy"
+                                _icon=icondata::AiLockOutlined
+                            />
+                            <MenuItem
+                                _text="Export my data"
+                                _href="/settings/export"
+                                _icon=icondata::AiDownloadOutlined
+                            />
+                            <MenuItem
+                                _text="Betting limits"
+                                _href="/settings/bet-limits"
+                                _icon=icondata::AiSafetyOutlined
+                            />
                             <DeleteAccount show_popup />
                         </div>
                         <MenuFooter />
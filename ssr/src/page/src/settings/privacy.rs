@@ -0,0 +1,78 @@
+use component::{back_btn::BackButton, title::TitleText, toggle::Toggle};
+use leptos::html::Input;
+use leptos::{ev, prelude::*};
+use leptos_use::use_event_listener;
+use utils::consent::{
+    browser_privacy_signal, store, use_consent_cookie, ConsentCategory, DEFAULT_CONSENT,
+};
+
+#[component]
+fn ConsentToggle(category: ConsentCategory, privacy_signal: Signal<bool>) -> impl IntoView {
+    let (consent, set_consent) = use_consent_cookie();
+    let locked = move || category == ConsentCategory::Essential || privacy_signal.get();
+    let checked = Signal::derive(move || {
+        consent
+            .get()
+            .unwrap_or(DEFAULT_CONSENT)
+            .with_privacy_signal(privacy_signal.get())
+            .allows(category)
+    });
+
+    let toggle_ref = NodeRef::<Input>::new();
+    _ = use_event_listener(toggle_ref, ev::change, move |_| {
+        let Some(input) = toggle_ref.get_untracked() else {
+            return;
+        };
+        if category == ConsentCategory::Essential || privacy_signal.get_untracked() {
+            input.set_checked(checked.get_untracked());
+            return;
+        }
+        let mut updated = consent.get_untracked().unwrap_or(DEFAULT_CONSENT);
+        updated.set(category, input.checked());
+        store(set_consent, updated);
+    });
+
+    view! {
+        <div class="flex flex-row gap-4 justify-between items-center w-full">
+            <div class="flex flex-col gap-1">
+                <span class="font-semibold">{category.label()}</span>
+                <span class="text-sm text-neutral-400">{category.description()}</span>
+            </div>
+            <div class="flex-shrink-0" class:opacity-50=locked>
+                <Toggle checked node_ref=toggle_ref />
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn PrivacySettings() -> impl IntoView {
+    // privacy signals are only known in the browser
+    let privacy_signal = RwSignal::new(false);
+    Effect::new(move |_| privacy_signal.set(browser_privacy_signal()));
+
+    view! {
+        <div class="flex flex-col items-center pt-2 pb-12 w-full min-h-screen text-white bg-black">
+            <TitleText justify_center=false>
+                <div class="flex flex-row justify-between">
+                    <BackButton fallback="/settings".to_string() />
+                    <span class="text-2xl font-bold">Privacy</span>
+                    <div></div>
+                </div>
+            </TitleText>
+            <div class="flex flex-col gap-8 py-12 px-8 w-full text-lg">
+                <Show when=privacy_signal>
+                    <p class="text-sm text-primary-600">
+                        "Your browser asks sites not to track you (Do Not Track or Global Privacy Control), so analytics and personalization stay off."
+                    </p>
+                </Show>
+                {ConsentCategory::all()
+                    .into_iter()
+                    .map(|category| {
+                        view! { <ConsentToggle category privacy_signal=privacy_signal.into() /> }
+                    })
+                    .collect_view()}
+            </div>
+        </div>
+    }
+}
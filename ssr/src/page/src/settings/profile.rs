@@ -0,0 +1,682 @@
+use candid::Principal;
+use component::{back_btn::BackButton, spinner::FullScreenSpinner, title::TitleText};
+use leptos::either::Either;
+use leptos::prelude::*;
+use leptos_meta::*;
+use leptos_router::components::Redirect;
+use serde::{Deserialize, Serialize};
+use state::{app_state::AppState, canisters::auth_state};
+use utils::{app_error::error_path, send_wrap};
+use yral_canisters_common::utils::profile::ProfileDetails;
+
+pub const MIN_USERNAME_LEN: usize = 3;
+pub const MAX_USERNAME_LEN: usize = 15;
+pub const MAX_DISPLAY_NAME_LEN: usize = 30;
+pub const MAX_BIO_LEN: usize = 160;
+const MAX_AVATAR_BYTES: f64 = 5.0 * 1024.0 * 1024.0;
+const AVATAR_TYPES: &[&str] = &["image/jpeg", "image/png", "image/webp", "image/gif"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+pub enum ProfileEditError {
+    #[error("Username must be between {MIN_USERNAME_LEN} and {MAX_USERNAME_LEN} characters")]
+    UsernameLength,
+    #[error("Username can only contain letters, numbers and _")]
+    UsernameCharset,
+    #[error("This username is already taken")]
+    UsernameTaken,
+    #[error("Display name can be at most {MAX_DISPLAY_NAME_LEN} characters")]
+    DisplayNameTooLong,
+    #[error("Bio can be at most {MAX_BIO_LEN} characters")]
+    BioTooLong,
+    #[error("Your {0} was not accepted, please choose another one")]
+    Rejected(String),
+    #[error("Profile pictures must be a JPEG, PNG, WebP or GIF smaller than 5 MB")]
+    InvalidAvatar,
+}
+
+/// Profile fields edited by the user, shown over the canister's profile details
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct ProfileEdits {
+    pub username: Option<String>,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+impl ProfileEdits {
+    pub fn apply(&self, details: &mut ProfileDetails) {
+        if let Some(username) = &self.username {
+            details.username = Some(username.clone());
+        }
+        if let Some(display_name) = &self.display_name {
+            details.display_name = Some(display_name.clone());
+        }
+        if let Some(avatar_url) = &self.avatar_url {
+            details.profile_pic = Some(avatar_url.clone());
+        }
+    }
+}
+
+/// Usernames are case insensitive and stored lowercase
+pub fn validate_username(username: &str) -> Result<String, ProfileEditError> {
+    let username = username.trim().trim_start_matches('@').to_lowercase();
+    if !(MIN_USERNAME_LEN..=MAX_USERNAME_LEN).contains(&username.chars().count()) {
+        return Err(ProfileEditError::UsernameLength);
+    }
+    if !username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(ProfileEditError::UsernameCharset);
+    }
+    Ok(username)
+}
+
+pub fn validate_display_name(display_name: &str) -> Result<String, ProfileEditError> {
+    let display_name = display_name.trim();
+    if display_name.chars().count() > MAX_DISPLAY_NAME_LEN {
+        return Err(ProfileEditError::DisplayNameTooLong);
+    }
+    Ok(display_name.to_string())
+}
+
+pub fn validate_bio(bio: &str) -> Result<String, ProfileEditError> {
+    let bio = bio.trim();
+    if bio.chars().count() > MAX_BIO_LEN {
+        return Err(ProfileEditError::BioTooLong);
+    }
+    Ok(bio.to_string())
+}
+
+pub fn validate_avatar(mime: &str, size: f64) -> Result<(), ProfileEditError> {
+    if !AVATAR_TYPES.contains(&mime) || size > MAX_AVATAR_BYTES {
+        return Err(ProfileEditError::InvalidAvatar);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use auth::server_impl::store::{KVStore, KVStoreImpl};
+    use candid::Principal;
+    use leptos::prelude::ServerFnError;
+    use yral_canisters_common::utils::time::current_epoch;
+    use yral_metadata_client::MetadataClient;
+
+    use super::*;
+
+    /// Names which could be mistaken for official accounts
+    const RESERVED_USERNAMES: &[&str] = &[
+        "admin",
+        "administrator",
+        "moderator",
+        "support",
+        "yral",
+        "official",
+        "help",
+        "security",
+    ];
+
+    fn edits_key(user: Principal) -> String {
+        format!("profile-edits:{user}")
+    }
+
+    fn username_key(username: &str) -> String {
+        format!("profile-username:{username}")
+    }
+
+    /// Usernames claimed through this page, adding a name to it is the claim
+    const USERNAMES_INDEX: &str = "profile-usernames";
+
+    fn avatar_upload_key(user: Principal) -> String {
+        format!("profile-avatar-upload:{user}")
+    }
+
+    fn is_reserved(word: &str) -> bool {
+        RESERVED_USERNAMES.contains(&word)
+    }
+
+    /// Whether `value` passes itself off as an official account
+    ///
+    /// Names can't start with a reserved word, no field may pair "yral" with
+    /// one (e.g "yral support" or "official yral")
+    fn impersonates(field: &str, value: &str) -> bool {
+        let words = value
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let claims_official = words.windows(2).any(|pair| {
+            (pair[0] == "yral" && is_reserved(&pair[1]))
+                || (is_reserved(&pair[0]) && pair[1] == "yral")
+        });
+        if field == "bio" {
+            return claims_official;
+        }
+        claims_official || words.first().is_some_and(|w| is_reserved(w))
+    }
+
+    /// Moderation hook run on every user supplied profile field before it's saved
+    pub fn moderate(field: &str, value: &str) -> Result<(), ProfileEditError> {
+        if impersonates(field, value) {
+            log::info!("rejected {field} {value:?}");
+            return Err(ProfileEditError::Rejected(field.to_string()));
+        }
+        Ok(())
+    }
+
+    pub async fn profile_edits(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<ProfileEdits, ServerFnError> {
+        let Some(raw) = kv.read(edits_key(user)).await? else {
+            return Ok(ProfileEdits::default());
+        };
+        Ok(serde_json::from_str(&raw)?)
+    }
+
+    pub async fn write_profile_edits(
+        kv: &KVStoreImpl,
+        user: Principal,
+        edits: &ProfileEdits,
+    ) -> Result<(), ServerFnError> {
+        kv.write(edits_key(user), serde_json::to_string(edits)?)
+            .await?;
+        Ok(())
+    }
+
+    /// `None` if the username is free
+    pub async fn username_owner(
+        kv: &KVStoreImpl,
+        username: &str,
+    ) -> Result<Option<Principal>, ServerFnError> {
+        let owner = kv
+            .read(username_key(username))
+            .await?
+            .filter(|owner| !owner.is_empty());
+        Ok(owner.and_then(|o| Principal::from_text(o).ok()))
+    }
+
+    /// Claims `username` for `user`, `false` if it's already held or someone
+    /// is claiming it at the same time
+    pub async fn claim_username(
+        kv: &KVStoreImpl,
+        username: &str,
+        user: Principal,
+    ) -> Result<bool, ServerFnError> {
+        let claimed_at = current_epoch().as_secs() as f64;
+        if !kv
+            .index_add(USERNAMES_INDEX.into(), username.to_string(), claimed_at)
+            .await?
+        {
+            return Ok(false);
+        }
+        kv.write(username_key(username), user.to_text()).await?;
+        Ok(true)
+    }
+
+    pub async fn release_username(kv: &KVStoreImpl, username: &str) -> Result<(), ServerFnError> {
+        kv.delete(username_key(username)).await?;
+        kv.index_remove(USERNAMES_INDEX.into(), username.to_string())
+            .await?;
+        Ok(())
+    }
+
+    /// Canister of the user holding `username` in the metadata service, which
+    /// also knows usernames that were never claimed through this page
+    pub async fn metadata_username_canister(
+        username: &str,
+    ) -> Result<Option<Principal>, ServerFnError> {
+        let metadata = MetadataClient::<false>::default()
+            .get_user_metadata_v2(username.to_string())
+            .await?;
+        Ok(metadata.map(|m| m.user_canister_id))
+    }
+
+    /// Removes the user's edits and releases their username
+    pub async fn delete_user_data(kv: &KVStoreImpl, user: Principal) -> Result<(), ServerFnError> {
+        let edits = profile_edits(kv, user).await?;
+        if let Some(username) = edits.username {
+            if username_owner(kv, &username).await? == Some(user) {
+                release_username(kv, &username).await?;
+            }
+        }
+        kv.delete(edits_key(user)).await?;
+        kv.delete(avatar_upload_key(user)).await?;
+        Ok(())
+    }
+
+    pub async fn pending_avatar_upload(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<Option<String>, ServerFnError> {
+        Ok(kv
+            .read(avatar_upload_key(user))
+            .await?
+            .filter(|id| !id.is_empty()))
+    }
+
+    pub async fn set_pending_avatar_upload(
+        kv: &KVStoreImpl,
+        user: Principal,
+        image_id: &str,
+    ) -> Result<(), ServerFnError> {
+        kv.write(avatar_upload_key(user), image_id.to_string())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Validation errors are returned as `Err`, `Ok(false)` if the username is taken
+#[server(endpoint = "check_username_available", input = server_fn::codec::Json)]
+pub async fn check_username_available(username: String) -> Result<bool, ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+    use yral_canisters_common::Canisters;
+
+    let username = validate_username(&username)?;
+    server::moderate("username", &username)?;
+
+    let kv: KVStoreImpl = expect_context();
+    let caller = extract_principal_impl().await?;
+    let owner = server::username_owner(&kv, &username).await?;
+    if owner.is_some() {
+        return Ok(owner == caller);
+    }
+
+    let Some(owner_canister) = server::metadata_username_canister(&username).await? else {
+        return Ok(true);
+    };
+    let Some(caller) = caller else {
+        return Ok(false);
+    };
+    let cans: Canisters<false> = use_context().unwrap_or_default();
+    let caller_canister = cans
+        .get_individual_canister_by_user_principal(caller)
+        .await?;
+    Ok(caller_canister == Some(owner_canister))
+}
+
+#[server(endpoint = "get_profile_edits", input = server_fn::codec::Json)]
+pub async fn get_profile_edits(user: Principal) -> Result<ProfileEdits, ServerFnError> {
+    use auth::server_impl::store::KVStoreImpl;
+
+    let kv: KVStoreImpl = expect_context();
+    server::profile_edits(&kv, user).await
+}
+
+/// One-time url the browser uploads the new profile picture to
+#[server(endpoint = "create_avatar_upload", input = server_fn::codec::Json)]
+pub async fn create_avatar_upload() -> Result<(String, String), ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let Some(user) = extract_principal_impl().await? else {
+        return Err(ServerFnError::new(
+            "Login required to change your profile picture",
+        ));
+    };
+
+    #[cfg(feature = "cloudflare")]
+    {
+        use state::cf_images::CfImagesClient;
+
+        let cf_images: CfImagesClient = expect_context();
+        let upload = cf_images.direct_upload().await?;
+        let kv: KVStoreImpl = expect_context();
+        server::set_pending_avatar_upload(&kv, user, &upload.id).await?;
+        Ok((upload.id, upload.upload_url))
+    }
+    #[cfg(not(feature = "cloudflare"))]
+    {
+        _ = user;
+        Err(ServerFnError::new(
+            "Profile picture uploads are not available",
+        ))
+    }
+}
+
+#[server(endpoint = "update_profile", input = server_fn::codec::Json)]
+pub async fn update_profile(
+    username: Option<String>,
+    display_name: String,
+    bio: String,
+    avatar_image_id: Option<String>,
+) -> Result<ProfileEdits, ServerFnError> {
+    use auth::server_impl::{extract_identity_impl, store::KVStoreImpl};
+    use ic_agent::identity::DelegatedIdentity;
+    use yral_canisters_common::Canisters;
+    use yral_metadata_client::MetadataClient;
+    use yral_metadata_types::SetUserMetadataReqMetadata;
+
+    let Some(identity) = extract_identity_impl().await? else {
+        return Err(ServerFnError::new("Login required to edit your profile"));
+    };
+    let user = Principal::self_authenticating(&identity.from_key);
+    let kv: KVStoreImpl = expect_context();
+    let mut edits = server::profile_edits(&kv, user).await?;
+
+    let display_name = validate_display_name(&display_name)?;
+    server::moderate("display name", &display_name)?;
+    edits.display_name = Some(display_name).filter(|d| !d.is_empty());
+
+    let bio = validate_bio(&bio)?;
+    server::moderate("bio", &bio)?;
+    edits.bio = Some(bio).filter(|b| !b.is_empty());
+
+    if let Some(image_id) = avatar_image_id {
+        // only images uploaded through `create_avatar_upload` by this user are accepted
+        if server::pending_avatar_upload(&kv, user).await?.as_ref() != Some(&image_id) {
+            return Err(ProfileEditError::InvalidAvatar.into());
+        }
+        #[cfg(feature = "cloudflare")]
+        {
+            edits.avatar_url = Some(state::cf_images::CfImagesClient::delivery_url(&image_id));
+        }
+    }
+
+    let username = username
+        .map(|u| validate_username(&u))
+        .transpose()?
+        .filter(|u| edits.username.as_ref() != Some(u));
+    if let Some(username) = username {
+        server::moderate("username", &username)?;
+        let already_held = server::username_owner(&kv, &username).await? == Some(user);
+        if !already_held && !server::claim_username(&kv, &username, user).await? {
+            return Err(ProfileEditError::UsernameTaken.into());
+        }
+
+        // the claim is given up again if the metadata service doesn't take the name
+        let res = async {
+            let cans: Canisters<false> = use_context().unwrap_or_default();
+            let user_canister_id = cans
+                .get_individual_canister_by_user_principal(user)
+                .await?
+                .ok_or_else(|| ServerFnError::new("User canister not found"))?;
+            if server::metadata_username_canister(&username)
+                .await?
+                .is_some_and(|owner| owner != user_canister_id)
+            {
+                return Err(ProfileEditError::UsernameTaken.into());
+            }
+            let id = DelegatedIdentity::try_from(identity)?;
+            MetadataClient::<false>::default()
+                .set_user_metadata(
+                    &id,
+                    SetUserMetadataReqMetadata {
+                        user_canister_id,
+                        user_name: username.clone(),
+                    },
+                )
+                .await?;
+            Ok::<_, ServerFnError>(())
+        }
+        .await;
+        if let Err(e) = res {
+            if !already_held {
+                server::release_username(&kv, &username).await?;
+            }
+            return Err(e);
+        }
+
+        if let Some(previous) = edits.username.replace(username) {
+            server::release_username(&kv, &previous).await?;
+        }
+    }
+
+    server::write_profile_edits(&kv, user, &edits).await?;
+
+    Ok(edits)
+}
+
+/// Uploads `file` to the one-time Cloudflare Images url
+#[cfg(feature = "hydrate")]
+async fn upload_avatar(upload_url: &str, file: leptos::web_sys::File) -> Result<(), String> {
+    use gloo::net::http::Request;
+    use leptos::web_sys::FormData;
+
+    let form = FormData::new().map_err(|e| format!("{e:?}"))?;
+    form.append_with_blob("file", &file)
+        .map_err(|e| format!("{e:?}"))?;
+    let res = Request::post(upload_url)
+        .body(form)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !res.ok() {
+        return Err(format!("upload failed with status {}", res.status()));
+    }
+    Ok(())
+}
+
+#[component]
+fn ProfileEditForm(details: ProfileDetails, edits: ProfileEdits) -> impl IntoView {
+    let current_username = edits.username.clone().or(details.username.clone());
+    let username = RwSignal::new(current_username.clone().unwrap_or_default());
+    let display_name = RwSignal::new(
+        edits
+            .display_name
+            .clone()
+            .or(details.display_name.clone())
+            .unwrap_or_default(),
+    );
+    let bio = RwSignal::new(edits.bio.clone().unwrap_or_default());
+    let avatar_preview = RwSignal::new(
+        edits
+            .avatar_url
+            .clone()
+            .unwrap_or_else(|| details.profile_pic_or_random()),
+    );
+    let avatar_image_id = RwSignal::new(None::<String>);
+    let avatar_uploading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+    let saved = RwSignal::new(false);
+
+    let username_status = Resource::new(
+        move || username.get(),
+        move |name| {
+            let unchanged = current_username.as_deref() == Some(name.as_str()) || name.is_empty();
+            send_wrap(async move {
+                if unchanged {
+                    return None;
+                }
+                match check_username_available(name).await {
+                    Ok(true) => Some(Ok(())),
+                    Ok(false) => Some(Err(ProfileEditError::UsernameTaken.to_string())),
+                    Err(e) => Some(Err(e.to_string())),
+                }
+            })
+        },
+    );
+
+    let on_avatar = move |ev: leptos::ev::Event| {
+        #[cfg(feature = "hydrate")]
+        {
+            use wasm_bindgen::JsCast;
+
+            let Some(file) = ev
+                .target()
+                .and_then(|t| t.dyn_into::<leptos::web_sys::HtmlInputElement>().ok())
+                .and_then(|input| input.files()?.get(0))
+            else {
+                return;
+            };
+            if let Err(e) = validate_avatar(&file.type_(), file.size()) {
+                error.set(Some(e.to_string()));
+                return;
+            }
+            avatar_uploading.set(true);
+            leptos::task::spawn_local(async move {
+                let res = async {
+                    let (id, upload_url) =
+                        create_avatar_upload().await.map_err(|e| e.to_string())?;
+                    let preview = leptos::web_sys::Url::create_object_url_with_blob(&file)
+                        .map_err(|e| format!("{e:?}"))?;
+                    upload_avatar(&upload_url, file).await?;
+                    Ok::<_, String>((id, preview))
+                }
+                .await;
+                match res {
+                    Ok((id, preview)) => {
+                        avatar_image_id.set(Some(id));
+                        avatar_preview.set(preview);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        log::warn!("failed to upload profile picture {e}");
+                        error.set(Some(
+                            "Failed to upload your profile picture, please try again".into(),
+                        ));
+                    }
+                }
+                avatar_uploading.set(false);
+            });
+        }
+        #[cfg(not(feature = "hydrate"))]
+        {
+            _ = ev;
+        }
+    };
+
+    let save_action = Action::new(move |&()| {
+        let username = Some(username.get_untracked()).filter(|u| !u.is_empty());
+        let display_name = display_name.get_untracked();
+        let bio = bio.get_untracked();
+        let avatar_image_id = avatar_image_id.get_untracked();
+        send_wrap(async move {
+            match update_profile(username, display_name, bio, avatar_image_id).await {
+                Ok(_) => {
+                    error.set(None);
+                    saved.set(true);
+                }
+                Err(e) => {
+                    log::warn!("failed to update profile {e}");
+                    saved.set(false);
+                    error.set(Some(e.to_string()));
+                }
+            }
+        })
+    });
+    let saving = save_action.pending();
+
+    view! {
+        <div class="flex flex-col gap-8 py-12 px-8 w-full text-lg">
+            <div class="flex flex-col gap-3 items-center">
+                <img
+                    class="object-cover w-24 h-24 rounded-full"
+                    class=("opacity-50", avatar_uploading)
+                    src=avatar_preview
+                />
+                <label class="text-sm cursor-pointer text-primary-600">
+                    {move || if avatar_uploading() { "Uploading..." } else { "Change picture" }}
+                    <input
+                        type="file"
+                        accept=AVATAR_TYPES.join(",")
+                        class="hidden"
+                        on:change=on_avatar
+                    />
+                </label>
+            </div>
+            <div class="flex flex-col gap-2">
+                <span class="font-semibold">Username</span>
+                <input
+                    type="text"
+                    maxlength=MAX_USERNAME_LEN
+                    class="py-2 px-3 w-full text-white rounded-md outline-none bg-neutral-900"
+                    bind:value=username
+                />
+                <Suspense>
+                    {move || Suspend::new(async move {
+                        match username_status.await {
+                            None => None,
+                            Some(Ok(())) => Some(Either::Left(view! {
+                                <span class="text-sm text-green-500">Username is available</span>
+                            })),
+                            Some(Err(e)) => Some(Either::Right(view! {
+                                <span class="text-sm text-red-500">{e}</span>
+                            })),
+                        }
+                    })}
+                </Suspense>
+            </div>
+            <div class="flex flex-col gap-2">
+                <span class="font-semibold">Display name</span>
+                <input
+                    type="text"
+                    maxlength=MAX_DISPLAY_NAME_LEN
+                    class="py-2 px-3 w-full text-white rounded-md outline-none bg-neutral-900"
+                    bind:value=display_name
+                />
+            </div>
+            <div class="flex flex-col gap-2">
+                <span class="font-semibold">Bio</span>
+                <textarea
+                    rows=3
+                    maxlength=MAX_BIO_LEN
+                    class="py-2 px-3 w-full text-white rounded-md outline-none resize-none bg-neutral-900"
+                    prop:value=bio
+                    on:input=move |ev| bio.set(event_target_value(&ev))
+                />
+                <span class="self-end text-xs text-neutral-400">
+                    {move || format!("{}/{MAX_BIO_LEN}", bio.with(|b| b.chars().count()))}
+                </span>
+            </div>
+            <Show when=move || error.with(|e| e.is_some())>
+                <span class="text-sm text-red-500">{move || error.get().unwrap_or_default()}</span>
+            </Show>
+            <Show when=saved>
+                <span class="text-sm text-green-500">Profile saved</span>
+            </Show>
+            <button
+                class="py-3 w-full font-bold text-white rounded-md bg-primary-600 disabled:opacity-50"
+                disabled=move || saving() || avatar_uploading()
+                on:click=move |_| {
+                    save_action.dispatch(());
+                }
+            >
+                {move || if saving() { "Saving..." } else { "Save profile" }}
+            </button>
+        </div>
+    }
+}
+
+#[component]
+pub fn ProfileSettings() -> impl IntoView {
+    let app_state = use_context::<AppState>();
+    let page_title = app_state.unwrap().name.to_owned() + " - Edit Profile";
+    let auth = auth_state();
+
+    let profile = auth.derive_resource(
+        || (),
+        move |cans, _| {
+            send_wrap(async move {
+                let details = cans.profile_details();
+                let edits = get_profile_edits(details.principal).await?;
+                Ok::<_, ServerFnError>((details, edits))
+            })
+        },
+    );
+
+    view! {
+        <Title text=page_title />
+        <div class="flex flex-col items-center pt-2 pb-12 w-full min-h-screen text-white bg-black">
+            <TitleText justify_center=false>
+                <div class="flex flex-row justify-between">
+                    <BackButton fallback="/settings".to_string() />
+                    <span class="text-2xl font-bold">Edit Profile</span>
+                    <div></div>
+                </div>
+            </TitleText>
+            <Suspense fallback=FullScreenSpinner>
+                {move || Suspend::new(async move {
+                    match profile.await {
+                        Ok((details, edits)) => {
+                            Either::Left(view! { <ProfileEditForm details edits /> })
+                        }
+                        Err(e) => Either::Right(view! { <Redirect path=error_path(e) /> }),
+                    }
+                })}
+            </Suspense>
+        </div>
+    }
+}
@@ -10,7 +10,9 @@ use leptos_meta::*;
 use leptos_router::components::Redirect;
 use leptos_router::hooks::use_params;
 use server_fn::codec::Json;
+use state::balance_store::{BalanceKey, BalanceStore};
 use state::canisters::{auth_state, unauth_canisters};
+use utils::app_error::error_path;
 use utils::mixpanel::mixpanel_events::*;
 use utils::send_wrap;
 use utils::{event_streaming::events::TokensTransferred, web::paste_from_clipboard};
@@ -153,6 +155,7 @@ fn TokenTransferInner(
     let mix_fees = info.fees.clone();
     let token_name = info.symbol.clone();
 
+    let balance_store = BalanceStore::use_store();
     let send_action = Action::new(move |&()| {
         let root = root.clone();
         let fees = mix_fees.clone();
@@ -167,7 +170,7 @@ fn TokenTransferInner(
 
             let amt = amt_res.get_untracked().unwrap().unwrap();
 
-            match root {
+            let ledger = match root {
                 RootType::Other(root) => {
                     let root_canister = cans.sns_root(root).await;
                     log::debug!("{root}");
@@ -186,18 +189,25 @@ fn TokenTransferInner(
                         amt.clone(),
                     )
                     .await?;
+                    ledger_canister
                 }
                 RootType::BTC { ledger, .. } => {
                     cans.transfer_ck_token_to_user_principal(destination, ledger, amt.clone())
                         .await?;
+                    ledger
                 }
                 RootType::USDC { ledger, .. } => {
                     cans.transfer_ck_token_to_user_principal(destination, ledger, amt.clone())
                         .await?;
+                    ledger
                 }
                 RootType::CENTS => return Err(ServerFnError::new("Cents cannot be transferred")),
                 RootType::SATS => return Err(ServerFnError::new("Satoshis cannot be transferred")),
-            }
+            };
+            balance_store.debit(
+                BalanceKey::Icrc1 { ledger },
+                amt.e8s.clone() + fees.e8s.clone(),
+            );
             TokensTransferred.send_event(amt.e8s.to_string(), destination, cans.clone());
             let is_logged_in = is_connected.get_untracked();
 
@@ -358,7 +368,7 @@ pub fn TokenTransfer() -> impl IntoView {
             {move || Suspend::new(async move {
                 let res = token_metadata_fetch.await;
                 match res {
-                    Err(e) => view! { <Redirect path=format!("/error?err={e}") /> }.into_any(),
+                    Err(e) => view! { <Redirect path=error_path(e) /> }.into_any(),
                     Ok(None) => view! { <Redirect path="/" /> }.into_any(),
                     Ok(Some((info, root, cans_wire))) => {
                         view! { <TokenTransferInner info=info root=root cans_wire /> }.into_any()
@@ -0,0 +1,411 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::video_upload::{SerializablePostDetailsFromFrontend, VideoMetadata};
+
+/// Posts can't be scheduled further ahead than this,
+/// the identity delegated for publishing would expire before the post is published
+pub const MAX_SCHEDULE_AHEAD_SECS: u64 = 6 * 24 * 60 * 60;
+/// How long after `publish_at` the identity stored for a scheduled post stays valid
+#[cfg(feature = "ssr")]
+const PUBLISH_GRACE_SECS: u64 = 60 * 60;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum DraftState {
+    Draft,
+    /// epoch secs
+    Scheduled {
+        publish_at: u64,
+    },
+    /// scheduled publication failed
+    Failed {
+        error: String,
+    },
+}
+
+/// An uploaded video whose post is not published yet
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PostDraft {
+    pub id: String,
+    pub meta: VideoMetadata,
+    pub post_details: SerializablePostDetailsFromFrontend,
+    /// epoch secs
+    pub created_at: u64,
+    pub state: DraftState,
+}
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use auth::server_impl::store::{KVStore, KVStoreImpl};
+    use candid::Principal;
+    use web_time::Duration;
+    use yral_canisters_common::{utils::time::current_epoch, Canisters};
+    use yral_types::delegated_identity::DelegatedIdentityWire;
+
+    use super::*;
+    use crate::post_view::tags::server::index_published_post;
+    use crate::upload::video_upload::publish_video;
+
+    /// Scheduled posts across all users by `publish_at`, members are `{user}:{draft_id}`
+    const SCHEDULE_INDEX: &str = "upload-scheduled-due";
+    const SCHEDULER_INTERVAL: Duration = Duration::from_secs(60);
+    /// Due posts claimed per scheduler tick
+    const SCHEDULER_BATCH: usize = 50;
+    const MAX_DRAFTS: usize = 200;
+
+    /// Draft ids of the user by `created_at`
+    fn drafts_key(user: Principal) -> String {
+        format!("upload-drafts:{user}")
+    }
+
+    fn draft_key(user: Principal, draft_id: &str) -> String {
+        format!("upload-draft:{user}:{draft_id}")
+    }
+
+    fn scheduled_key(user: Principal, draft_id: &str) -> String {
+        format!("scheduled:{user}:{draft_id}")
+    }
+
+    fn schedule_member(user: Principal, draft_id: &str) -> String {
+        format!("{user}:{draft_id}")
+    }
+
+    /// Identity a scheduled post is published with, only kept until it's claimed
+    #[derive(Serialize, Deserialize, Clone)]
+    struct ScheduledPost {
+        /// can only call the user's individual canister and expires
+        /// [PUBLISH_GRACE_SECS] after `publish_at`, see [scheduling_identity]
+        identity: DelegatedIdentityWire,
+    }
+
+    pub fn now_secs() -> u64 {
+        current_epoch().as_secs()
+    }
+
+    /// Delegates `identity` to a fresh key which expires shortly after `publish_at`
+    /// and is restricted to `user_canister`, the only canister the upload service
+    /// calls to publish the post. Fails if `identity` itself expires before then
+    pub fn scheduling_identity(
+        identity: DelegatedIdentityWire,
+        user_canister: Principal,
+        publish_at: u64,
+    ) -> Result<DelegatedIdentityWire, ServerFnError> {
+        use ic_agent::identity::DelegatedIdentity;
+
+        let valid_until = publish_at + PUBLISH_GRACE_SECS;
+        let expires_at = identity
+            .delegation_chain
+            .iter()
+            .map(|d| d.delegation.expiration / 1_000_000_000)
+            .min()
+            .unwrap_or(u64::MAX);
+        if valid_until > expires_at {
+            return Err(ServerFnError::new(
+                "Your session expires before the scheduled time, log in again or pick an earlier time",
+            ));
+        }
+
+        let id = DelegatedIdentity::try_from(identity)?;
+        let max_age = Duration::from_secs(valid_until.saturating_sub(now_secs()));
+        Ok(auth::delegate_identity_with_targets(
+            &id,
+            max_age,
+            Some(vec![user_canister]),
+        ))
+    }
+
+    async fn read_draft(
+        kv: &KVStoreImpl,
+        user: Principal,
+        draft_id: &str,
+    ) -> Result<Option<PostDraft>, ServerFnError> {
+        let Some(raw) = kv.read(draft_key(user, draft_id)).await? else {
+            return Ok(None);
+        };
+        Ok(Some(serde_json::from_str(&raw)?))
+    }
+
+    async fn write_draft(
+        kv: &KVStoreImpl,
+        user: Principal,
+        draft: &PostDraft,
+    ) -> Result<(), ServerFnError> {
+        kv.write(draft_key(user, &draft.id), serde_json::to_string(draft)?)
+            .await?;
+        kv.index_add(drafts_key(user), draft.id.clone(), draft.created_at as f64)
+            .await?;
+        Ok(())
+    }
+
+    /// Drafts of the user, newest first
+    pub async fn drafts(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<Vec<PostDraft>, ServerFnError> {
+        let ids = kv
+            .index_range(drafts_key(user), 0, MAX_DRAFTS, true)
+            .await?;
+        let mut drafts = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(draft) = read_draft(kv, user, &id).await? {
+                drafts.push(draft);
+            }
+        }
+        Ok(drafts)
+    }
+
+    async fn unschedule(
+        kv: &KVStoreImpl,
+        user: Principal,
+        draft_id: &str,
+    ) -> Result<(), ServerFnError> {
+        kv.index_remove(SCHEDULE_INDEX.into(), schedule_member(user, draft_id))
+            .await?;
+        kv.delete(scheduled_key(user, draft_id)).await?;
+        Ok(())
+    }
+
+    /// Inserts or replaces the draft, `identity` is required for scheduled drafts
+    /// and should come from [scheduling_identity]
+    pub async fn save_draft(
+        kv: &KVStoreImpl,
+        user: Principal,
+        draft: PostDraft,
+        identity: Option<DelegatedIdentityWire>,
+    ) -> Result<(), ServerFnError> {
+        let is_new = kv
+            .index_score(drafts_key(user), draft.id.clone())
+            .await?
+            .is_none();
+        if is_new && kv.index_len(drafts_key(user)).await? >= MAX_DRAFTS {
+            return Err(ServerFnError::new(format!(
+                "You can keep at most {MAX_DRAFTS} drafts"
+            )));
+        }
+        unschedule(kv, user, &draft.id).await?;
+        write_draft(kv, user, &draft).await?;
+        if let (DraftState::Scheduled { publish_at }, Some(identity)) = (&draft.state, identity) {
+            let post = ScheduledPost { identity };
+            let key = scheduled_key(user, &draft.id);
+            kv.write(key.clone(), serde_json::to_string(&post)?).await?;
+            kv.expire(
+                key,
+                Duration::from_secs(publish_at.saturating_sub(now_secs()) + PUBLISH_GRACE_SECS),
+            )
+            .await?;
+            kv.index_add(
+                SCHEDULE_INDEX.into(),
+                schedule_member(user, &draft.id),
+                *publish_at as f64,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn remove_draft(
+        kv: &KVStoreImpl,
+        user: Principal,
+        draft_id: &str,
+    ) -> Result<(), ServerFnError> {
+        unschedule(kv, user, draft_id).await?;
+        kv.delete(draft_key(user, draft_id)).await?;
+        kv.index_remove(drafts_key(user), draft_id.to_string())
+            .await?;
+        Ok(())
+    }
+
+    /// Drops the user's drafts and scheduled posts, along with the identities stored for them
+    pub async fn delete_user_data(kv: &KVStoreImpl, user: Principal) -> Result<(), ServerFnError> {
+        for draft in drafts(kv, user).await? {
+            unschedule(kv, user, &draft.id).await?;
+            kv.delete(draft_key(user, &draft.id)).await?;
+        }
+        kv.index_delete(drafts_key(user)).await?;
+        Ok(())
+    }
+
+    async fn set_draft_state(
+        kv: &KVStoreImpl,
+        user: Principal,
+        draft_id: &str,
+        state: DraftState,
+    ) -> Result<(), ServerFnError> {
+        let Some(mut draft) = read_draft(kv, user, draft_id).await? else {
+            return Ok(());
+        };
+        draft.state = state;
+        write_draft(kv, user, &draft).await
+    }
+
+    async fn publish_scheduled(
+        kv: &KVStoreImpl,
+        user: Principal,
+        draft_id: &str,
+    ) -> Result<(), ServerFnError> {
+        let key = scheduled_key(user, draft_id);
+        let post = kv.read(key.clone()).await?;
+        kv.delete(key).await?;
+        let Some(draft) = read_draft(kv, user, draft_id).await? else {
+            return Ok(());
+        };
+        let Some(post) = post else {
+            return set_draft_state(
+                kv,
+                user,
+                draft_id,
+                DraftState::Failed {
+                    error: "Scheduling expired".into(),
+                },
+            )
+            .await;
+        };
+        let post: ScheduledPost = serde_json::from_str(&post)?;
+
+        let video_uid = draft.post_details.video_uid.clone();
+        match publish_video(post.identity, draft.meta, draft.post_details).await {
+            Ok(_) => {
+                let cans = Canisters::<false>::default();
+                if let Err(e) = index_published_post(kv, &cans, user, &video_uid).await {
+                    log::warn!("failed to index hashtags of {video_uid}: {e}");
+                }
+                remove_draft(kv, user, draft_id).await
+            }
+            Err(e) => {
+                log::warn!("failed to publish scheduled post {draft_id}: {e}");
+                set_draft_state(
+                    kv,
+                    user,
+                    draft_id,
+                    DraftState::Failed {
+                        error: e.to_string(),
+                    },
+                )
+                .await
+            }
+        }
+    }
+
+    /// Publishes scheduled posts which are due
+    ///
+    /// Every instance runs the scheduler, a post is only published by the
+    /// instance which removes it from the schedule
+    pub async fn publish_due_posts(kv: &KVStoreImpl) -> Result<(), ServerFnError> {
+        let due = kv
+            .index_range_by_score(SCHEDULE_INDEX.into(), now_secs() as f64, SCHEDULER_BATCH)
+            .await?;
+        for (member, _) in due {
+            if !kv
+                .index_remove(SCHEDULE_INDEX.into(), member.clone())
+                .await?
+            {
+                continue;
+            }
+            let Some((user, draft_id)) = member
+                .split_once(':')
+                .and_then(|(user, id)| Some((Principal::from_text(user).ok()?, id)))
+            else {
+                log::warn!("invalid scheduled post {member}");
+                continue;
+            };
+            if let Err(e) = publish_scheduled(kv, user, draft_id).await {
+                log::warn!("failed to publish scheduled post {draft_id}: {e}");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Background task publishing scheduled posts, runs forever
+    pub async fn run_post_scheduler(kv: KVStoreImpl) {
+        let mut interval = tokio::time::interval(SCHEDULER_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = publish_due_posts(&kv).await {
+                log::warn!("post scheduler failed: {e}");
+            }
+        }
+    }
+}
+
+/// Saves the post as a draft, or schedules it if `publish_at` (epoch secs) is set
+#[server(endpoint = "save_post_draft", input = server_fn::codec::Json)]
+pub async fn save_post_draft(
+    draft_id: Option<String>,
+    post_details: SerializablePostDetailsFromFrontend,
+    thumbnail_timestamp_pct: Option<f64>,
+    publish_at: Option<u64>,
+) -> Result<PostDraft, ServerFnError> {
+    use super::validators::{description_validator, hashtags_validator};
+    use auth::server_impl::{extract_identity_impl, store::KVStoreImpl};
+    use candid::Principal;
+    use yral_canisters_common::Canisters;
+
+    description_validator(post_details.description.clone()).map_err(ServerFnError::new)?;
+    hashtags_validator(post_details.hashtags.join(",")).map_err(ServerFnError::new)?;
+
+    let Some(identity) = extract_identity_impl().await? else {
+        return Err(ServerFnError::new("Login required to save drafts"));
+    };
+    let user = Principal::self_authenticating(&identity.from_key);
+
+    let now = server::now_secs();
+    let state = match publish_at {
+        None => DraftState::Draft,
+        Some(at) if at <= now => {
+            return Err(ServerFnError::new("Scheduled time must be in the future"))
+        }
+        Some(at) if at > now + MAX_SCHEDULE_AHEAD_SECS => {
+            return Err(ServerFnError::new(format!(
+                "Posts can be scheduled at most {} days ahead",
+                MAX_SCHEDULE_AHEAD_SECS / (24 * 60 * 60)
+            )))
+        }
+        Some(publish_at) => DraftState::Scheduled { publish_at },
+    };
+
+    let draft = PostDraft {
+        id: draft_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        meta: VideoMetadata::from_post_details(&post_details, thumbnail_timestamp_pct),
+        post_details,
+        created_at: now,
+        state,
+    };
+    let identity = match publish_at {
+        Some(at) => {
+            let cans: Canisters<false> = use_context().unwrap_or_default();
+            let user_canister = cans
+                .get_individual_canister_by_user_principal(user)
+                .await?
+                .ok_or_else(|| ServerFnError::new("User canister not found"))?;
+            Some(server::scheduling_identity(identity, user_canister, at)?)
+        }
+        None => None,
+    };
+    let kv: KVStoreImpl = expect_context();
+    server::save_draft(&kv, user, draft.clone(), identity).await?;
+
+    Ok(draft)
+}
+
+#[server(endpoint = "list_post_drafts", input = server_fn::codec::Json)]
+pub async fn list_post_drafts() -> Result<Vec<PostDraft>, ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let Some(user) = extract_principal_impl().await? else {
+        return Ok(vec![]);
+    };
+    let kv: KVStoreImpl = expect_context();
+    server::drafts(&kv, user).await
+}
+
+#[server(endpoint = "delete_post_draft", input = server_fn::codec::Json)]
+pub async fn delete_post_draft(draft_id: String) -> Result<(), ServerFnError> {
+    use auth::server_impl::{extract_principal_impl, store::KVStoreImpl};
+
+    let Some(user) = extract_principal_impl().await? else {
+        return Err(ServerFnError::new("Login required to delete drafts"));
+    };
+    let kv: KVStoreImpl = expect_context();
+    server::remove_draft(&kv, user, &draft_id).await
+}
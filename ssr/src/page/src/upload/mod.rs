@@ -1,3 +1,7 @@
+pub(crate) mod drafts;
+mod preprocess;
+mod resumable;
+mod status;
 mod validators;
 mod video_upload;
 use leptos_meta::*;
@@ -15,9 +19,25 @@ use leptos::{
 };
 
 use component::buttons::HighlightedButton;
+#[cfg(feature = "ssr")]
+pub use drafts::server::run_post_scheduler;
+use drafts::MAX_SCHEDULE_AHEAD_SECS;
+use resumable::UploadControl;
+pub use status::MyUploads;
 use validators::{description_validator, hashtags_validator};
 use video_upload::{PreVideoUpload, VideoUploader};
 
+/// What happens to the post once the video is uploaded
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum PublishMode {
+    Now,
+    Draft,
+    /// epoch secs
+    Schedule {
+        publish_at: u64,
+    },
+}
+
 #[derive(Clone)]
 struct UploadParams {
     file_blob: FileWithUrl,
@@ -25,13 +45,22 @@ struct UploadParams {
     description: String,
     enable_hot_or_not: bool,
     is_nsfw: bool,
+    /// position of the cover frame, 0.0..=1.0
+    cover_frame_pct: Option<f64>,
+    publish_mode: PublishMode,
+}
+
+/// Parses the value of a `datetime-local` input to epoch secs
+fn parse_local_datetime(value: &str) -> Option<u64> {
+    let ms = js_sys::Date::new(&value.into()).get_time();
+    (!ms.is_nan() && ms > 0.0).then_some((ms / 1000.0) as u64)
 }
 
 #[component]
 fn PreUploadView(
     trigger_upload: WriteSignal<Option<UploadParams>, LocalStorage>,
     uid: RwSignal<Option<String>, LocalStorage>,
-    upload_file_actual_progress: WriteSignal<f64>,
+    control: UploadControl,
 ) -> impl IntoView {
     let description_err = RwSignal::new(String::new());
     let desc_err_memo = Memo::new(move |_| description_err());
@@ -39,6 +68,7 @@ fn PreUploadView(
     let hashtags_err = RwSignal::new(String::new());
     let hashtags_err_memo = Memo::new(move |_| hashtags_err());
     let file_blob = RwSignal::new_local(None::<FileWithUrl>);
+    let cover_frame = RwSignal::new(None::<f64>);
     let desc = NodeRef::<Textarea>::new();
     let invalid_form = Memo::new(move |_| {
         // Description error
@@ -53,12 +83,39 @@ fn PreUploadView(
     let hashtag_inp = NodeRef::<Input>::new();
     let enable_hot_or_not = NodeRef::<Input>::new();
     let is_nsfw = NodeRef::<Input>::new();
+    let schedule_enabled = RwSignal::new(false);
+    let schedule_at = RwSignal::new(String::new());
+    let schedule_err = RwSignal::new(String::new());
 
     let auth = auth_state();
     let ev_ctx = auth.event_ctx();
     VideoUploadInitiated.send_event(ev_ctx);
 
-    let on_submit = move || {
+    let on_submit = move |draft: bool| {
+        let publish_mode = if draft {
+            PublishMode::Draft
+        } else if schedule_enabled.get_untracked() {
+            let now_secs = (js_sys::Date::now() / 1000.0) as u64;
+            match schedule_at.with_untracked(|at| parse_local_datetime(at)) {
+                Some(publish_at)
+                    if publish_at > now_secs
+                        && publish_at <= now_secs + MAX_SCHEDULE_AHEAD_SECS =>
+                {
+                    schedule_err.set(String::new());
+                    PublishMode::Schedule { publish_at }
+                }
+                _ => {
+                    schedule_err.set(format!(
+                        "Pick a time within the next {} days",
+                        MAX_SCHEDULE_AHEAD_SECS / (24 * 60 * 60)
+                    ));
+                    return;
+                }
+            }
+        } else {
+            PublishMode::Now
+        };
+
         VideoUploadUploadButtonClicked.send_event(ev_ctx, hashtag_inp, is_nsfw, enable_hot_or_not);
 
         let description = desc.get_untracked().unwrap().value();
@@ -86,6 +143,8 @@ fn PreUploadView(
                 .get_untracked()
                 .map(|v| v.checked())
                 .unwrap_or_default(),
+            cover_frame_pct: cover_frame.get_untracked(),
+            publish_mode,
         }));
     };
 
@@ -114,7 +173,8 @@ fn PreUploadView(
                 <PreVideoUpload
                     file_blob=file_blob
                     uid=uid
-                    upload_file_actual_progress=upload_file_actual_progress
+                    control
+                    cover_frame
                 />
             </div>
             <div class="flex overflow-y-auto flex-col gap-4 justify-between p-2 w-full h-auto rounded-2xl max-w-[627px] min-h-[400px] max-h-[90vh] lg:w-[627px] lg:h-[600px]">
@@ -164,17 +224,46 @@ fn PreUploadView(
                         placeholder="Hit enter to add #hashtags"
                     />
                 </div>
+                <div class="flex flex-col gap-y-2 mt-2">
+                    <label class="flex flex-row gap-2 items-center font-light text-[17px] text-neutral-300">
+                        <input
+                            type="checkbox"
+                            class="accent-pink-400"
+                            on:change=move |ev| schedule_enabled.set(event_target_checked(&ev))
+                        />
+                        Schedule for later
+                    </label>
+                    <Show when=schedule_enabled>
+                        <Show when=move || schedule_err.with(|e| !e.is_empty())>
+                            <span class="text-sm text-red-500">{move || schedule_err.get()}</span>
+                        </Show>
+                        <input
+                            type="datetime-local"
+                            bind:value=schedule_at
+                            class="p-3 rounded-lg border transition outline-none focus:border-pink-400 focus:ring-pink-400 bg-neutral-900 border-neutral-800 text-[15px]"
+                        />
+                    </Show>
+                </div>
                 {move || {
                     let disa = invalid_form.get();
                     view! {
-                        <HighlightedButton
-                            on_click=move || on_submit()
-                            disabled=disa
-                            classes="w-full mx-auto py-[12px] px-[20px] rounded-xl bg-linear-to-r from-pink-300 to-pink-500 text-white font-light text-[17px] transition disabled:opacity-60 disabled:cursor-not-allowed"
-                                .to_string()
-                        >
-                            "Upload"
-                        </HighlightedButton>
+                        <div class="flex flex-col gap-2 w-full">
+                            <HighlightedButton
+                                on_click=move || on_submit(false)
+                                disabled=disa
+                                classes="w-full mx-auto py-[12px] px-[20px] rounded-xl bg-linear-to-r from-pink-300 to-pink-500 text-white font-light text-[17px] transition disabled:opacity-60 disabled:cursor-not-allowed"
+                                    .to_string()
+                            >
+                                {move || if schedule_enabled.get() { "Schedule" } else { "Upload" }}
+                            </HighlightedButton>
+                            <button
+                                class="py-3 w-full font-light text-pink-300 rounded-xl border border-pink-300 text-[17px] disabled:opacity-60 disabled:cursor-not-allowed"
+                                disabled=disa
+                                on:click=move |_| on_submit(true)
+                            >
+                                Save as draft
+                            </button>
+                        </div>
                     }
                 }}
             </div>
@@ -186,7 +275,7 @@ fn PreUploadView(
 pub fn UploadPostPage() -> impl IntoView {
     let trigger_upload = RwSignal::new_local(None::<UploadParams>);
     let uid = RwSignal::new_local(None);
-    let upload_file_actual_progress = RwSignal::new(0.0f64);
+    let control = UploadControl::default();
 
     view! {
         <Title text="YRAL - Upload" />
@@ -199,7 +288,7 @@ pub fn UploadPostPage() -> impl IntoView {
                             <PreUploadView
                                 trigger_upload=trigger_upload.write_only()
                                 uid=uid
-                                upload_file_actual_progress=upload_file_actual_progress.write_only()
+                                control
                             />
                         }
                     }
@@ -208,7 +297,7 @@ pub fn UploadPostPage() -> impl IntoView {
                     <VideoUploader
                         params=trigger_upload.get_untracked().unwrap()
                         uid=uid
-                        upload_file_actual_progress=upload_file_actual_progress.read_only()
+                        control
                     />
                 </Show>
             </div>
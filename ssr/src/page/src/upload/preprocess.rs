@@ -0,0 +1,255 @@
+//! Checks run on the selected video before it is uploaded
+//!
+//! Files the transcoder is likely to reject are refused up front, and clips
+//! longer than [MAX_VIDEO_DURATION_SECS] are trimmed in the browser instead
+//! of being rejected.
+
+/// Longer videos are trimmed to this duration
+pub const MAX_VIDEO_DURATION_SECS: f64 = 60.0;
+pub const MAX_FILE_SIZE_BYTES: u64 = 500 * 1024 * 1024;
+const MIN_SHORT_SIDE: u32 = 240;
+const MAX_LONG_SIDE: u32 = 4096;
+/// width / height, from 9:21 to 21:9
+const MIN_ASPECT_RATIO: f64 = 9.0 / 21.0;
+const MAX_ASPECT_RATIO: f64 = 21.0 / 9.0;
+const SUPPORTED_CONTAINERS: &[&str] = &[
+    "video/mp4",
+    "video/quicktime",
+    "video/webm",
+    "video/x-matroska",
+    "video/3gpp",
+];
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PreprocessError {
+    #[error("This video format is not supported, please upload an MP4, MOV or WebM file")]
+    UnsupportedContainer,
+    #[error("This video could not be decoded by your browser, please try another file")]
+    UnsupportedCodec,
+    #[error("Video must be smaller than {} MB", MAX_FILE_SIZE_BYTES / (1024 * 1024))]
+    FileTooLarge,
+    #[error("Video resolution is too low, it must be at least {MIN_SHORT_SIDE}p")]
+    ResolutionTooLow,
+    #[error(
+        "Video resolution is too high, it must be at most {MAX_LONG_SIDE}px on the longer side"
+    )]
+    ResolutionTooHigh,
+    #[error("Video is too wide or too tall, please use an aspect ratio between 9:21 and 21:9")]
+    UnsupportedAspectRatio,
+    #[error("Failed to trim video: {0}")]
+    TrimFailed(String),
+}
+
+/// Metadata read from the video element once it has loaded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VideoProbe {
+    /// secs, may be NaN or infinite for some containers
+    pub duration: f64,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreprocessPlan {
+    /// trim the video to this many secs before uploading
+    pub trim_to: Option<f64>,
+}
+
+impl PreprocessPlan {
+    /// Duration of the video that will be uploaded
+    pub fn upload_duration(&self, probe: &VideoProbe) -> f64 {
+        self.trim_to.unwrap_or(probe.duration)
+    }
+}
+
+pub fn probe(video: &leptos::web_sys::HtmlVideoElement) -> VideoProbe {
+    VideoProbe {
+        duration: video.duration(),
+        width: video.video_width(),
+        height: video.video_height(),
+    }
+}
+
+/// Checks which only need the file itself, run as soon as a file is selected
+pub fn check_file(mime: &str, size: u64) -> Result<(), PreprocessError> {
+    // some browsers don't report a type for less common containers,
+    // those are left to the decoder check
+    let container = mime.split(';').next().unwrap_or_default().trim();
+    if !container.is_empty() && !SUPPORTED_CONTAINERS.contains(&container) {
+        return Err(PreprocessError::UnsupportedContainer);
+    }
+    if size > MAX_FILE_SIZE_BYTES {
+        return Err(PreprocessError::FileTooLarge);
+    }
+    Ok(())
+}
+
+/// Checks run after the browser has decoded the video's metadata
+pub fn plan(probe: VideoProbe) -> Result<PreprocessPlan, PreprocessError> {
+    let VideoProbe {
+        duration,
+        width,
+        height,
+    } = probe;
+    // audio only files or codecs the browser can't decode
+    if width == 0 || height == 0 {
+        return Err(PreprocessError::UnsupportedCodec);
+    }
+    if width.min(height) < MIN_SHORT_SIDE {
+        return Err(PreprocessError::ResolutionTooLow);
+    }
+    if width.max(height) > MAX_LONG_SIDE {
+        return Err(PreprocessError::ResolutionTooHigh);
+    }
+    let aspect_ratio = width as f64 / height as f64;
+    if !(MIN_ASPECT_RATIO..=MAX_ASPECT_RATIO).contains(&aspect_ratio) {
+        return Err(PreprocessError::UnsupportedAspectRatio);
+    }
+
+    let trim_to = (duration.is_finite() && duration > MAX_VIDEO_DURATION_SECS)
+        .then_some(MAX_VIDEO_DURATION_SECS);
+    Ok(PreprocessPlan { trim_to })
+}
+
+#[cfg(feature = "hydrate")]
+mod client {
+    use std::cell::RefCell;
+
+    use futures::channel::oneshot;
+    use leptos::{
+        prelude::document,
+        web_sys::{
+            BlobEvent, FilePropertyBag, HtmlVideoElement, MediaRecorder, MediaRecorderOptions,
+            MediaStream,
+        },
+    };
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    use super::*;
+
+    const RECORDER_MIME_TYPES: &[&str] = &[
+        "video/webm;codecs=vp9,opus",
+        "video/webm;codecs=vp8,opus",
+        "video/webm",
+        "video/mp4",
+    ];
+
+    fn trim_err(e: JsValue) -> PreprocessError {
+        PreprocessError::TrimFailed(format!("{e:?}"))
+    }
+
+    fn capture_stream(video: &HtmlVideoElement) -> Result<MediaStream, PreprocessError> {
+        // firefox only supports the prefixed version
+        let capture = ["captureStream", "mozCaptureStream"]
+            .into_iter()
+            .filter_map(|name| js_sys::Reflect::get(video, &name.into()).ok())
+            .find_map(|f| f.dyn_into::<js_sys::Function>().ok())
+            .ok_or_else(|| {
+                PreprocessError::TrimFailed("video capture is not supported by this browser".into())
+            })?;
+        capture
+            .call0(video)
+            .and_then(|s| s.dyn_into::<MediaStream>().map_err(JsValue::from))
+            .map_err(trim_err)
+    }
+
+    /// Re-records the first `max_secs` of the video at `src`
+    ///
+    /// Recording happens in real time, `on_progress` reports 0.0..=1.0
+    pub async fn trim_video(
+        src: &str,
+        name: &str,
+        max_secs: f64,
+        on_progress: impl Fn(f64),
+    ) -> Result<leptos::web_sys::File, PreprocessError> {
+        let mime = RECORDER_MIME_TYPES
+            .iter()
+            .copied()
+            .find(|m| MediaRecorder::is_type_supported(m))
+            .ok_or_else(|| {
+                PreprocessError::TrimFailed(
+                    "video recording is not supported by this browser".into(),
+                )
+            })?;
+
+        let video: HtmlVideoElement = document()
+            .create_element("video")
+            .map_err(trim_err)?
+            .dyn_into()
+            .map_err(|e| trim_err(e.into()))?;
+        video.set_muted(true);
+        video.set_src(src);
+        JsFuture::from(video.play().map_err(trim_err)?)
+            .await
+            .map_err(trim_err)?;
+
+        let stream = capture_stream(&video)?;
+        let options = MediaRecorderOptions::new();
+        options.set_mime_type(mime);
+        let recorder =
+            MediaRecorder::new_with_media_stream_and_media_recorder_options(&stream, &options)
+                .map_err(trim_err)?;
+
+        let chunks = js_sys::Array::new();
+        let chunks_ondata = chunks.clone();
+        let on_data = Closure::wrap(Box::new(move |ev: BlobEvent| {
+            if let Some(data) = ev.data() {
+                chunks_ondata.push(&data);
+            }
+        }) as Box<dyn FnMut(_)>);
+        recorder.set_ondataavailable(Some(on_data.as_ref().unchecked_ref()));
+        on_data.forget();
+
+        let (tx, rx) = oneshot::channel::<()>();
+        let tx = RefCell::new(Some(tx));
+        let on_stop = Closure::wrap(Box::new(move || {
+            if let Some(tx) = tx.borrow_mut().take() {
+                _ = tx.send(());
+            }
+        }) as Box<dyn FnMut()>);
+        recorder.set_onstop(Some(on_stop.as_ref().unchecked_ref()));
+        on_stop.forget();
+
+        recorder.start_with_time_slice(1000).map_err(trim_err)?;
+        loop {
+            utils::time::sleep(web_time::Duration::from_millis(250)).await;
+            let elapsed = video.current_time();
+            on_progress((elapsed / max_secs).min(1.0));
+            if elapsed >= max_secs || video.ended() {
+                break;
+            }
+        }
+        recorder.stop().map_err(trim_err)?;
+        video.pause().map_err(trim_err)?;
+        _ = rx.await;
+
+        let container = mime.split(';').next().unwrap_or(mime);
+        let props = FilePropertyBag::new();
+        props.set_type(container);
+        let extension = if container == "video/mp4" {
+            "mp4"
+        } else {
+            "webm"
+        };
+        let stem = name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(name);
+        if chunks.length() == 0 {
+            return Err(PreprocessError::TrimFailed("recording was empty".into()));
+        }
+        leptos::web_sys::File::new_with_blob_sequence_and_options(
+            &chunks,
+            &format!("{stem}-trimmed.{extension}"),
+            &props,
+        )
+        .map_err(trim_err)
+    }
+
+    /// Seeks `video` to `pct` of `duration`, used for picking the cover frame
+    pub fn seek_to(video: &HtmlVideoElement, duration: f64, pct: f64) {
+        _ = video.pause();
+        video.set_current_time(duration * pct.clamp(0.0, 1.0));
+    }
+}
+
+#[cfg(feature = "hydrate")]
+pub use client::*;
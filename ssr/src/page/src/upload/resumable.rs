@@ -0,0 +1,456 @@
+//! Resumable, chunked video uploads using the [tus protocol](https://tus.io/protocols/resumable-upload)
+//!
+//! The upload url and the video uid are persisted in local storage, keyed by a
+//! fingerprint of the selected file. Selecting the same file again after a
+//! reload resumes the upload from the last offset acknowledged by the server.
+//!
+//! If the upload service doesn't offer resumable uploads, the whole file is
+//! sent in a single request to a `get_upload_url_v2` url instead.
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Size of each uploaded chunk
+/// must be a multiple of 256 KiB as required by cloudflare stream
+pub const CHUNK_SIZE: u64 = 20 * 256 * 1024;
+/// Maximum attempts for a single request before the upload is failed
+#[cfg(feature = "hydrate")]
+const MAX_ATTEMPTS: u32 = 5;
+#[cfg(feature = "hydrate")]
+const RETRY_BASE_DELAY: web_time::Duration = web_time::Duration::from_millis(500);
+#[cfg(feature = "hydrate")]
+const TUS_VERSION: &str = "1.0.0";
+
+/// Progress of a chunked upload
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChunkProgress {
+    /// chunks acknowledged by the server
+    pub done: u64,
+    pub total: u64,
+    /// progress of the chunk currently being uploaded, 0.0..=1.0
+    pub current: f64,
+}
+
+impl ChunkProgress {
+    fn new(offset: u64, size: u64) -> Self {
+        Self {
+            done: offset / CHUNK_SIZE,
+            total: size.div_ceil(CHUNK_SIZE).max(1),
+            current: 0.0,
+        }
+    }
+
+    /// Overall progress, 0.0..=1.0
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        ((self.done as f64 + self.current) / self.total as f64).min(1.0)
+    }
+
+    /// e.g "Chunk 2 of 5"
+    pub fn label(&self) -> String {
+        let current = (self.done + 1).min(self.total);
+        format!("Chunk {current} of {}", self.total)
+    }
+}
+
+/// Upload state persisted across reloads
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PersistedUpload {
+    pub fingerprint: String,
+    pub upload_url: String,
+    pub uid: String,
+}
+
+/// Shared controls for an in flight upload
+#[derive(Clone, Copy)]
+pub struct UploadControl {
+    pub progress: RwSignal<ChunkProgress>,
+    pub paused: RwSignal<bool>,
+    /// set while a failed request is being retried
+    pub retry_attempt: RwSignal<Option<u32>>,
+    /// set if a previous upload of the selected file is being resumed
+    pub resumed: RwSignal<bool>,
+}
+
+impl Default for UploadControl {
+    fn default() -> Self {
+        Self {
+            progress: RwSignal::new(ChunkProgress::default()),
+            paused: RwSignal::new(false),
+            retry_attempt: RwSignal::new(None),
+            resumed: RwSignal::new(false),
+        }
+    }
+}
+
+#[cfg(feature = "hydrate")]
+mod client {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    use futures::channel::oneshot;
+    use gloo::file::File;
+    use leptos::web_sys::{Blob, FormData, ProgressEvent, XmlHttpRequest};
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    use super::*;
+    use crate::upload::video_upload::Message;
+
+    pub fn fingerprint(file: &File) -> String {
+        let modified = file
+            .last_modified_time()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+        format!("{}-{}-{modified}", file.name(), file.size())
+    }
+
+    struct XhrResponse {
+        status: u16,
+        upload_offset: Option<u64>,
+    }
+
+    fn xhr_err(ctx: &str, e: impl std::fmt::Debug) -> ServerFnError {
+        ServerFnError::new(format!("{ctx}: {e:?}"))
+    }
+
+    enum XhrBody<'a> {
+        Blob(&'a Blob),
+        Form(&'a FormData),
+    }
+
+    async fn xhr_send(
+        method: &str,
+        url: &str,
+        headers: &[(&str, String)],
+        body: Option<XhrBody<'_>>,
+        on_progress: impl Fn(f64) + 'static,
+    ) -> Result<XhrResponse, ServerFnError> {
+        let (tx, rx) = oneshot::channel::<Result<XhrResponse, ServerFnError>>();
+        let sender = Rc::new(RefCell::new(Some(tx)));
+        let xhr = XmlHttpRequest::new().map_err(|e| xhr_err("Failed to create XHR", e))?;
+
+        xhr.open(method, url)
+            .map_err(|e| xhr_err("XHR open failed", e))?;
+        for (name, value) in headers {
+            xhr.set_request_header(name, value)
+                .map_err(|e| xhr_err("Failed to set XHR header", e))?;
+        }
+
+        let on_progress_callback = Closure::wrap(Box::new(move |event: ProgressEvent| {
+            if event.length_computable() {
+                on_progress(event.loaded() / event.total());
+            }
+        }) as Box<dyn FnMut(_)>);
+        xhr.upload()
+            .map_err(|e| xhr_err("Failed to get XHR upload", e))?
+            .set_onprogress(Some(on_progress_callback.as_ref().unchecked_ref()));
+        on_progress_callback.forget();
+
+        let sender_onload = sender.clone();
+        let xhr_onload = xhr.clone();
+        let on_load_callback = Closure::wrap(Box::new(move || {
+            let Some(sender) = sender_onload.borrow_mut().take() else {
+                return;
+            };
+            let res = xhr_onload
+                .status()
+                .map(|status| XhrResponse {
+                    status,
+                    upload_offset: xhr_onload
+                        .get_response_header("Upload-Offset")
+                        .ok()
+                        .flatten()
+                        .and_then(|o| o.parse().ok()),
+                })
+                .map_err(|e| xhr_err("Upload XHR failed to get status", e));
+            _ = sender.send(res);
+        }) as Box<dyn FnMut()>);
+        xhr.set_onload(Some(on_load_callback.as_ref().unchecked_ref()));
+        on_load_callback.forget();
+
+        let sender_onerror = sender.clone();
+        let on_error_callback = Closure::wrap(Box::new(move || {
+            if let Some(sender) = sender_onerror.borrow_mut().take() {
+                _ = sender.send(Err(ServerFnError::new("Upload XHR network error")));
+            }
+        }) as Box<dyn FnMut()>);
+        xhr.set_onerror(Some(on_error_callback.as_ref().unchecked_ref()));
+        xhr.set_ontimeout(Some(on_error_callback.as_ref().unchecked_ref()));
+        on_error_callback.forget();
+
+        match body {
+            None => xhr.send(),
+            Some(XhrBody::Blob(blob)) => xhr.send_with_opt_blob(Some(blob)),
+            Some(XhrBody::Form(form)) => xhr.send_with_opt_form_data(Some(form)),
+        }
+        .map_err(|e| xhr_err("XHR send failed", e))?;
+
+        rx.await
+            .map_err(|_| ServerFnError::new("XHR future cancelled or sender dropped"))?
+    }
+
+    /// Retries `f` with exponential backoff
+    async fn with_retry<T, Fut>(
+        control: UploadControl,
+        mut f: impl FnMut() -> Fut,
+    ) -> Result<T, ServerFnError>
+    where
+        Fut: std::future::Future<Output = Result<T, ServerFnError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match f().await {
+                Ok(res) => {
+                    control.retry_attempt.set(None);
+                    return Ok(res);
+                }
+                Err(e) if attempt >= MAX_ATTEMPTS => {
+                    control.retry_attempt.set(None);
+                    return Err(e);
+                }
+                Err(e) => {
+                    log::warn!("upload request failed (attempt {attempt}): {e}");
+                    control.retry_attempt.set(Some(attempt));
+                    utils::time::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt - 1)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Where the file is uploaded to
+    enum UploadTarget {
+        /// tus upload url, takes the file in chunks
+        Resumable { upload_url: String, uid: String },
+        /// takes the whole file as a single form upload
+        Direct { upload_url: String, uid: String },
+    }
+
+    async fn parse_upload_url(
+        response: gloo::net::http::Response,
+    ) -> Result<(String, String), ServerFnError> {
+        if !response.ok() {
+            return Err(ServerFnError::new(format!(
+                "Failed to get upload URL: status {}",
+                response.status()
+            )));
+        }
+        let message: Message = response
+            .json()
+            .await
+            .map_err(|e| ServerFnError::new(format!("Failed to parse upload URL response: {e}")))?;
+        let data = message
+            .data
+            .ok_or_else(|| ServerFnError::new("Upload URL not found in response"))?;
+
+        match (data.upload_url, data.uid) {
+            (Some(url), Some(uid)) => Ok((url, uid)),
+            _ => Err(ServerFnError::new("Upload URL not found in response")),
+        }
+    }
+
+    async fn create_upload(
+        upload_base_url: &str,
+        size: u64,
+    ) -> Result<UploadTarget, ServerFnError> {
+        use gloo::net::http::Request;
+
+        let endpoint = format!("{upload_base_url}/get_resumable_upload_url?upload_length={size}");
+        let response = Request::get(&endpoint).send().await?;
+        if matches!(response.status(), 404 | 405 | 501) {
+            log::warn!(
+                "resumable uploads unavailable (status {}), uploading in a single request",
+                response.status()
+            );
+            let endpoint = format!("{upload_base_url}/get_upload_url_v2");
+            let (upload_url, uid) = parse_upload_url(Request::get(&endpoint).send().await?).await?;
+            return Ok(UploadTarget::Direct { upload_url, uid });
+        }
+
+        let (upload_url, uid) = parse_upload_url(response).await?;
+        Ok(UploadTarget::Resumable { upload_url, uid })
+    }
+
+    /// Sends the whole file as one form upload, a failed attempt starts over
+    async fn upload_direct(
+        upload_url: &str,
+        file: &File,
+        control: UploadControl,
+    ) -> Result<(), ServerFnError> {
+        let form = FormData::new().map_err(|e| xhr_err("Failed to create FormData", e))?;
+        form.append_with_blob("file", file.as_ref())
+            .map_err(|e| xhr_err("Failed to append blob to FormData", e))?;
+
+        let progress = control.progress;
+        progress.set(ChunkProgress {
+            done: 0,
+            total: 1,
+            current: 0.0,
+        });
+        with_retry(control, || async {
+            let res = xhr_send(
+                "POST",
+                upload_url,
+                &[],
+                Some(XhrBody::Form(&form)),
+                move |p| progress.update(|progress| progress.current = p),
+            )
+            .await?;
+            if !(200..300).contains(&res.status) {
+                return Err(ServerFnError::new(format!(
+                    "Upload failed: status {}",
+                    res.status
+                )));
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Offset acknowledged by the server for an existing upload
+    /// returns None if the upload has expired
+    async fn fetch_offset(upload_url: &str) -> Result<Option<u64>, ServerFnError> {
+        let res = xhr_send(
+            "HEAD",
+            upload_url,
+            &[("Tus-Resumable", TUS_VERSION.into())],
+            None,
+            |_| (),
+        )
+        .await?;
+        match res.status {
+            200..300 => Ok(res.upload_offset),
+            404 | 410 => Ok(None),
+            status => Err(ServerFnError::new(format!(
+                "Failed to fetch upload offset: status {status}"
+            ))),
+        }
+    }
+
+    /// Uploads `file` in chunks of [CHUNK_SIZE], resuming `persisted` if it
+    /// belongs to the same file. Returns the video uid on completion
+    ///
+    /// Falls back to a single request upload if resumable uploads aren't available
+    pub async fn upload_resumable(
+        upload_base_url: &str,
+        file: &File,
+        control: UploadControl,
+        persisted: Signal<Option<PersistedUpload>>,
+        set_persisted: WriteSignal<Option<PersistedUpload>>,
+    ) -> Result<String, ServerFnError> {
+        let size = file.size();
+        let fingerprint = fingerprint(file);
+
+        let mut resume = persisted
+            .get_untracked()
+            .filter(|p| p.fingerprint == fingerprint);
+        let mut offset = 0;
+        if let Some(p) = resume.as_ref() {
+            match with_retry(control, || fetch_offset(&p.upload_url)).await? {
+                Some(acked) => {
+                    offset = acked;
+                    control.resumed.set(true);
+                }
+                None => resume = None,
+            }
+        }
+
+        let PersistedUpload {
+            upload_url, uid, ..
+        } = match resume {
+            Some(p) => p,
+            None => {
+                let (upload_url, uid) =
+                    match with_retry(control, || create_upload(upload_base_url, size)).await? {
+                        UploadTarget::Resumable { upload_url, uid } => (upload_url, uid),
+                        UploadTarget::Direct { upload_url, uid } => {
+                            set_persisted.set(None);
+                            upload_direct(&upload_url, file, control).await?;
+                            control.progress.update(|p| {
+                                p.done = p.total;
+                                p.current = 0.0;
+                            });
+                            return Ok(uid);
+                        }
+                    };
+                let p = PersistedUpload {
+                    fingerprint,
+                    upload_url,
+                    uid,
+                };
+                set_persisted.set(Some(p.clone()));
+                p
+            }
+        };
+
+        control.progress.set(ChunkProgress::new(offset, size));
+        while offset < size {
+            while control.paused.get_untracked() {
+                utils::time::sleep(web_time::Duration::from_millis(300)).await;
+            }
+
+            let progress = control.progress;
+            let chunk_start = Cell::new(offset);
+            let retrying = Cell::new(false);
+            offset = with_retry(control, || async {
+                // a failed PATCH may have been partially applied, resending the
+                // same Upload-Offset would be rejected with a 409
+                if retrying.replace(true) {
+                    let acked = fetch_offset(&upload_url)
+                        .await?
+                        .ok_or_else(|| ServerFnError::new("Upload expired on the server"))?;
+                    chunk_start.set(acked);
+                    progress.set(ChunkProgress::new(acked, size));
+                }
+                let start = chunk_start.get();
+                if start >= size {
+                    return Ok(start);
+                }
+
+                let end = (start + CHUNK_SIZE).min(size);
+                let chunk = file.slice(start, end);
+                let headers = [
+                    ("Tus-Resumable", TUS_VERSION.to_string()),
+                    ("Upload-Offset", start.to_string()),
+                    (
+                        "Content-Type",
+                        "application/offset+octet-stream".to_string(),
+                    ),
+                ];
+                let res = xhr_send(
+                    "PATCH",
+                    &upload_url,
+                    &headers,
+                    Some(XhrBody::Blob(chunk.as_ref())),
+                    move |p| progress.update(|progress| progress.current = p),
+                )
+                .await?;
+                if !(200..300).contains(&res.status) {
+                    return Err(ServerFnError::new(format!(
+                        "Chunk upload failed: status {}",
+                        res.status
+                    )));
+                }
+                Ok(res.upload_offset.unwrap_or(end))
+            })
+            .await?;
+            control.progress.set(ChunkProgress::new(offset, size));
+        }
+
+        set_persisted.set(None);
+        control.progress.update(|p| {
+            p.done = p.total;
+            p.current = 0.0;
+        });
+
+        Ok(uid)
+    }
+}
+
+#[cfg(feature = "hydrate")]
+pub use client::*;
@@ -0,0 +1,516 @@
+use std::collections::HashSet;
+
+use auth::delegate_short_lived_identity;
+use candid::Principal;
+use codee::string::JsonSerdeCodec;
+use component::{back_btn::BackButton, spinner::FullScreenSpinner, title::TitleText};
+use consts::{FAILED_UPLOADS_STORE, OFF_CHAIN_AGENT_URL, RESUMABLE_UPLOAD_STORE};
+use leptos::prelude::*;
+use leptos_meta::*;
+use leptos_use::{storage::use_local_storage, use_interval, UseIntervalReturn};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use state::{
+    app_state::AppState,
+    canisters::{auth_state, unauth_canisters},
+};
+use utils::{bg_url, http_client::HttpClientError, send_wrap, types::PostStatus};
+use yral_canisters_client::individual_user_template::{GetPostsOfUserProfileError, Result6};
+use yral_types::delegated_identity::DelegatedIdentityWire;
+
+use super::drafts::{delete_post_draft, list_post_drafts, DraftState, PostDraft};
+use super::resumable::PersistedUpload;
+use super::video_upload::{publish_video, SerializablePostDetailsFromFrontend, VideoMetadata};
+
+/// Number of most recent posts shown
+const MY_UPLOADS_LIMIT: u64 = 20;
+/// Status is polled at this interval (ms) while any post is being processed
+const STATUS_POLL_INTERVAL_MS: u64 = 10_000;
+
+/// An upload whose metadata failed to publish
+/// the video itself is already uploaded, so retrying only publishes the metadata
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FailedUpload {
+    pub meta: VideoMetadata,
+    pub post_details: SerializablePostDetailsFromFrontend,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct UploadedPost {
+    post_id: u64,
+    video_uid: String,
+    description: String,
+    status: PostStatus,
+}
+
+async fn delete_post(
+    identity: DelegatedIdentityWire,
+    canister_id: Principal,
+    post: &UploadedPost,
+) -> Result<(), ServerFnError> {
+    let client = reqwest::Client::new();
+    let body = json!({
+        "delegated_identity_wire": identity,
+        "canister_id": canister_id.to_text(),
+        "post_id": post.post_id,
+        "video_id": post.video_uid,
+    });
+
+    let url = OFF_CHAIN_AGENT_URL.join("api/v1/posts").unwrap();
+
+    let response = client
+        .delete(url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(HttpClientError::from)?;
+    HttpClientError::check(response).await?;
+
+    Ok(())
+}
+
+#[component]
+fn StageProgress(status: PostStatus) -> impl IntoView {
+    let current = status.stage();
+
+    view! {
+        <div class="flex flex-row gap-1 items-center w-full">
+            {PostStatus::STAGES
+                .iter()
+                .enumerate()
+                .map(|(idx, stage)| {
+                    let reached = current.is_some_and(|c| idx <= c);
+                    view! {
+                        <div class="flex flex-col flex-1 gap-1">
+                            <div
+                                class="h-1 rounded-full"
+                                class=("bg-primary-600", reached)
+                                class=("bg-neutral-700", !reached)
+                            ></div>
+                            <span
+                                class="text-[10px]"
+                                class=("text-white", reached)
+                                class=("text-neutral-500", !reached)
+                            >
+                                {stage.label()}
+                            </span>
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+#[component]
+fn UploadedPostCard(post: UploadedPost, deleted: RwSignal<HashSet<u64>>) -> impl IntoView {
+    let auth = auth_state();
+    let error = RwSignal::new(None::<String>);
+    let status = post.status;
+    let post_id = post.post_id;
+    let post = StoredValue::new(post);
+
+    let delete_action = Action::new(move |&()| {
+        send_wrap(async move {
+            let res = async {
+                let cans = auth.auth_cans(unauth_canisters()).await?;
+                let identity = delegate_short_lived_identity(cans.identity());
+                delete_post(identity, cans.user_canister(), &post.get_value()).await
+            }
+            .await;
+            match res {
+                Ok(_) => deleted.update(|d| {
+                    d.insert(post_id);
+                }),
+                Err(e) => {
+                    log::warn!("failed to delete post {e}");
+                    error.set(Some("Failed to delete the video, please try again".into()));
+                }
+            }
+        })
+    });
+    let deleting = delete_action.pending();
+
+    view! {
+        <div class="flex flex-row gap-3 p-3 w-full rounded-lg bg-neutral-900">
+            <img
+                class="object-cover w-16 h-24 rounded-md bg-neutral-800"
+                src=bg_url(post.with_value(|p| p.video_uid.clone()))
+            />
+            <div class="flex flex-col flex-1 gap-2 min-w-0">
+                <span class="text-sm truncate">{post.with_value(|p| p.description.clone())}</span>
+                <span class="text-xs font-semibold text-neutral-300">{status.label()}</span>
+                {match status.ban_reason() {
+                    Some(reason) => view! { <span class="text-xs text-red-500">{reason}</span> }.into_any(),
+                    None => view! { <StageProgress status /> }.into_any(),
+                }}
+                <Show when=move || error.with(|e| e.is_some())>
+                    <span class="text-xs text-red-500">{move || error.get().unwrap_or_default()}</span>
+                </Show>
+                <div class="flex flex-row gap-2 justify-end">
+                    <Show when=move || status == PostStatus::ReadyToView>
+                        <a
+                            class="py-1 px-3 text-xs rounded-full bg-neutral-800"
+                            href=move || {
+                                format!(
+                                    "/profile/{}/post/{post_id}",
+                                    auth.user_canister_if_available()
+                                        .map(|c| c.to_text())
+                                        .unwrap_or_default(),
+                                )
+                            }
+                        >
+                            View
+                        </a>
+                    </Show>
+                    <button
+                        class="py-1 px-3 text-xs text-red-400 rounded-full bg-neutral-800 disabled:opacity-50"
+                        disabled=deleting
+                        on:click=move |_| {
+                            delete_action.dispatch(());
+                        }
+                    >
+                        {move || if deleting() { "Deleting..." } else { "Delete" }}
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn FailedUploadCard(
+    failed: FailedUpload,
+    set_failed_uploads: WriteSignal<Vec<FailedUpload>>,
+    on_published: impl Fn() + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let auth = auth_state();
+    let error = RwSignal::new(Some(failed.error.clone()));
+    let video_uid = failed.post_details.video_uid.clone();
+    let failed = StoredValue::new(failed);
+
+    let remove = move || {
+        let video_uid = failed.with_value(|f| f.post_details.video_uid.clone());
+        set_failed_uploads.update(|f| f.retain(|f| f.post_details.video_uid != video_uid));
+    };
+
+    let retry_action = Action::new(move |&()| {
+        send_wrap(async move {
+            let FailedUpload {
+                meta, post_details, ..
+            } = failed.get_value();
+            let res = async {
+                let cans = auth.auth_cans(unauth_canisters()).await?;
+                let identity = delegate_short_lived_identity(cans.identity());
+                publish_video(identity, meta, post_details).await
+            }
+            .await;
+            match res {
+                Ok(_) => {
+                    remove();
+                    on_published();
+                }
+                Err(e) => {
+                    log::warn!("failed to publish video {e}");
+                    error.set(Some(e.to_string()));
+                }
+            }
+        })
+    });
+    let retrying = retry_action.pending();
+
+    view! {
+        <div class="flex flex-row gap-3 p-3 w-full rounded-lg bg-neutral-900">
+            <img class="object-cover w-16 h-24 rounded-md bg-neutral-800" src=bg_url(video_uid) />
+            <div class="flex flex-col flex-1 gap-2 min-w-0">
+                <span class="text-sm truncate">
+                    {failed.with_value(|f| f.meta.description.clone())}
+                </span>
+                <span class="text-xs font-semibold text-red-500">Failed to publish</span>
+                <span class="text-xs text-neutral-400 break-words">
+                    {move || error.get().unwrap_or_default()}
+                </span>
+                <div class="flex flex-row gap-2 justify-end">
+                    <button
+                        class="py-1 px-3 text-xs rounded-full bg-primary-600 disabled:opacity-50"
+                        disabled=retrying
+                        on:click=move |_| {
+                            retry_action.dispatch(());
+                        }
+                    >
+                        {move || if retrying() { "Retrying..." } else { "Retry" }}
+                    </button>
+                    <button
+                        class="py-1 px-3 text-xs text-red-400 rounded-full bg-neutral-800"
+                        on:click=move |_| remove()
+                    >
+                        Delete
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn DraftCard(
+    draft: PostDraft,
+    on_change: impl Fn() + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let auth = auth_state();
+    let error = RwSignal::new(None::<String>);
+    let state_text = match &draft.state {
+        DraftState::Draft => format!(
+            "Draft, saved {}",
+            utils::time::get_day_month(draft.created_at)
+        ),
+        DraftState::Scheduled { publish_at } => format!(
+            "Scheduled for {} UTC",
+            utils::time::parse_ns_to_datetime(publish_at * 1_000_000_000).unwrap_or_default()
+        ),
+        DraftState::Failed { error } => format!("Scheduled publish failed: {error}"),
+    };
+    let video_uid = draft.post_details.video_uid.clone();
+    let draft = StoredValue::new(draft);
+
+    let publish_action = Action::new(move |&()| {
+        send_wrap(async move {
+            let PostDraft {
+                id,
+                meta,
+                post_details,
+                ..
+            } = draft.get_value();
+            let res = async {
+                let cans = auth.auth_cans(unauth_canisters()).await?;
+                let identity = delegate_short_lived_identity(cans.identity());
+                publish_video(identity, meta, post_details).await?;
+                delete_post_draft(id).await
+            }
+            .await;
+            match res {
+                Ok(_) => on_change(),
+                Err(e) => {
+                    log::warn!("failed to publish draft {e}");
+                    error.set(Some("Failed to publish, please try again".into()));
+                }
+            }
+        })
+    });
+    let delete_action = Action::new(move |&()| {
+        send_wrap(async move {
+            match delete_post_draft(draft.with_value(|d| d.id.clone())).await {
+                Ok(_) => on_change(),
+                Err(e) => {
+                    log::warn!("failed to delete draft {e}");
+                    error.set(Some("Failed to delete the draft, please try again".into()));
+                }
+            }
+        })
+    });
+    let busy =
+        Signal::derive(move || publish_action.pending().get() || delete_action.pending().get());
+
+    view! {
+        <div class="flex flex-row gap-3 p-3 w-full rounded-lg bg-neutral-900">
+            <img class="object-cover w-16 h-24 rounded-md bg-neutral-800" src=bg_url(video_uid) />
+            <div class="flex flex-col flex-1 gap-2 min-w-0">
+                <span class="text-sm truncate">
+                    {draft.with_value(|d| d.post_details.description.clone())}
+                </span>
+                <span class="text-xs font-semibold text-neutral-300">{state_text}</span>
+                <Show when=move || error.with(|e| e.is_some())>
+                    <span class="text-xs text-red-500">{move || error.get().unwrap_or_default()}</span>
+                </Show>
+                <div class="flex flex-row gap-2 justify-end">
+                    <button
+                        class="py-1 px-3 text-xs rounded-full bg-primary-600 disabled:opacity-50"
+                        disabled=busy
+                        on:click=move |_| {
+                            publish_action.dispatch(());
+                        }
+                    >
+                        Publish now
+                    </button>
+                    <button
+                        class="py-1 px-3 text-xs text-red-400 rounded-full bg-neutral-800 disabled:opacity-50"
+                        disabled=busy
+                        on:click=move |_| {
+                            delete_action.dispatch(());
+                        }
+                    >
+                        Delete
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn UnfinishedUploadCard(
+    persisted: Signal<Option<PersistedUpload>>,
+    set_persisted: WriteSignal<Option<PersistedUpload>>,
+) -> impl IntoView {
+    view! {
+        <Show when=move || persisted.with(|p| p.is_some())>
+            <div class="flex flex-col gap-2 p-3 w-full rounded-lg bg-neutral-900">
+                <span class="text-sm font-semibold">Unfinished upload</span>
+                <span class="text-xs text-neutral-400">
+                    "Select the same video again on the upload page to continue where you left off."
+                </span>
+                <div class="flex flex-row gap-2 justify-end">
+                    <a class="py-1 px-3 text-xs rounded-full bg-primary-600" href="/upload">
+                        Resume
+                    </a>
+                    <button
+                        class="py-1 px-3 text-xs text-red-400 rounded-full bg-neutral-800"
+                        on:click=move |_| set_persisted.set(None)
+                    >
+                        Discard
+                    </button>
+                </div>
+            </div>
+        </Show>
+    }
+}
+
+/// Status of the user's recent uploads, polled while they are processed
+#[component]
+pub fn MyUploads() -> impl IntoView {
+    let app_state = use_context::<AppState>();
+    let page_title = app_state.unwrap().name.to_owned() + " - My Uploads";
+    let auth = auth_state();
+
+    let (failed_uploads, set_failed_uploads, _) =
+        use_local_storage::<Vec<FailedUpload>, JsonSerdeCodec>(FAILED_UPLOADS_STORE);
+    let (persisted, set_persisted, _) =
+        use_local_storage::<Option<PersistedUpload>, JsonSerdeCodec>(RESUMABLE_UPLOAD_STORE);
+    let deleted = RwSignal::new(HashSet::<u64>::new());
+
+    let UseIntervalReturn {
+        counter,
+        pause,
+        resume,
+        ..
+    } = use_interval(STATUS_POLL_INTERVAL_MS);
+
+    let posts = auth.derive_resource(
+        move || counter.get(),
+        |cans, _| async move {
+            let user = cans.individual_user(cans.user_canister()).await;
+            let posts = user
+                .get_posts_of_this_user_profile_with_pagination_cursor(0, MY_UPLOADS_LIMIT)
+                .await?;
+            match posts {
+                Result6::Ok(posts) => Ok(posts
+                    .into_iter()
+                    .map(|p| UploadedPost {
+                        post_id: p.id,
+                        video_uid: p.video_uid,
+                        description: p.description,
+                        status: PostStatus::from(&p.status),
+                    })
+                    .filter(|p| p.status != PostStatus::Deleted)
+                    .collect::<Vec<_>>()),
+                Result6::Err(GetPostsOfUserProfileError::ReachedEndOfItemsList) => Ok(vec![]),
+                Result6::Err(e) => Err(ServerFnError::new(format!("failed to fetch posts: {e:?}"))),
+            }
+        },
+    );
+
+    let drafts = Resource::new(|| (), |_| send_wrap(list_post_drafts()));
+    let on_drafts_change = move || {
+        drafts.refetch();
+        posts.refetch();
+    };
+
+    // stop polling once every post has finished processing
+    Effect::new(move |_| {
+        let Some(Ok(posts)) = posts.get() else {
+            return;
+        };
+        if posts.iter().any(|p| p.status.is_processing()) {
+            resume();
+        } else {
+            pause();
+        }
+    });
+
+    view! {
+        <Title text=page_title />
+        <div class="flex flex-col items-center pt-2 pb-12 w-full min-h-screen text-white bg-black">
+            <TitleText justify_center=false>
+                <div class="flex flex-row justify-between">
+                    <BackButton fallback="/upload".to_string() />
+                    <span class="text-2xl font-bold">My Uploads</span>
+                    <div></div>
+                </div>
+            </TitleText>
+            <div class="flex flex-col gap-3 py-6 px-4 w-full max-w-lg">
+                <UnfinishedUploadCard persisted set_persisted />
+                <For
+                    each=move || failed_uploads.get()
+                    key=|f| f.post_details.video_uid.clone()
+                    let:failed
+                >
+                    <FailedUploadCard failed set_failed_uploads on_published=move || posts.refetch() />
+                </For>
+                <Suspense>
+                    {move || Suspend::new(async move {
+                        let drafts = drafts.await.unwrap_or_else(|e| {
+                            log::warn!("failed to load drafts {e}");
+                            vec![]
+                        });
+                        drafts
+                            .into_iter()
+                            .map(|draft| view! { <DraftCard draft on_change=on_drafts_change /> })
+                            .collect_view()
+                    })}
+                </Suspense>
+                <Suspense fallback=FullScreenSpinner>
+                    {move || Suspend::new(async move {
+                        match posts.await {
+                            Ok(posts) if posts.is_empty() => {
+                                view! {
+                                    <span class="text-sm text-center text-neutral-400">
+                                        "You haven't uploaded any videos yet"
+                                    </span>
+                                }
+                                    .into_any()
+                            }
+                            Ok(posts) => {
+                                view! {
+                                    <For
+                                        each=move || {
+                                            let deleted = deleted.get();
+                                            posts
+                                                .clone()
+                                                .into_iter()
+                                                .filter(move |p| !deleted.contains(&p.post_id))
+                                        }
+                                        key=|p| (p.post_id, p.status)
+                                        let:post
+                                    >
+                                        <UploadedPostCard post deleted />
+                                    </For>
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => {
+                                log::warn!("failed to load uploads {e}");
+                                view! {
+                                    <span class="text-sm text-center text-neutral-400">
+                                        Failed to load your uploads
+                                    </span>
+                                }
+                                    .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
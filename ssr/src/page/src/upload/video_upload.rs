@@ -1,24 +1,25 @@
-use super::UploadParams;
+use super::drafts::save_post_draft;
+use super::preprocess::{self, PreprocessError, MAX_VIDEO_DURATION_SECS};
+use super::resumable::{PersistedUpload, UploadControl};
+use super::status::FailedUpload;
+use super::{PublishMode, UploadParams};
 use auth::delegate_short_lived_identity;
+use codee::string::JsonSerdeCodec;
 use component::buttons::HighlightedLinkButton;
 use component::modal::Modal;
 use component::notification_nudge::NotificationNudge;
-use consts::UPLOAD_URL;
-use futures::channel::oneshot;
-use gloo::net::http::Request;
-use leptos::web_sys::{Blob, FormData, ProgressEvent};
+use consts::{FAILED_UPLOADS_STORE, RESUMABLE_UPLOAD_STORE, UPLOAD_URL};
 use leptos::{
-    ev::durationchange,
+    ev::{error, loadedmetadata},
     html::{Input, Video},
     prelude::*,
 };
 use leptos_icons::*;
+use leptos_use::storage::use_local_storage;
 use leptos_use::use_event_listener;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use state::canisters::{auth_state, unauth_canisters};
-use std::cell::RefCell;
-use std::rc::Rc;
 use utils::mixpanel::mixpanel_events::*;
 use utils::{
     event_streaming::events::{
@@ -27,7 +28,7 @@ use utils::{
     try_or_redirect_opt,
     web::FileWithUrl,
 };
-use wasm_bindgen::{closure::Closure, JsCast};
+use yral_types::delegated_identity::DelegatedIdentityWire;
 
 #[component]
 pub fn DropBox() -> impl IntoView {
@@ -47,12 +48,30 @@ pub fn DropBox() -> impl IntoView {
 pub fn PreVideoUpload(
     file_blob: RwSignal<Option<FileWithUrl>, LocalStorage>,
     uid: RwSignal<Option<String>, LocalStorage>,
-    upload_file_actual_progress: WriteSignal<f64>,
+    control: UploadControl,
+    cover_frame: RwSignal<Option<f64>>,
 ) -> impl IntoView {
     let file_ref = NodeRef::<Input>::new();
     let file = RwSignal::new_local(None::<FileWithUrl>);
     let video_ref = NodeRef::<Video>::new();
     let modal_show = RwSignal::new(false);
+    let preprocess_err = RwSignal::new(None::<PreprocessError>);
+    // duration of the video that will be uploaded, after trimming
+    let upload_duration = RwSignal::new(None::<f64>);
+    let trim_progress = RwSignal::new(0.0f64);
+
+    let reject = move |err: PreprocessError| {
+        preprocess_err.set(Some(err));
+        modal_show.set(true);
+        file.set(None);
+        uid.set(None);
+        file_blob.set(None);
+        upload_duration.set(None);
+        cover_frame.set(None);
+        if let Some(f) = file_ref.get_untracked() {
+            f.set_value("");
+        }
+    };
     let auth = auth_state();
     let ev_ctx = auth.event_ctx();
     let file_upload_clicked = Action::new(move |_: &()| {
@@ -95,6 +114,10 @@ pub fn PreVideoUpload(
             ev.target().and_then(move |target| {
                 let input: &HtmlInputElement = target.dyn_ref()?;
                 let inp_file = input.files()?.get(0)?;
+                if let Err(e) = preprocess::check_file(&inp_file.type_(), inp_file.size() as u64) {
+                    reject(e);
+                    return None;
+                }
                 file.set(Some(FileWithUrl::new(inp_file.into())));
 
                 VideoUploadVideoSelected.send_event(ev_ctx);
@@ -104,65 +127,130 @@ pub fn PreVideoUpload(
         });
     }
 
-    let upload_action: Action<(), _> = Action::new_local(move |_| {
-        let captured_progress_signal = upload_file_actual_progress;
+    let (persisted_upload, set_persisted_upload, _) =
+        use_local_storage::<Option<PersistedUpload>, JsonSerdeCodec>(RESUMABLE_UPLOAD_STORE);
+
+    let upload_action: Action<(), _> = Action::new_local(move |_| async move {
+        #[cfg(feature = "hydrate")]
+        {
+            use super::resumable::upload_resumable;
+
+            let file = file_blob.get_untracked().unwrap().file;
+            let uploaded_uid = try_or_redirect_opt!(upload_resumable(
+                UPLOAD_URL,
+                &file,
+                control,
+                persisted_upload,
+                set_persisted_upload,
+            )
+            .await
+            .inspect_err(|e| {
+                VideoUploadUnsuccessful.send_event(ev_ctx, e.to_string(), 0, false, true);
+                if let Some(global) = MixpanelGlobalProps::from_ev_ctx(ev_ctx) {
+                    MixPanelEvent::track_video_upload_error_shown(
+                        MixpanelVideoUploadFailureProps {
+                            user_id: global.user_id,
+                            visitor_id: global.visitor_id,
+                            is_logged_in: global.is_logged_in,
+                            canister_id: global.canister_id,
+                            is_nsfw_enabled: global.is_nsfw_enabled,
+                            error: e.to_string(),
+                        },
+                    );
+                }
+            }));
+
+            uid.set(Some(uploaded_uid));
+        }
+        #[cfg(not(feature = "hydrate"))]
+        {
+            _ = (persisted_upload, set_persisted_upload);
+        }
+
+        Some(())
+    });
+
+    let trim_action: Action<FileWithUrl, _> = Action::new_local(move |vid_file: &FileWithUrl| {
+        let vid_file = vid_file.clone();
         async move {
             #[cfg(feature = "hydrate")]
             {
-                let message = try_or_redirect_opt!(upload_video_part(
-                    UPLOAD_URL,
-                    "file",
-                    file_blob.get_untracked().unwrap().file.as_ref(),
-                    captured_progress_signal,
+                trim_progress.set(0.0);
+                let trimmed = preprocess::trim_video(
+                    &vid_file.url.to_string(),
+                    &vid_file.file.name(),
+                    MAX_VIDEO_DURATION_SECS,
+                    move |p| trim_progress.set(p),
                 )
-                .await
-                .inspect_err(|e| {
-                    VideoUploadUnsuccessful.send_event(ev_ctx, e.to_string(), 0, false, true);
-                    if let Some(global) = MixpanelGlobalProps::from_ev_ctx(ev_ctx) {
-                        MixPanelEvent::track_video_upload_error_shown(
-                            MixpanelVideoUploadFailureProps {
-                                user_id: global.user_id,
-                                visitor_id: global.visitor_id,
-                                is_logged_in: global.is_logged_in,
-                                canister_id: global.canister_id,
-                                is_nsfw_enabled: global.is_nsfw_enabled,
-                                error: e.to_string(),
-                            },
-                        );
+                .await;
+                match trimmed {
+                    Ok(trimmed) => {
+                        let trimmed = FileWithUrl::new(trimmed.into());
+                        // set before `file` so the reloaded preview is not re-checked
+                        file_blob.set(Some(trimmed.clone()));
+                        file.set(Some(trimmed));
+                        upload_action.dispatch(());
                     }
-                }));
-
-                uid.set(message.data.and_then(|m| m.uid));
+                    Err(e) => reject(e),
+                }
+            }
+            #[cfg(not(feature = "hydrate"))]
+            {
+                _ = vid_file;
             }
-
-            Some(())
         }
     });
 
-    _ = use_event_listener(video_ref, durationchange, move |_| {
-        let duration = video_ref
-            .get_untracked()
-            .map(|v| v.duration())
-            .unwrap_or_default();
+    _ = use_event_listener(video_ref, loadedmetadata, move |_| {
+        if file_blob.with_untracked(|f| f.is_some()) {
+            return;
+        }
+        let Some(video) = video_ref.get_untracked() else {
+            return;
+        };
         let Some(vid_file) = file.get_untracked() else {
             return;
         };
-        if duration <= 60.0 || duration.is_nan() {
-            modal_show.set(false);
-            file_blob.set(Some(vid_file));
-            upload_action.dispatch(());
+
+        let probe = preprocess::probe(&video);
+        let plan = match preprocess::plan(probe) {
+            Ok(plan) => plan,
+            Err(e) => {
+                reject(e);
+                return;
+            }
+        };
+
+        modal_show.set(false);
+        upload_duration.set(Some(plan.upload_duration(&probe)));
+        if plan.trim_to.is_some() {
+            trim_action.dispatch(vid_file);
             return;
         }
+        file_blob.set(Some(vid_file));
+        upload_action.dispatch(());
+    });
 
-        modal_show.set(true);
-        file.set(None);
-        uid.set(None);
-        file_blob.set(None);
-        if let Some(f) = file_ref.get_untracked() {
-            f.set_value("");
+    _ = use_event_listener(video_ref, error, move |_| {
+        if file.with_untracked(|f| f.is_some()) {
+            reject(PreprocessError::UnsupportedCodec);
         }
     });
 
+    let on_cover_input = move |ev| {
+        let Ok(pct) = event_target_value(&ev).parse::<f64>() else {
+            return;
+        };
+        let pct = pct / 100.0;
+        cover_frame.set(Some(pct));
+        #[cfg(feature = "hydrate")]
+        if let (Some(video), Some(duration)) =
+            (video_ref.get_untracked(), upload_duration.get_untracked())
+        {
+            preprocess::seek_to(&video, duration, pct);
+        }
+    };
+
     view! {
         <label
             for="dropzone-file"
@@ -174,13 +262,38 @@ pub fn PreVideoUpload(
                         Upload a video to share with the world!
                     </div>
                     <div class="leading-tight text-center text-neutral-400 text-[13px]">
-                        Drag & Drop or select video file ( Max 60s )
+                        Drag & Drop or select video file ( videos over 60s are trimmed )
                     </div>
                     <span class="inline-block py-2 px-6 font-medium text-pink-300 bg-transparent rounded-lg border border-pink-300 transition-colors duration-150 cursor-pointer select-none text-[15px]">
                         Select File
                     </span>
                 </div>
             </Show>
+            <Show when=move || trim_action.pending().get()>
+                <div class="flex flex-col gap-1 items-center py-2 w-full text-xs text-neutral-300">
+                    <span>
+                        {move || {
+                            format!(
+                                "Trimming video to {MAX_VIDEO_DURATION_SECS}s... {:.0}%",
+                                trim_progress.get() * 100.0,
+                            )
+                        }}
+                    </span>
+                </div>
+            </Show>
+            <Show when=move || upload_action.pending().get()>
+                <div class="flex flex-col gap-1 items-center py-2 w-full text-xs text-neutral-300">
+                    <span>{move || upload_status_text(control)}</span>
+                    <div class="w-3/4 h-1 rounded-full bg-neutral-800">
+                        <div
+                            class="h-1 rounded-full bg-pink-400"
+                            style:width=move || {
+                                format!("{:.0}%", control.progress.get().current * 100.0)
+                            }
+                        ></div>
+                    </div>
+                </div>
+            </Show>
             <Show when=move || { file.with(|file| file.is_some()) }>
                 <video
                     node_ref=video_ref
@@ -194,7 +307,11 @@ pub fn PreVideoUpload(
                 ></video>
             </Show>
             <input
-                on:click=move |_| {modal_show.set(true); file_upload_clicked.dispatch(());}
+                on:click=move |_| {
+                    preprocess_err.set(None);
+                    modal_show.set(true);
+                    file_upload_clicked.dispatch(());
+                }
                 id="dropzone-file"
                 node_ref=file_ref
                 type="file"
@@ -202,9 +319,34 @@ pub fn PreVideoUpload(
                 class="hidden w-0 h-0"
             />
         </label>
+        <Show when=move || upload_duration.with(|d| d.is_some())>
+            <div class="flex flex-col gap-1 py-2 w-full text-left">
+                <label for="cover-frame" class="text-sm text-neutral-300">
+                    Cover frame
+                </label>
+                <input
+                    id="cover-frame"
+                    type="range"
+                    min="0"
+                    max="100"
+                    value="0"
+                    class="w-full accent-pink-400"
+                    on:input=on_cover_input
+                />
+            </div>
+        </Show>
         <Modal show=modal_show>
             <span class="flex flex-col justify-center items-center py-10 w-full h-full text-lg text-center text-white md:text-xl">
-                Please ensure that the video is shorter than 60 seconds
+                {move || {
+                    preprocess_err
+                        .get()
+                        .map(|e| e.to_string())
+                        .unwrap_or_else(|| {
+                            format!(
+                                "Videos longer than {MAX_VIDEO_DURATION_SECS} seconds are trimmed before uploading",
+                            )
+                        })
+                }}
             </span>
         </Modal>
     }
@@ -243,14 +385,20 @@ pub struct Watermark {
     pub uid: Option<String>,
 }
 #[allow(dead_code)]
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct VideoMetadata {
     pub title: String,
     pub description: String,
     pub tags: String,
+    /// position of the cover frame, 0.0..=1.0
+    #[serde(
+        rename = "thumbnailTimestampPct",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub thumbnail_timestamp_pct: Option<f64>,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct SerializablePostDetailsFromFrontend {
     pub is_nsfw: bool,
     pub hashtags: Vec<String>,
@@ -259,134 +407,74 @@ pub struct SerializablePostDetailsFromFrontend {
     pub creator_consent_for_inclusion_in_hot_or_not: bool,
 }
 
-#[cfg(feature = "hydrate")]
-async fn upload_video_part(
-    upload_base_url: &str,
-    form_field_name: &str,
-    file_blob: &Blob,
-    progress_signal: WriteSignal<f64>,
-) -> Result<Message, ServerFnError> {
-    let get_url_endpoint = format!("{upload_base_url}/get_upload_url_v2");
-    let response = Request::get(&get_url_endpoint).send().await?;
-    if !response.ok() {
+impl VideoMetadata {
+    pub fn from_post_details(
+        details: &SerializablePostDetailsFromFrontend,
+        thumbnail_timestamp_pct: Option<f64>,
+    ) -> Self {
+        Self {
+            title: details.description.clone(),
+            description: details.description.clone(),
+            tags: details.hashtags.join(","),
+            thumbnail_timestamp_pct,
+        }
+    }
+}
+
+/// Publishes the metadata of an uploaded video, which creates the post
+pub async fn publish_video(
+    identity: DelegatedIdentityWire,
+    meta: VideoMetadata,
+    post_details: SerializablePostDetailsFromFrontend,
+) -> Result<(), ServerFnError> {
+    let client = reqwest::Client::new();
+    let res = client
+        .post(format!("{UPLOAD_URL}/update_metadata"))
+        .json(&json!({
+            "video_uid": post_details.video_uid,
+            "delegated_identity_wire": identity,
+            "meta": meta,
+            "post_details": post_details,
+        }))
+        .send()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    if !res.status().is_success() {
         return Err(ServerFnError::new(format!(
-            "Failed to get upload URL: status {}",
-            response.status()
+            "Publishing video failed with status: {}",
+            res.status()
         )));
     }
-    let response_text = response.text().await?;
-    let upload_message: Message = serde_json::from_str(&response_text)
-        .map_err(|e| ServerFnError::new(format!("Failed to parse upload URL response: {e}")))?;
-
-    let upload_url = upload_message
-        .data
-        .clone()
-        .and_then(|d| d.upload_url)
-        .ok_or_else(|| ServerFnError::new("Upload URL not found in response".to_string()))?;
-
-    let form = FormData::new().map_err(|js_value| {
-        ServerFnError::new(format!("Failed to create FormData: {js_value:?}"))
-    })?;
-    form.append_with_blob(form_field_name, file_blob)
-        .map_err(|js_value| {
-            ServerFnError::new(format!("Failed to append blob to FormData: {js_value:?}"))
-        })?;
-
-    let (tx, rx) = oneshot::channel();
-    let xhr = web_sys::XmlHttpRequest::new()
-        .map_err(|e| ServerFnError::new(format!("Failed to create XHR: {e:?}")))?;
-    let xhr_upload = xhr
-        .upload()
-        .map_err(|e| ServerFnError::new(format!("Failed to get XHR upload: {e:?}")))?;
-
-    let sender_rc = Rc::new(RefCell::new(Some(tx)));
-
-    let progress_signal_clone = progress_signal;
-    let on_progress_callback = Closure::wrap(Box::new(move |event: ProgressEvent| {
-        if event.length_computable() {
-            let progress = event.loaded() / event.total();
-            progress_signal_clone.set(progress);
-        }
-    }) as Box<dyn FnMut(_)>);
-    xhr_upload.set_onprogress(Some(on_progress_callback.as_ref().unchecked_ref()));
-    on_progress_callback.forget();
-
-    let sender_onload_rc = sender_rc.clone();
-    let progress_signal_onload = progress_signal;
-    let xhr_clone_onload = xhr.clone();
-    let on_load_callback = Closure::wrap(Box::new(move || {
-        if let Some(sender) = sender_onload_rc.borrow_mut().take() {
-            match xhr_clone_onload.status() {
-                Ok(status) if (200..300).contains(&status) => {
-                    progress_signal_onload.set(1.0);
-                    let _ = sender.send(Ok(()));
-                }
-                Ok(status) => {
-                    let err_msg = format!(
-                        "Upload XHR failed: status {} {}",
-                        status,
-                        xhr_clone_onload.status_text().unwrap_or_default()
-                    );
-                    let _ = sender.send(Err(ServerFnError::new(err_msg)));
-                }
-                Err(_) => {
-                    let _ = sender.send(Err(ServerFnError::new(
-                        "Upload XHR failed to get status".to_string(),
-                    )));
-                }
-            }
-        }
-    }) as Box<dyn FnMut()>);
-    xhr.set_onload(Some(on_load_callback.as_ref().unchecked_ref()));
-    on_load_callback.forget();
-
-    let sender_onerror_rc = sender_rc.clone();
-    let xhr_clone_onerror = xhr.clone();
-    let on_error_callback = Closure::wrap(Box::new(move || {
-        if let Some(sender) = sender_onerror_rc.borrow_mut().take() {
-            let status = xhr_clone_onerror.status().unwrap_or(0);
-            let status_text = xhr_clone_onerror.status_text().unwrap_or_default();
-            let err_msg =
-                format!("Upload XHR network error. Status: {status}, Text: {status_text}");
-            let _ = sender.send(Err(ServerFnError::new(err_msg)));
-        }
-    }) as Box<dyn FnMut()>);
-    xhr.set_onerror(Some(on_error_callback.as_ref().unchecked_ref()));
-    on_error_callback.forget();
-
-    let sender_ontimeout_rc = sender_rc.clone();
-    let on_timeout_callback = Closure::wrap(Box::new(move || {
-        if let Some(sender) = sender_ontimeout_rc.borrow_mut().take() {
-            let _ = sender.send(Err(ServerFnError::new("Upload XHR timeout".to_string())));
+
+    // scheduled posts are indexed by the scheduler, which has no request to call back into
+    #[cfg(feature = "hydrate")]
+    {
+        use crate::post_view::tags::index_published_post;
+
+        let user = candid::Principal::self_authenticating(&identity.from_key);
+        if let Err(e) = index_published_post(user, post_details.video_uid.clone()).await {
+            log::warn!(
+                "failed to index hashtags of {}: {e}",
+                post_details.video_uid
+            );
         }
-    }) as Box<dyn FnMut()>);
-    xhr.set_ontimeout(Some(on_timeout_callback.as_ref().unchecked_ref()));
-    on_timeout_callback.forget();
-
-    xhr.open("POST", &upload_url)
-        .map_err(|e| ServerFnError::new(format!("XHR open failed: {e:?}")))?;
-
-    xhr.send_with_opt_form_data(Some(&form))
-        .map_err(|e| ServerFnError::new(format!("XHR send failed: {e:?}")))?;
-
-    match rx.await {
-        Ok(Ok(())) => Ok(upload_message),
-        Ok(Err(e)) => Err(e),
-        Err(_) => Err(ServerFnError::new(
-            "XHR future cancelled or sender dropped".to_string(),
-        )),
     }
+
+    Ok(())
 }
 
 #[component]
 pub fn VideoUploader(
     params: UploadParams,
     uid: RwSignal<Option<String>, LocalStorage>,
-    upload_file_actual_progress: ReadSignal<f64>,
+    control: UploadControl,
 ) -> impl IntoView {
     let file_blob = params.file_blob;
     let hashtags = params.hashtags;
     let description = params.description;
+    let cover_frame_pct = params.cover_frame_pct;
+    let publish_mode = params.publish_mode;
 
     let published = RwSignal::new(false);
     let video_url = StoredValue::new_local(file_blob.url);
@@ -399,6 +487,8 @@ pub fn VideoUploader(
     let ev_ctx = auth.event_ctx();
 
     let notification_nudge = RwSignal::new(false);
+    let (_, set_failed_uploads, _) =
+        use_local_storage::<Vec<FailedUpload>, JsonSerdeCodec>(FAILED_UPLOADS_STORE);
 
     let publish_action: Action<_, _> = Action::new_unsync(move |&()| {
         let unauth_cans = unauth_canisters();
@@ -413,34 +503,39 @@ pub fn VideoUploader(
             let canisters = auth.auth_cans(unauth_cans).await.ok()?;
             let id = canisters.identity();
             let delegated_identity = delegate_short_lived_identity(id);
-            let res: std::result::Result<reqwest::Response, ServerFnError> = {
-                let client = reqwest::Client::new();
-                notification_nudge.set(true);
-                let req = client
-                    .post(format!("{UPLOAD_URL}/update_metadata"))
-                    .json(&json!({
-                        "video_uid": uid,
-                        "delegated_identity_wire": delegated_identity,
-                        "meta": VideoMetadata{
-                            title: description.clone(),
-                            description: description.clone(),
-                            tags: hashtags.join(",")
-                        },
-                        "post_details": SerializablePostDetailsFromFrontend{
-                            is_nsfw,
-                            hashtags,
-                            description,
-                            video_uid: uid_value.clone(),
-                            creator_consent_for_inclusion_in_hot_or_not: enable_hot_or_not,
-                        }
-                    }));
-
-                req.send()
-                    .await
-                    .map_err(|e| ServerFnError::new(e.to_string()))
+            notification_nudge.set(publish_mode == PublishMode::Now);
+            let post_details = SerializablePostDetailsFromFrontend {
+                is_nsfw,
+                hashtags,
+                description,
+                video_uid: uid_value.clone(),
+                creator_consent_for_inclusion_in_hot_or_not: enable_hot_or_not,
             };
 
-            match res {
+            if publish_mode != PublishMode::Now {
+                let publish_at = match publish_mode {
+                    PublishMode::Schedule { publish_at } => Some(publish_at),
+                    _ => None,
+                };
+                let res = save_post_draft(None, post_details, cover_frame_pct, publish_at).await;
+                if let Err(e) = &res {
+                    VideoUploadUnsuccessful.send_event(
+                        ev_ctx,
+                        e.to_string(),
+                        hashtags_len,
+                        is_nsfw,
+                        enable_hot_or_not,
+                    );
+                }
+                try_or_redirect_opt!(res);
+                published.set(true);
+                return Some(());
+            }
+
+            let meta = VideoMetadata::from_post_details(&post_details, cover_frame_pct);
+            let res = publish_video(delegated_identity, meta.clone(), post_details.clone()).await;
+
+            match &res {
                 Ok(_) => {
                     let is_logged_in = is_connected.get_untracked();
                     let global = MixpanelGlobalProps::try_get(&canisters, is_logged_in);
@@ -457,8 +552,17 @@ pub fn VideoUploader(
                     });
                     published.set(true)
                 }
-                Err(_) => {
-                    let e = res.as_ref().err().unwrap().to_string();
+                Err(e) => {
+                    let e = e.to_string();
+                    // kept so the user can retry from the uploads page
+                    set_failed_uploads.update(|failed| {
+                        failed.retain(|f| f.post_details.video_uid != uid_value);
+                        failed.push(FailedUpload {
+                            meta,
+                            post_details,
+                            error: e.clone(),
+                        });
+                    });
                     VideoUploadUnsuccessful.send_event(
                         ev_ctx,
                         e,
@@ -536,7 +640,7 @@ pub fn VideoUploader(
                             } else {
                                 format!(
                                     "{:.2}%",
-                                    upload_file_actual_progress.get() * video_uploaded_base_width,
+                                    control.progress.get().fraction() * video_uploaded_base_width,
                                 )
                             }
                         }
@@ -549,7 +653,7 @@ pub fn VideoUploader(
                         } else if publish_action.pending().get() {
                             "Processing video metadata...".to_string()
                         } else if uid.with(|u| u.is_none()) {
-                            "Uploading video file...".to_string()
+                            upload_status_text(control)
                         } else if uid.with(|u| u.is_some()) && !publish_action.pending().get()
                             && !published.get()
                         {
@@ -559,17 +663,56 @@ pub fn VideoUploader(
                         }
                     }}
                 </p>
+                <Show when=move || uid.with(|u| u.is_none())>
+                    <button
+                        class="self-center py-2 px-6 text-sm font-medium text-pink-300 rounded-lg border border-pink-300"
+                        on:click=move |_| control.paused.update(|p| *p = !*p)
+                    >
+                        {move || if control.paused.get() { "Resume upload" } else { "Pause upload" }}
+                    </button>
+                </Show>
             </div>
         </div>
         <Show when=published>
-            <PostUploadScreen />
+            <PostUploadScreen publish_mode />
         </Show>
     }.into_any()
 }
 
+fn upload_status_text(control: UploadControl) -> String {
+    let progress = control.progress.get();
+    if control.paused.get() {
+        format!("Upload paused ({})", progress.label())
+    } else if let Some(attempt) = control.retry_attempt.get() {
+        format!("Connection lost, retrying (attempt {attempt})...")
+    } else if control.resumed.get() {
+        format!("Resuming upload... {}", progress.label())
+    } else {
+        format!("Uploading video file... {}", progress.label())
+    }
+}
+
 // post as in after not the content post
 #[component]
-fn PostUploadScreen() -> impl IntoView {
+fn PostUploadScreen(publish_mode: PublishMode) -> impl IntoView {
+    let (title, message) = match publish_mode {
+        PublishMode::Now => (
+            "Video uploaded sucessfully",
+            "We're processing your video. It'll be in 'Your Videos' under My Profile soon. Happy scrolling!".to_string(),
+        ),
+        PublishMode::Draft => (
+            "Draft saved",
+            "Your video is saved as a draft, you can publish it any time from My Uploads.".to_string(),
+        ),
+        PublishMode::Schedule { publish_at } => (
+            "Post scheduled",
+            format!(
+                "Your video will be published on {} UTC.",
+                utils::time::parse_ns_to_datetime(publish_at * 1_000_000_000).unwrap_or_default(),
+            ),
+        ),
+    };
+
     view! {
         <div
             style="background: radial-gradient(circle, rgba(0,0,0,0) 0%, rgba(0,0,0,0) 75%, rgba(50,0,28,0.5) 100%);"
@@ -583,11 +726,12 @@ fn PostUploadScreen() -> impl IntoView {
             <div class="flex z-50 flex-col items-center">
                 <img src="/img/common/coins/sucess-coin.png" width=170 class="mb-6 z-300" />
 
-                <h1 class="mb-2 text-lg font-semibold">Video uploaded sucessfully</h1>
+                <h1 class="mb-2 text-lg font-semibold">{title}</h1>
 
-                <p class="px-4 mb-8 text-center">
-                    "We're processing your video. It'll be in 'Your Videos' under My Profile soon. Happy scrolling!"
-                </p>
+                <p class="px-4 mb-8 text-center">{message}</p>
+                <a href="/my-uploads" class="mb-4 text-sm text-pink-300 underline">
+                    Track processing status
+                </a>
                 <HighlightedLinkButton
                     alt_style=false
                     disabled=false
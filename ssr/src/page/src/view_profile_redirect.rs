@@ -2,6 +2,7 @@ use component::spinner::FullScreenSpinner;
 use leptos::prelude::*;
 use leptos_router::components::Redirect;
 use state::canisters::auth_state;
+use utils::app_error::error_path;
 
 #[component]
 pub fn ProfileInfo() -> impl IntoView {
@@ -13,7 +14,7 @@ pub fn ProfileInfo() -> impl IntoView {
                     .get()
                     .map(|res| match res {
                         Ok(user_principal) => view! { <Redirect path=user_principal.to_text() /> },
-                        Err(e) => view! { <Redirect path=format!("/error?err={e}") /> },
+                        Err(e) => view! { <Redirect path=error_path(e) /> },
                     })
             }}
         </Suspense>
@@ -33,16 +33,21 @@ pub enum AirdropStatus {
     WaitFor(web_time::Duration),
 }
 
-pub async fn is_airdrop_claimed(user_principal: Principal) -> Result<bool, ServerFnError> {
+/// Epoch millis of the user's last sats airdrop claim, `None` if they never claimed one
+pub async fn last_airdrop_claimed_at(
+    user_principal: Principal,
+) -> Result<Option<u64>, ServerFnError> {
     let req_url: Url = WORKER_URL.parse().expect("url to be valid");
     let req_url = req_url
         .join(&format!("/last_airdrop_claimed_at/{user_principal}"))
         .expect("url to be valid");
 
-    let response: Option<u64> = reqwest::get(req_url).await?.json().await?;
+    Ok(reqwest::get(req_url).await?.json().await?)
+}
 
+pub async fn is_airdrop_claimed(user_principal: Principal) -> Result<bool, ServerFnError> {
     // user has never claimed airdrop before
-    let Some(last_airdrop_timestamp) = response else {
+    let Some(last_airdrop_timestamp) = last_airdrop_claimed_at(user_principal).await? else {
         return Ok(false);
     };
     let last_airdrop_timestamp: u128 = last_airdrop_timestamp.into();
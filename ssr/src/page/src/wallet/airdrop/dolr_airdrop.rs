@@ -31,3 +31,27 @@ pub async fn claim_dolr_airdrop(
 
     call(user_canister, user_principal).await
 }
+
+/// Removes the user's claim history, part of account deletion
+#[cfg(feature = "ssr")]
+pub async fn delete_airdrop_records(user_principal: Principal) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "dolr-airdrop"))]
+    use mock::delete_airdrop_records as call;
+    #[cfg(feature = "dolr-airdrop")]
+    use real::delete_airdrop_records as call;
+
+    call(user_principal).await
+}
+
+/// Epoch millis of the user's last DOLR airdrop claim
+#[cfg(feature = "ssr")]
+pub async fn last_airdrop_claimed_at(
+    user_principal: Principal,
+) -> Result<Option<u64>, ServerFnError> {
+    #[cfg(not(feature = "dolr-airdrop"))]
+    use mock::last_airdrop_claimed_at as call;
+    #[cfg(feature = "dolr-airdrop")]
+    use real::last_airdrop_claimed_at as call;
+
+    call(user_principal).await
+}
@@ -17,3 +17,15 @@ pub async fn claim_dolr_airdrop(
 ) -> Result<u64, ServerFnError> {
     Ok(0)
 }
+
+#[cfg(feature = "ssr")]
+pub async fn delete_airdrop_records(_user_principal: Principal) -> Result<(), ServerFnError> {
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn last_airdrop_claimed_at(
+    _user_principal: Principal,
+) -> Result<Option<u64>, ServerFnError> {
+    Ok(None)
+}
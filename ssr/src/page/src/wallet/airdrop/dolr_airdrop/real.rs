@@ -168,6 +168,26 @@ async fn mark_airdrop_claimed(
     Ok(())
 }
 
+pub async fn last_airdrop_claimed_at(
+    user_principal: Principal,
+) -> Result<Option<u64>, ServerFnError> {
+    let DolrAirdrop(db) = expect_context();
+    let airdrop_data = dolr_airdrop_data::Entity::find_by_id(user_principal.to_text())
+        .one(&db)
+        .await
+        .map_err(ServerFnError::new)?;
+    Ok(airdrop_data.map(|data| data.last_airdrop_at.and_utc().timestamp_millis() as u64))
+}
+
+pub async fn delete_airdrop_records(user_principal: Principal) -> Result<(), ServerFnError> {
+    let DolrAirdrop(db) = expect_context();
+    dolr_airdrop_data::Entity::delete_by_id(user_principal.to_text())
+        .exec(&db)
+        .await
+        .map_err(ServerFnError::new)?;
+    Ok(())
+}
+
 #[server(input = server_fn::codec::Json)]
 pub async fn claim_dolr_airdrop(
     user_canister: Principal,
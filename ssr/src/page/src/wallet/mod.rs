@@ -23,6 +23,7 @@ use leptos_use::use_event_listener;
 use state::app_state::AppState;
 use state::canisters::{auth_state, unauth_canisters};
 use tokens::TokenList;
+use utils::app_error::error_path;
 use utils::notifications::get_device_registeration_token;
 use utils::send_wrap;
 use yral_canisters_common::utils::profile::ProfileDetails;
@@ -167,7 +168,7 @@ pub fn Wallet() -> impl IntoView {
                                                 }
                                             }
                                             Err(e) => {
-                                                view! { <Redirect path=format!("/error?err={e}") /> }
+                                                view! { <Redirect path=error_path(e) /> }
                                             }
                                         })
                                 }}
@@ -230,7 +231,7 @@ pub fn WalletImpl(principal: Principal) -> impl IntoView {
                             )
                         }
                         Err(e) => {
-                            Either::Right(view! { <Redirect path=format!("/error?err={e}") /> })
+                            Either::Right(view! { <Redirect path=error_path(e) /> })
                         }
                     }
                 })}
@@ -254,7 +255,7 @@ pub fn WalletImpl(principal: Principal) -> impl IntoView {
                                 )
                             }
                             Err(e) => {
-                                Either::Right(view! { <Redirect path=format!("/error?err={e}") /> })
+                                Either::Right(view! { <Redirect path=error_path(e) /> })
                             }
                         }
                     })}
@@ -277,7 +278,7 @@ pub fn WalletImpl(principal: Principal) -> impl IntoView {
                                 )
                             }
                             Err(e) => {
-                                Either::Right(view! { <Redirect path=format!("/error?err={e}") /> })
+                                Either::Right(view! { <Redirect path=error_path(e) /> })
                             }
                         }
                     })}
@@ -25,7 +25,7 @@ use crate::wallet::airdrop::dolr_airdrop::{claim_dolr_airdrop, is_user_eligible_
 use crate::wallet::airdrop::{
     claim_sats_airdrop, AirdropClaimState, AirdropStatus, SatsAirdropPopup, StatefulAirdropPopup,
 };
-use candid::Principal;
+use candid::{Nat, Principal};
 use component::action_btn::{ActionButton, ActionButtonLink};
 use component::icons::information_icon::Information;
 use component::icons::padlock_icon::{PadlockClose, PadlockOpen};
@@ -45,6 +45,7 @@ use leptos::prelude::*;
 use leptos_icons::*;
 use leptos_router::hooks::use_navigate;
 use leptos_use::{use_interval, UseIntervalReturn};
+use state::balance_store::{BalanceKey, BalanceStore};
 use state::canisters::{auth_state, unauth_canisters};
 use utils::host::get_host;
 use utils::mixpanel::mixpanel_events::*;
@@ -278,22 +279,48 @@ impl From<TokenType> for BalanceFetcherType {
     }
 }
 
+impl From<TokenType> for BalanceKey {
+    fn from(value: TokenType) -> Self {
+        match BalanceFetcherType::from(value) {
+            BalanceFetcherType::Icrc1 { ledger, .. } => Self::Icrc1 { ledger },
+            BalanceFetcherType::Sats => Self::Sats,
+            BalanceFetcherType::Cents => Self::Cents,
+        }
+    }
+}
+
 impl TokenType {
     /// Whether the token is maintained artifically by our platform, unlike
     /// icrc1/2 tokens. For example, `Sats` and `Cents`
     fn is_utility_token(&self) -> bool {
         matches!(self, Self::Sats | Self::Cents)
     }
+
+    fn decimals(&self) -> u8 {
+        match BalanceFetcherType::from(*self) {
+            BalanceFetcherType::Icrc1 { decimals, .. } => decimals,
+            BalanceFetcherType::Sats => 0,
+            BalanceFetcherType::Cents => 6,
+        }
+    }
 }
 
 #[component]
 pub fn TokenList(user_principal: Principal, user_canister: Principal) -> impl IntoView {
-    let balance = |token_type: TokenType| {
+    let balance_store = BalanceStore::use_store();
+    // refetched whenever the balance is updated optimistically,
+    // the fetched balance is then reconciled with the store
+    let balance = move |token_type: TokenType| {
+        let key: BalanceKey = token_type.into();
         Resource::new(
-            || (),
-            move |_| async move {
+            move || balance_store.version(key),
+            move |version| async move {
                 let fetcher: BalanceFetcherType = token_type.into();
-                send_wrap(fetcher.fetch(unauth_canisters(), user_canister, user_principal)).await
+                let balance =
+                    send_wrap(fetcher.fetch(unauth_canisters(), user_canister, user_principal))
+                        .await?;
+                balance_store.reconcile(key, version, balance.clone());
+                Ok::<_, ServerFnError>(balance)
             },
         )
     };
@@ -819,6 +846,9 @@ pub fn FastWalletCard(
 
     let auth = auth_state();
     let base = unauth_canisters();
+    let balance_store = BalanceStore::use_store();
+    let balance_key: BalanceKey = token_type.into();
+    let live_balance = balance_store.balance(balance_key);
     let show_login = use_context()
         .map(|ShowLoginSignal(show_login)| show_login)
         .unwrap_or_else(|| RwSignal::new(false));
@@ -828,6 +858,7 @@ pub fn FastWalletCard(
         let airdrop_amount_claimed = airdrop_amount_claimed;
         let error_claiming_airdrop = error_claiming_airdrop;
         let airdropper = airdropper_c2.clone();
+        let decimals = token_type.decimals();
         let token_type: StakeType = token_type.into();
         async move {
             if !is_connected {
@@ -863,7 +894,10 @@ pub fn FastWalletCard(
                     });
                     is_airdrop_claimed.set(true);
                     error_claiming_airdrop.set(false);
-                    balance.refetch();
+                    balance_store.credit(
+                        balance_key,
+                        Nat::from(amount) * Nat(Nat::from(10u32).0.pow(decimals as u32)),
+                    );
                     airdrop_status.refetch();
                     Ok(amount)
                 }
@@ -939,9 +973,14 @@ pub fn FastWalletCard(
                                         log::error!("balance loading error: {err:?}");
                                     })
                                     .ok();
-                                let bal = bal.map(|b| b.humanize_float_truncate_to_dp(8));
-                                let err = bal.is_none();
-                                let text = bal.unwrap_or_else(|| "err".into());
+                                let bal = Signal::derive(move || {
+                                    live_balance
+                                        .get()
+                                        .or_else(|| bal.clone())
+                                        .map(|b| b.humanize_float_truncate_to_dp(8))
+                                });
+                                let err = move || bal().is_none();
+                                let text = move || bal().unwrap_or_else(|| "err".into());
                                 view! {
                                     // show error text if balance fails to load for whatever reason
                                     // error logs are captured by sentry
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use candid::{Nat, Principal};
+use leptos::prelude::*;
+use yral_canisters_common::utils::token::balance::TokenBalance;
+
+/// Identifies a balance tracked by [BalanceStore]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BalanceKey {
+    Sats,
+    Cents,
+    Icrc1 { ledger: Principal },
+}
+
+#[derive(Clone, Debug)]
+struct BalanceEntry {
+    /// last balance reported by the worker or the ledger
+    confirmed: Option<Nat>,
+    decimals: u8,
+    /// optimistic changes which are yet to be confirmed
+    pending_credit: Nat,
+    pending_debit: Nat,
+    /// bumped on every optimistic change, fetches started before
+    /// the latest bump are stale and are discarded
+    version: u64,
+}
+
+impl BalanceEntry {
+    fn new(decimals: u8) -> Self {
+        Self {
+            confirmed: None,
+            decimals,
+            pending_credit: 0u32.into(),
+            pending_debit: 0u32.into(),
+            version: 0,
+        }
+    }
+
+    fn current(&self) -> Option<TokenBalance> {
+        let confirmed = self.confirmed.clone()?;
+        let credited = confirmed + self.pending_credit.clone();
+        let bal = if credited > self.pending_debit {
+            credited - self.pending_debit.clone()
+        } else {
+            0u32.into()
+        };
+        Some(TokenBalance::new(bal, self.decimals))
+    }
+
+    fn confirm(&mut self, balance: TokenBalance) {
+        self.confirmed = Some(balance.e8s);
+        self.decimals = balance.decimals;
+        self.pending_credit = 0u32.into();
+        self.pending_debit = 0u32.into();
+    }
+}
+
+/// Single source of truth for the user's balances across all pages
+///
+/// Bets, airdrops, withdrawals and transfers update the store optimistically
+/// and the store is then reconciled with the balance reported by the
+/// worker or the ledger
+#[derive(Clone, Copy)]
+pub struct BalanceStore {
+    entries: RwSignal<HashMap<BalanceKey, BalanceEntry>>,
+}
+
+impl BalanceStore {
+    /// Provided once at the root so every page shares the same balances
+    pub fn init() -> Self {
+        let this = Self {
+            entries: RwSignal::new(HashMap::new()),
+        };
+        provide_context(this);
+        this
+    }
+
+    pub fn use_store() -> Self {
+        expect_context()
+    }
+
+    /// Current balance, including optimistic changes
+    /// returns None if the balance was never loaded
+    pub fn balance(&self, key: BalanceKey) -> Signal<Option<TokenBalance>> {
+        let entries = self.entries;
+        Signal::derive(move || entries.with(|e| e.get(&key).and_then(|e| e.current())))
+    }
+
+    /// Changes whenever the balance should be re-fetched from the source of truth
+    /// use this as the source of resources loading the balance
+    pub fn version(&self, key: BalanceKey) -> u64 {
+        self.entries
+            .with(|e| e.get(&key).map(|e| e.version))
+            .unwrap_or_default()
+    }
+
+    /// Sets the balance reported by the worker or the ledger,
+    /// discarding any optimistic changes
+    pub fn confirm(&self, key: BalanceKey, balance: TokenBalance) {
+        self.entries.update(|e| {
+            e.entry(key)
+                .or_insert_with(|| BalanceEntry::new(balance.decimals))
+                .confirm(balance)
+        });
+    }
+
+    /// Reconcile with a balance fetched in the background
+    /// the balance is ignored if it was fetched before the latest optimistic change,
+    /// i.e `fetched_at` is not the current [BalanceStore::version]
+    pub fn reconcile(&self, key: BalanceKey, fetched_at: u64, balance: TokenBalance) {
+        self.entries.update(|e| {
+            let entry = e
+                .entry(key)
+                .or_insert_with(|| BalanceEntry::new(balance.decimals));
+            if entry.version == fetched_at {
+                entry.confirm(balance);
+            }
+        });
+    }
+
+    /// Optimistically increase the balance, triggers reconciliation
+    pub fn credit(&self, key: BalanceKey, amount: impl Into<Nat>) {
+        let amount = amount.into();
+        self.entries.update(|e| {
+            let entry = e.entry(key).or_insert_with(|| BalanceEntry::new(0));
+            entry.pending_credit += amount;
+            entry.version += 1;
+        });
+    }
+
+    /// Optimistically decrease the balance, triggers reconciliation
+    pub fn debit(&self, key: BalanceKey, amount: impl Into<Nat>) {
+        let amount = amount.into();
+        self.entries.update(|e| {
+            let entry = e.entry(key).or_insert_with(|| BalanceEntry::new(0));
+            entry.pending_debit += amount;
+            entry.version += 1;
+        });
+    }
+
+    /// Force reconciliation without changing the balance
+    pub fn invalidate(&self, key: BalanceKey) {
+        self.entries.update(|e| {
+            if let Some(entry) = e.get_mut(&key) {
+                entry.version += 1;
+            }
+        });
+    }
+}
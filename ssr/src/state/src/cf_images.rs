@@ -0,0 +1,70 @@
+//! Cloudflare Images direct creator uploads
+//!
+//! Built from the credentials of the app's `gob_cloudflare` client, the
+//! browser uploads the image straight to the returned one-time url.
+
+use consts::{CF_BASE_URL, CF_IMAGES_DELIVERY_BASE};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use utils::http_client::HttpClientError;
+
+#[derive(Clone, Debug)]
+pub struct CfImagesClient {
+    client: Client,
+    token: String,
+    account_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DirectUpload {
+    pub id: String,
+    #[serde(rename = "uploadURL")]
+    pub upload_url: String,
+}
+
+#[derive(Deserialize)]
+struct CfResponse<T> {
+    success: bool,
+    result: Option<T>,
+    #[serde(default)]
+    errors: Vec<serde_json::Value>,
+}
+
+impl CfImagesClient {
+    pub fn new(creds: &gob_cloudflare::Credentials) -> Self {
+        Self {
+            client: Default::default(),
+            token: creds.token.clone(),
+            account_id: creds.account_id.clone(),
+        }
+    }
+
+    /// Creates a one-time upload url for a single image
+    pub async fn direct_upload(&self) -> Result<DirectUpload, HttpClientError> {
+        let url = CF_BASE_URL
+            .join(&format!(
+                "accounts/{}/images/v2/direct_upload",
+                self.account_id
+            ))
+            .expect("url join error");
+        let res = self
+            .client
+            .post(url)
+            .bearer_auth(&self.token)
+            .send()
+            .await?;
+        let res: CfResponse<DirectUpload> = HttpClientError::check(res).await?.json().await?;
+        match res.result {
+            Some(upload) if res.success => Ok(upload),
+            _ => Err(HttpClientError::Decode(format!(
+                "direct upload failed: {:?}",
+                res.errors
+            ))),
+        }
+    }
+
+    /// Public url of an uploaded image
+    pub fn delivery_url(image_id: &str) -> String {
+        format!("{CF_IMAGES_DELIVERY_BASE}/{image_id}/public")
+    }
+}
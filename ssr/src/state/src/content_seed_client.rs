@@ -3,9 +3,9 @@ use reqwest::Client;
 use reqwest::Url;
 use serde::Deserialize;
 use serde::Serialize;
-use std::error::Error;
 
 use consts::DOWNLOAD_UPLOAD_SERVICE;
+use utils::http_client::HttpClientError;
 use yral_types::delegated_identity::DelegatedIdentityWire;
 
 #[derive(Deserialize)]
@@ -19,9 +19,42 @@ pub struct UploadContentPayload {
     payload: DelegatedIdentityWire,
 }
 
-#[derive(Deserialize)]
-pub struct ServerError {
-    error: String,
+#[derive(Deserialize, Default)]
+struct UploadContentRes {
+    #[serde(default)]
+    job_id: Option<String>,
+}
+
+/// Status of an import job in the download-upload service
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ImportJobStatus {
+    Queued,
+    Downloading,
+    Uploading,
+    Completed {
+        #[serde(default)]
+        video_uid: Option<String>,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+impl ImportJobStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed { .. } | Self::Failed { .. })
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Queued => "Queued",
+            Self::Downloading => "Downloading",
+            Self::Uploading => "Uploading",
+            Self::Completed { .. } => "Imported",
+            Self::Failed { .. } => "Failed",
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -47,7 +80,7 @@ impl ContentSeedClient {
         }
     }
 
-    pub async fn check_if_authorized(&self, principal: Principal) -> Result<bool, Box<dyn Error>> {
+    pub async fn check_if_authorized(&self, principal: Principal) -> Result<bool, HttpClientError> {
         let api_url = self
             .base_url
             .join("allowed/")
@@ -55,15 +88,16 @@ impl ContentSeedClient {
             .join(&principal.to_string())
             .expect("url error");
         let res = self.client.get(api_url).send().await?;
-        let res_json: AllowPrincpalRes = res.json().await?;
+        let res_json: AllowPrincpalRes = HttpClientError::check(res).await?.json().await?;
         Ok(res_json.allowed)
     }
 
+    /// Submits `url` for import, returns the job id if the service tracks the import
     pub async fn upload_content(
         &self,
         url: String,
         identity: DelegatedIdentityWire,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<Option<String>, HttpClientError> {
         let api_url = self
             .base_url
             .join("upload-leptos/")
@@ -73,10 +107,21 @@ impl ContentSeedClient {
             payload: identity,
         };
         let res = self.client.post(api_url).json(&req_body).send().await?;
-        if res.status().is_success() {
-            return Ok(());
-        }
-        let ServerError { error } = res.json().await?;
-        Err(error.into())
+        let res = HttpClientError::check(res).await?;
+        // older deployments respond without a body
+        let res: UploadContentRes = res.json().await.unwrap_or_default();
+        Ok(res.job_id)
+    }
+
+    /// Deployments without job tracking respond with a 404
+    pub async fn import_status(&self, job_id: &str) -> Result<ImportJobStatus, HttpClientError> {
+        let api_url = self
+            .base_url
+            .join("status/")
+            .expect("url join error")
+            .join(job_id)
+            .map_err(|e| HttpClientError::Decode(e.to_string()))?;
+        let res = self.client.get(api_url).send().await?;
+        Ok(HttpClientError::check(res).await?.json().await?)
     }
 }
@@ -10,6 +10,18 @@ pub struct VideoComparisonResult {
     pub previous_video_score: f32,
 }
 
+/// A single hot or not comparison, persisted per user
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct VideoComparisonRecord {
+    /// uid of the video that was voted on
+    pub video_uid: String,
+    pub result: VideoComparisonResult,
+    /// uid of the video this video was compared against
+    pub previous_video_uid: Option<String>,
+    /// epoch secs
+    pub compared_at: u64,
+}
+
 #[derive(Default, Clone)]
 pub struct HnBetState {
     state: RwSignal<BTreeMap<String, VideoComparisonResult>>,
@@ -35,6 +47,11 @@ impl HnBetState {
             state.insert(video_uid, result);
         });
     }
+
+    /// Forgets every cached result, used when the account is deleted
+    pub fn clear(&self) {
+        self.state.set(BTreeMap::new());
+    }
 }
 
 impl VideoComparisonResult {
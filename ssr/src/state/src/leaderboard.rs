@@ -0,0 +1,167 @@
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use yral_canisters_common::cursored_data::KeyedData;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LeaderboardPeriod {
+    Daily,
+    Weekly,
+    AllTime,
+}
+
+impl LeaderboardPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+            Self::AllTime => "all_time",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Daily => "Daily",
+            Self::Weekly => "Weekly",
+            Self::AllTime => "All Time",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LeaderboardKind {
+    /// Ranked by SATS won in Hot or Not games
+    Winners,
+    /// Ranked by SATS earned as creator rewards
+    Creators,
+}
+
+impl LeaderboardKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Winners => "winners",
+            Self::Creators => "creators",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Winners => "Top Winners",
+            Self::Creators => "Top Creators",
+        }
+    }
+}
+
+/// Name and avatar shown next to a rank
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LeaderboardProfile {
+    pub display_name: String,
+    pub profile_pic: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LeaderboardEntry {
+    /// 1-indexed rank
+    pub rank: u64,
+    pub principal: Principal,
+    /// in SATS
+    pub amount: u64,
+    /// Filled in per page, `None` if the user's profile couldn't be loaded
+    #[serde(default)]
+    pub profile: Option<LeaderboardProfile>,
+}
+
+impl KeyedData for LeaderboardEntry {
+    type Key = u64;
+
+    fn key(&self) -> Self::Key {
+        self.rank
+    }
+}
+
+#[cfg(feature = "ssr")]
+mod cache {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, RwLock},
+    };
+
+    use web_time::{Duration, Instant};
+
+    use super::*;
+
+    /// How long a computed leaderboard is served before it is recomputed
+    pub const LEADERBOARD_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+    type CacheKey = (LeaderboardPeriod, LeaderboardKind);
+    type CacheValue = (Instant, Arc<Vec<LeaderboardEntry>>);
+    type ProfileCacheValue = (Instant, Option<LeaderboardProfile>);
+
+    /// Server side cache for computed leaderboards
+    /// leaderboards are expensive to compute, so they are only recomputed
+    /// once every [LEADERBOARD_CACHE_TTL]
+    #[derive(Clone)]
+    pub struct LeaderboardCache {
+        entries: Arc<RwLock<HashMap<CacheKey, CacheValue>>>,
+        /// Profiles of ranked users, shared by every board
+        profiles: Arc<RwLock<HashMap<Principal, ProfileCacheValue>>>,
+        ttl: Duration,
+    }
+
+    impl Default for LeaderboardCache {
+        fn default() -> Self {
+            Self::new(LEADERBOARD_CACHE_TTL)
+        }
+    }
+
+    impl LeaderboardCache {
+        pub fn new(ttl: Duration) -> Self {
+            Self {
+                entries: Default::default(),
+                profiles: Default::default(),
+                ttl,
+            }
+        }
+
+        pub fn get(
+            &self,
+            period: LeaderboardPeriod,
+            kind: LeaderboardKind,
+        ) -> Option<Arc<Vec<LeaderboardEntry>>> {
+            let entries = self.entries.read().unwrap();
+            let (computed_at, board) = entries.get(&(period, kind))?;
+            if computed_at.elapsed() > self.ttl {
+                return None;
+            }
+            Some(board.clone())
+        }
+
+        pub fn insert(
+            &self,
+            period: LeaderboardPeriod,
+            kind: LeaderboardKind,
+            board: Arc<Vec<LeaderboardEntry>>,
+        ) {
+            let mut entries = self.entries.write().unwrap();
+            entries.insert((period, kind), (Instant::now(), board));
+        }
+
+        /// `None` if the profile isn't cached, `Some(None)` if it failed to load recently
+        pub fn get_profile(&self, principal: Principal) -> Option<Option<LeaderboardProfile>> {
+            let profiles = self.profiles.read().unwrap();
+            let (fetched_at, profile) = profiles.get(&principal)?;
+            if fetched_at.elapsed() > self.ttl {
+                return None;
+            }
+            Some(profile.clone())
+        }
+
+        pub fn insert_profile(&self, principal: Principal, profile: Option<LeaderboardProfile>) {
+            let mut profiles = self.profiles.write().unwrap();
+            profiles.retain(|_, (fetched_at, _)| fetched_at.elapsed() <= self.ttl);
+            profiles.insert(principal, (Instant::now(), profile));
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub use cache::*;
@@ -5,9 +5,13 @@ pub mod alloydb;
 pub mod app_state;
 pub mod app_type;
 pub mod audio_state;
+pub mod balance_store;
 pub mod canisters;
+#[cfg(all(feature = "ssr", feature = "cloudflare"))]
+pub mod cf_images;
 pub mod content_seed_client;
 pub mod hn_bet_state;
+pub mod leaderboard;
 
 #[cfg(not(feature = "ssr"))]
 pub mod server {
@@ -30,6 +34,16 @@ pub mod server {
     #[derive(Clone)]
     pub struct HonWorkerJwt(pub std::sync::Arc<String>);
 
+    /// Principals allowed to use the `/admin` pages
+    #[derive(Clone, Default, Debug)]
+    pub struct AdminAllowlist(pub std::sync::Arc<std::collections::HashSet<candid::Principal>>);
+
+    impl AdminAllowlist {
+        pub fn contains(&self, principal: candid::Principal) -> bool {
+            self.0.contains(&principal)
+        }
+    }
+
     #[derive(FromRef, Clone)]
     pub struct AppState {
         pub leptos_options: LeptosOptions,
@@ -38,7 +52,10 @@ pub mod server {
         pub admin_canisters: super::admin_canisters::AdminCanisters,
         #[cfg(feature = "cloudflare")]
         pub cloudflare: gob_cloudflare::CloudflareAuth,
+        #[cfg(feature = "cloudflare")]
+        pub cf_images: super::cf_images::CfImagesClient,
         pub kv: KVStoreImpl,
+        pub analytics_outbox: utils::analytics::outbox::AnalyticsOutbox,
         pub routes: Vec<AxumRouteListing>,
         pub cookie_key: Key,
         #[cfg(feature = "oauth-ssr")]
@@ -55,5 +72,7 @@ pub mod server {
         pub hon_worker_jwt: HonWorkerJwt,
         #[cfg(feature = "dolr-airdrop")]
         pub dolr_airdrop_db: dolr_airdrop::db::DolrAirdrop,
+        pub leaderboard_cache: super::leaderboard::LeaderboardCache,
+        pub admin_allowlist: AdminAllowlist,
     }
 }
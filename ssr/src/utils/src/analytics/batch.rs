@@ -0,0 +1,318 @@
+//! Client-side batching of video watch events
+//!
+//! Started, viewed and watch duration events fire over and over on a
+//! fast-swiping feed. They're deduplicated per [`WatchSession`], buffered in
+//! the browser and sent to [`track_events_batch`] on an interval, once the
+//! buffer fills up and through `navigator.sendBeacon` when the page is hidden
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{AnalyticsContext, AnalyticsEvent};
+
+/// Most events accepted in a single batch request
+pub const MAX_BATCH_EVENTS: usize = 200;
+/// Buffered events are sent at least this often
+pub const WATCH_FLUSH_INTERVAL_MS: u64 = 10_000;
+/// Buffer size that triggers an early flush, keeps beacons well under the browser's size limit
+const WATCH_FLUSH_THRESHOLD: usize = 50;
+/// Sessions whose dedupe state is kept around
+const MAX_TRACKED_SESSIONS: usize = 256;
+
+/// One viewing of a post, from when it comes into view until it's swiped away
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WatchSession(u64);
+
+impl WatchSession {
+    pub fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// An event as its sink would have sent it to the server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "sink", rename_all = "snake_case")]
+pub enum BatchedEvent {
+    /// Props as prepared for the analytics server
+    Mixpanel {
+        props: Value,
+    },
+    Warehouse {
+        event: String,
+        params: Value,
+    },
+}
+
+/// Also the target of `navigator.sendBeacon`, so it takes a plain JSON body
+///
+/// The whole batch goes into the outbox as a single entry
+#[server(endpoint = "track_events_batch", input = server_fn::codec::Json)]
+pub async fn track_events_batch(events: Vec<BatchedEvent>) -> Result<(), ServerFnError> {
+    use super::outbox::{AnalyticsOutbox, OutboxDestination, OutboxEvent};
+    use crate::event_streaming::{send_event_warehouse, with_host};
+    use crate::mixpanel::mixpanel_events::{
+        forward_to_analytics_server, request_client_info, with_client_info,
+    };
+
+    if events.len() > MAX_BATCH_EVENTS {
+        return Err(ServerFnError::new("Too many events in batch"));
+    }
+    if !crate::consent::server::analytics_allowed().await {
+        return Ok(());
+    }
+
+    let Some(outbox) = use_context::<AnalyticsOutbox>() else {
+        for event in events {
+            match event {
+                BatchedEvent::Mixpanel { props } => {
+                    if let Err(e) = forward_to_analytics_server(props).await {
+                        log::warn!("failed to forward batched analytics event: {e}");
+                    }
+                }
+                BatchedEvent::Warehouse { event, params } => {
+                    send_event_warehouse(&event, &params).await
+                }
+            }
+        }
+        return Ok(());
+    };
+
+    let client_info = request_client_info().await;
+    let events = events
+        .into_iter()
+        .map(|event| match event {
+            BatchedEvent::Mixpanel { props } => OutboxEvent {
+                destination: OutboxDestination::Mixpanel,
+                params: with_client_info(props, &client_info),
+            },
+            BatchedEvent::Warehouse { event, params } => OutboxEvent {
+                destination: OutboxDestination::Warehouse { event },
+                params: with_host(params),
+            },
+        })
+        .collect();
+    outbox.enqueue_all(events).await;
+    Ok(())
+}
+
+/// Buffers `event` if it's a watch event, returns `false` for anything else
+///
+/// Events already seen in `session` are dropped, a watch duration event
+/// replaces a smaller one that hasn't been sent yet
+pub fn queue(session: WatchSession, event: &AnalyticsEvent, ctx: &AnalyticsContext) -> bool {
+    #[cfg(feature = "hydrate")]
+    {
+        buffer::queue(session, event, ctx)
+    }
+    #[cfg(not(feature = "hydrate"))]
+    {
+        _ = (session, event, ctx);
+        false
+    }
+}
+
+#[cfg(feature = "hydrate")]
+mod buffer {
+    use std::cell::RefCell;
+
+    use indexmap::IndexMap;
+    use leptos::task::spawn_local;
+    use serde_json::json;
+    use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+    use web_sys::{Blob, BlobPropertyBag, VisibilityState};
+
+    use super::*;
+    use crate::analytics::sink::{MixpanelSink, WarehouseSink};
+    use crate::mixpanel::mixpanel_events::{server_event_props, track_in_sdk};
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum WatchKind {
+        Started,
+        Viewed,
+        Progress,
+    }
+
+    impl WatchKind {
+        fn of(event: &AnalyticsEvent) -> Option<Self> {
+            match event {
+                AnalyticsEvent::VideoStarted(_) => Some(Self::Started),
+                AnalyticsEvent::VideoViewed(_) => Some(Self::Viewed),
+                AnalyticsEvent::VideoDurationWatched { .. } => Some(Self::Progress),
+                _ => None,
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct SessionSeen {
+        started: bool,
+        viewed: bool,
+        /// Highest percentage watched sent or queued so far
+        watched: Option<f64>,
+    }
+
+    struct Queued {
+        session: WatchSession,
+        kind: WatchKind,
+        event: BatchedEvent,
+    }
+
+    #[derive(Default)]
+    struct WatchBuffer {
+        queue: Vec<Queued>,
+        sessions: IndexMap<WatchSession, SessionSeen>,
+        hooks_installed: bool,
+    }
+
+    thread_local! {
+        static BUFFER: RefCell<WatchBuffer> = RefCell::default();
+    }
+
+    impl WatchBuffer {
+        fn accept(
+            &mut self,
+            session: WatchSession,
+            kind: WatchKind,
+            event: &AnalyticsEvent,
+        ) -> bool {
+            if !self.sessions.contains_key(&session) && self.sessions.len() >= MAX_TRACKED_SESSIONS
+            {
+                self.sessions.shift_remove_index(0);
+            }
+            let seen = self.sessions.entry(session).or_default();
+            match (kind, event) {
+                (WatchKind::Started, _) => !std::mem::replace(&mut seen.started, true),
+                (WatchKind::Viewed, _) => !std::mem::replace(&mut seen.viewed, true),
+                (WatchKind::Progress, AnalyticsEvent::VideoDurationWatched { progress, .. }) => {
+                    let pct = progress.percentage_watched;
+                    if seen.watched.is_some_and(|watched| watched >= pct) {
+                        return false;
+                    }
+                    seen.watched = Some(pct);
+                    self.queue
+                        .retain(|q| q.session != session || q.kind != WatchKind::Progress);
+                    true
+                }
+                (WatchKind::Progress, _) => false,
+            }
+        }
+    }
+
+    pub fn queue(session: WatchSession, event: &AnalyticsEvent, ctx: &AnalyticsContext) -> bool {
+        let Some(kind) = WatchKind::of(event) else {
+            return false;
+        };
+        let accepted = BUFFER.with_borrow_mut(|buffer| buffer.accept(session, kind, event));
+        if !accepted {
+            return true;
+        }
+
+        let mut rendered = vec![];
+        if let Some(sink_event) = MixpanelSink::render(event, ctx) {
+            if event.mixpanel_client_side() {
+                track_in_sdk(sink_event.name, &sink_event.props);
+            }
+            rendered.push(BatchedEvent::Mixpanel {
+                props: server_event_props(sink_event.name, sink_event.props),
+            });
+        }
+        if let Some(mut sink_event) = WarehouseSink::render(event, ctx) {
+            sink_event.props["page_location"] = json!(window().location().href().ok());
+            rendered.push(BatchedEvent::Warehouse {
+                event: sink_event.name.to_string(),
+                params: sink_event.props,
+            });
+        }
+
+        let full = BUFFER.with_borrow_mut(|buffer| {
+            install_hooks(buffer);
+            buffer
+                .queue
+                .extend(rendered.into_iter().map(|event| Queued {
+                    session,
+                    kind,
+                    event,
+                }));
+            buffer.queue.len() >= WATCH_FLUSH_THRESHOLD
+        });
+        if full {
+            flush();
+        }
+        true
+    }
+
+    fn take() -> Vec<BatchedEvent> {
+        BUFFER.with_borrow_mut(|buffer| buffer.queue.drain(..).map(|q| q.event).collect())
+    }
+
+    fn flush() {
+        for chunk in take().chunks(MAX_BATCH_EVENTS) {
+            let events = chunk.to_vec();
+            spawn_local(async move {
+                if let Err(e) = track_events_batch(events).await {
+                    leptos::logging::error!("Error sending analytics batch: {e}");
+                }
+            });
+        }
+    }
+
+    fn send_beacon(events: &[BatchedEvent]) -> Result<bool, JsValue> {
+        use leptos::server_fn::ServerFn;
+
+        let body = json!({ "events": events }).to_string();
+        let opts = BlobPropertyBag::new();
+        opts.set_type("application/json");
+        let parts = js_sys::Array::of1(&JsValue::from_str(&body));
+        let blob = Blob::new_with_str_sequence_and_options(&parts, &opts)?;
+        window()
+            .navigator()
+            .send_beacon_with_opt_blob(TrackEventsBatch::PATH, Some(&blob))
+    }
+
+    /// Regular requests may be cancelled while the page goes away, beacons aren't
+    fn flush_beacon() {
+        for chunk in take().chunks(MAX_BATCH_EVENTS) {
+            match send_beacon(chunk) {
+                Ok(true) => continue,
+                // over the browser's beacon quota
+                Ok(false) => {}
+                Err(e) => leptos::logging::error!("Error sending analytics beacon: {e:?}"),
+            }
+            let events = chunk.to_vec();
+            spawn_local(async move {
+                _ = track_events_batch(events).await;
+            });
+        }
+    }
+
+    fn install_hooks(buffer: &mut WatchBuffer) {
+        if std::mem::replace(&mut buffer.hooks_installed, true) {
+            return;
+        }
+
+        set_interval(
+            flush,
+            std::time::Duration::from_millis(WATCH_FLUSH_INTERVAL_MS),
+        );
+
+        let on_visibility_change = Closure::<dyn Fn()>::new(|| {
+            if document().visibility_state() == VisibilityState::Hidden {
+                flush_beacon();
+            }
+        });
+        _ = document().add_event_listener_with_callback(
+            "visibilitychange",
+            on_visibility_change.as_ref().unchecked_ref(),
+        );
+        on_visibility_change.forget();
+
+        let on_page_hide = Closure::<dyn Fn()>::new(flush_beacon);
+        _ = window()
+            .add_event_listener_with_callback("pagehide", on_page_hide.as_ref().unchecked_ref());
+        on_page_hide.forget();
+    }
+}
@@ -0,0 +1,48 @@
+use candid::Principal;
+use codee::string::FromToStringCodec;
+use consts::NSFW_TOGGLE_STORE;
+use leptos::prelude::*;
+use leptos_use::storage::use_local_storage;
+
+use crate::event_streaming::events::EventCtx;
+use crate::mixpanel::mixpanel_events::MixpanelGlobalProps;
+
+/// User and device details attached to every event, whatever the sink
+#[derive(Clone, Debug)]
+pub struct AnalyticsContext {
+    pub principal: Principal,
+    pub canister_id: Principal,
+    pub display_name: Option<String>,
+    pub is_logged_in: bool,
+    pub is_nsfw_enabled: bool,
+    pub device_id: String,
+    pub custom_device_id: String,
+}
+
+impl AnalyticsContext {
+    /// `None` until the user's canisters are loaded
+    pub fn from_ev_ctx(ev_ctx: EventCtx) -> Option<Self> {
+        let user = ev_ctx.user_details()?;
+        let (is_nsfw_enabled, _, _) =
+            use_local_storage::<bool, FromToStringCodec>(NSFW_TOGGLE_STORE);
+
+        Some(Self {
+            principal: user.details.principal,
+            canister_id: user.canister_id,
+            display_name: user.details.display_name,
+            is_logged_in: ev_ctx.is_connected(),
+            is_nsfw_enabled: is_nsfw_enabled.get_untracked(),
+            device_id: MixpanelGlobalProps::get_device_id(),
+            custom_device_id: MixpanelGlobalProps::get_custom_device_id(),
+        })
+    }
+
+    pub fn mixpanel_global(&self) -> MixpanelGlobalProps {
+        MixpanelGlobalProps::new(
+            self.principal,
+            self.canister_id,
+            self.is_logged_in,
+            self.is_nsfw_enabled,
+        )
+    }
+}
@@ -0,0 +1,386 @@
+use candid::Principal;
+use serde::Serialize;
+use serde_json::Value;
+use yral_canisters_common::utils::posts::PostDetails;
+
+use crate::mixpanel::mixpanel_events::{
+    ChosenGameOption, GameConclusion, MixpanelPostGameType, MixpanelVideoClickedCTAType, StakeType,
+};
+
+/// The post an event is about
+#[derive(Serialize, Clone, Debug)]
+pub struct VideoProps {
+    pub video_id: String,
+    pub post_id: u64,
+    pub publisher_user_id: Principal,
+    pub publisher_canister_id: Principal,
+    pub hashtag_count: usize,
+    pub is_nsfw: bool,
+    pub nsfw_probability: f32,
+    pub is_hot_or_not: bool,
+    pub view_count: u64,
+    pub like_count: u64,
+}
+
+impl From<&PostDetails> for VideoProps {
+    fn from(post: &PostDetails) -> Self {
+        Self {
+            video_id: post.uid.clone(),
+            post_id: post.post_id,
+            publisher_user_id: post.poster_principal,
+            publisher_canister_id: post.canister_id,
+            hashtag_count: post.hastags.len(),
+            is_nsfw: post.is_nsfw,
+            nsfw_probability: post.nsfw_probability,
+            is_hot_or_not: post.hot_or_not_feed_ranking_score.is_some(),
+            view_count: post.views,
+            like_count: post.likes,
+        }
+    }
+}
+
+impl VideoProps {
+    pub fn with_likes(mut self, likes: u64) -> Self {
+        self.like_count = likes;
+        self
+    }
+}
+
+/// A vote placed on a post
+#[derive(Serialize, Clone, Debug)]
+pub struct GameStake {
+    pub stake_amount: u64,
+    pub stake_type: StakeType,
+    pub option_chosen: ChosenGameOption,
+}
+
+/// How much of a video was watched, in seconds
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct WatchProgress {
+    pub percentage_watched: f64,
+    pub absolute_watched: f64,
+    pub video_duration: f64,
+}
+
+impl WatchProgress {
+    pub fn completed(duration: f64) -> Self {
+        Self {
+            percentage_watched: 100.0,
+            absolute_watched: duration,
+            video_duration: duration,
+        }
+    }
+
+    pub fn paused(current_time: f64, duration: f64) -> Self {
+        Self {
+            percentage_watched: (current_time / duration) * 100.0,
+            absolute_watched: current_time,
+            video_duration: duration,
+        }
+    }
+}
+
+/// Every event sent through [`super::dispatch`]
+///
+/// Adding an event means adding a variant here and its arms in
+/// [`AnalyticsEvent::mixpanel`] and [`AnalyticsEvent::warehouse`],
+/// sinks pick it up from there
+#[derive(Clone, Debug)]
+pub enum AnalyticsEvent {
+    VideoImpression(VideoProps),
+    VideoStarted(VideoProps),
+    VideoViewed(VideoProps),
+    VideoDurationWatched {
+        video: VideoProps,
+        progress: WatchProgress,
+    },
+    VideoClicked {
+        video: VideoProps,
+        cta_type: MixpanelVideoClickedCTAType,
+    },
+    VideoLiked(VideoProps),
+    VideoShared(VideoProps),
+    VideoNotInterested(VideoProps),
+    VideoReported {
+        video: VideoProps,
+        report_reason: String,
+    },
+    /// NSFW content turned on from a post
+    NsfwEnabled(VideoProps),
+    /// A post opened from a profile's grid, `position` is 1 based
+    ProfileVideoClicked {
+        video: VideoProps,
+        position: Option<u64>,
+        is_own_profile: bool,
+    },
+    GameVoted {
+        video: VideoProps,
+        stake: GameStake,
+    },
+    GamePlayed {
+        video: VideoProps,
+        stake: GameStake,
+        conclusion: GameConclusion,
+        won_loss_amount: String,
+        creator_commision_percentage: u64,
+    },
+    HowToPlayClicked {
+        video: VideoProps,
+        stake: GameStake,
+        conclusion: GameConclusion,
+    },
+}
+
+/// Name and properties of an event as a single sink sees it
+pub struct SinkEvent {
+    pub name: &'static str,
+    pub props: Value,
+}
+
+impl SinkEvent {
+    fn new(name: &'static str, props: impl Serialize) -> Option<Self> {
+        match serde_json::to_value(props) {
+            Ok(props) => Some(Self { name, props }),
+            Err(e) => {
+                log::error!("Error serializing {name} properties: {e}");
+                None
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct MixpanelVideo<'a> {
+    #[serde(flatten)]
+    video: &'a VideoProps,
+    game_type: MixpanelPostGameType,
+    is_game_enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cta_type: Option<&'a MixpanelVideoClickedCTAType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    report_reason: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct MixpanelProfileVideo<'a> {
+    #[serde(flatten)]
+    video: MixpanelVideo<'a>,
+    position: Option<u64>,
+    is_own_profile: bool,
+    page_name: &'static str,
+}
+
+#[derive(Serialize)]
+struct MixpanelGame<'a> {
+    #[serde(flatten)]
+    video: MixpanelVideo<'a>,
+    #[serde(flatten)]
+    stake: &'a GameStake,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    conclusion: Option<&'a GameConclusion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    won_loss_amount: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    creator_commision_percentage: Option<u64>,
+}
+
+impl<'a> MixpanelGame<'a> {
+    fn new(video: &'a VideoProps, stake: &'a GameStake) -> Self {
+        Self {
+            video: MixpanelVideo::new(video),
+            stake,
+            conclusion: None,
+            won_loss_amount: None,
+            creator_commision_percentage: None,
+        }
+    }
+}
+
+impl<'a> MixpanelVideo<'a> {
+    fn new(video: &'a VideoProps) -> Self {
+        Self {
+            video,
+            game_type: MixpanelPostGameType::HotOrNot,
+            is_game_enabled: true,
+            cta_type: None,
+            report_reason: None,
+        }
+    }
+
+    fn clicked(video: &'a VideoProps, cta_type: &'a MixpanelVideoClickedCTAType) -> Self {
+        Self {
+            cta_type: Some(cta_type),
+            ..Self::new(video)
+        }
+    }
+}
+
+/// Video properties under the names the warehouse tables use
+#[derive(Serialize)]
+struct WarehouseVideo<'a> {
+    video_id: &'a str,
+    post_id: u64,
+    publisher_user_id: Principal,
+    publisher_canister_id: Principal,
+    hashtag_count: usize,
+    #[serde(rename = "is_NSFW")]
+    is_nsfw: bool,
+    nsfw_probability: f32,
+    #[serde(rename = "is_hotorNot")]
+    is_hot_or_not: bool,
+    view_count: u64,
+    like_count: u64,
+    share_count: u64,
+    video_category: &'static str,
+    creator_category: &'static str,
+    feed_type: &'static str,
+    #[serde(flatten)]
+    progress: Option<WatchProgress>,
+}
+
+#[derive(Serialize)]
+struct WarehouseProfileVideo<'a> {
+    publisher_user_id: Principal,
+    video_id: &'a str,
+    profile_feed: &'static str,
+}
+
+impl<'a> WarehouseVideo<'a> {
+    fn new(video: &'a VideoProps) -> Self {
+        Self {
+            video_id: &video.video_id,
+            post_id: video.post_id,
+            publisher_user_id: video.publisher_user_id,
+            publisher_canister_id: video.publisher_canister_id,
+            hashtag_count: video.hashtag_count,
+            is_nsfw: video.is_nsfw,
+            nsfw_probability: video.nsfw_probability,
+            is_hot_or_not: video.is_hot_or_not,
+            view_count: video.view_count,
+            like_count: video.like_count,
+            share_count: 0,
+            video_category: "NA",
+            creator_category: "NA",
+            feed_type: "NA",
+            progress: None,
+        }
+    }
+}
+
+impl AnalyticsEvent {
+    /// `None` if Mixpanel doesn't track this event
+    pub fn mixpanel(&self) -> Option<SinkEvent> {
+        match self {
+            Self::VideoImpression(video) => {
+                SinkEvent::new("video_impression", MixpanelVideo::new(video))
+            }
+            Self::VideoStarted(video) => SinkEvent::new("video_started", MixpanelVideo::new(video)),
+            Self::VideoViewed(video) => SinkEvent::new("video_viewed", MixpanelVideo::new(video)),
+            Self::VideoClicked { video, cta_type } => {
+                SinkEvent::new("video_clicked", MixpanelVideo::clicked(video, cta_type))
+            }
+            Self::VideoLiked(video) => SinkEvent::new(
+                "video_clicked",
+                MixpanelVideo::clicked(video, &MixpanelVideoClickedCTAType::Like),
+            ),
+            Self::VideoShared(video) => SinkEvent::new(
+                "video_clicked",
+                MixpanelVideo::clicked(video, &MixpanelVideoClickedCTAType::Share),
+            ),
+            Self::VideoReported {
+                video,
+                report_reason,
+            } => SinkEvent::new(
+                "video_reported",
+                MixpanelVideo {
+                    report_reason: Some(report_reason),
+                    ..MixpanelVideo::new(video)
+                },
+            ),
+            Self::NsfwEnabled(video) => SinkEvent::new("nsfw_enabled", MixpanelVideo::new(video)),
+            Self::ProfileVideoClicked {
+                video,
+                position,
+                is_own_profile,
+            } => SinkEvent::new(
+                "video_clicked",
+                MixpanelProfileVideo {
+                    video: MixpanelVideo::clicked(video, &MixpanelVideoClickedCTAType::VideoPlay),
+                    position: *position,
+                    is_own_profile: *is_own_profile,
+                    page_name: "profile",
+                },
+            ),
+            Self::GameVoted { video, stake } => {
+                SinkEvent::new("game_voted", MixpanelGame::new(video, stake))
+            }
+            Self::GamePlayed {
+                video,
+                stake,
+                conclusion,
+                won_loss_amount,
+                creator_commision_percentage,
+            } => SinkEvent::new(
+                "game_played",
+                MixpanelGame {
+                    conclusion: Some(conclusion),
+                    won_loss_amount: Some(won_loss_amount.as_str()),
+                    creator_commision_percentage: Some(*creator_commision_percentage),
+                    ..MixpanelGame::new(video, stake)
+                },
+            ),
+            Self::HowToPlayClicked {
+                video,
+                stake,
+                conclusion,
+            } => SinkEvent::new(
+                "how_to_play_clicked",
+                MixpanelGame {
+                    conclusion: Some(conclusion),
+                    ..MixpanelGame::new(video, stake)
+                },
+            ),
+            Self::VideoDurationWatched { .. } | Self::VideoNotInterested(_) => None,
+        }
+    }
+
+    /// Whether Mixpanel's browser SDK also sees the event, the rest only go through the server
+    pub fn mixpanel_client_side(&self) -> bool {
+        !matches!(self, Self::VideoImpression(_) | Self::VideoReported { .. })
+    }
+
+    /// `None` if the event isn't streamed to the warehouse
+    pub fn warehouse(&self) -> Option<SinkEvent> {
+        match self {
+            Self::VideoViewed(video) => SinkEvent::new("video_viewed", WarehouseVideo::new(video)),
+            Self::VideoDurationWatched { video, progress } => SinkEvent::new(
+                "video_duration_watched",
+                WarehouseVideo {
+                    progress: Some(*progress),
+                    ..WarehouseVideo::new(video)
+                },
+            ),
+            Self::VideoLiked(video) => SinkEvent::new("like_video", WarehouseVideo::new(video)),
+            Self::VideoShared(video) => SinkEvent::new("share_video", WarehouseVideo::new(video)),
+            Self::VideoNotInterested(video) => {
+                SinkEvent::new("video_not_interested", WarehouseVideo::new(video))
+            }
+            Self::ProfileVideoClicked { video, .. } => SinkEvent::new(
+                "profile_view_video",
+                WarehouseProfileVideo {
+                    publisher_user_id: video.publisher_user_id,
+                    video_id: &video.video_id,
+                    profile_feed: "main",
+                },
+            ),
+            Self::VideoImpression(_)
+            | Self::VideoStarted(_)
+            | Self::VideoClicked { .. }
+            | Self::VideoReported { .. }
+            | Self::NsfwEnabled(_)
+            | Self::GameVoted { .. }
+            | Self::GamePlayed { .. }
+            | Self::HowToPlayClicked { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Typed analytics events, fanned out to every configured sink
+//!
+//! Call sites build an [`AnalyticsEvent`] and hand it to [`dispatch`],
+//! sinks decide their own event names and property layout
+//!
+//! Post view, profile grid and game events go through the bus. Page views,
+//! auth, wallet, upload, menu, referral and settings events still call
+//! `MixPanelEvent::track_*` and `event_streaming::events` directly and move
+//! over family by family
+
+#[cfg(feature = "ga4")]
+pub mod batch;
+pub mod context;
+pub mod event;
+#[cfg(feature = "ssr")]
+pub mod outbox;
+pub mod sink;
+
+pub use context::AnalyticsContext;
+pub use event::{AnalyticsEvent, GameStake, VideoProps, WatchProgress};
+pub use sink::AnalyticsSink;
+
+use serde::{Deserialize, Serialize};
+
+use crate::consent;
+use crate::event_streaming::events::EventCtx;
+
+/// Size of the server's analytics outbox in entries, a batch request is one entry
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct OutboxDepth {
+    pub pending: usize,
+    /// Pending events waiting out a retry backoff
+    pub retrying: usize,
+    pub dead_letters: usize,
+    pub oldest_pending_secs: Option<u64>,
+}
+
+fn sinks() -> &'static [&'static dyn AnalyticsSink] {
+    &[
+        #[cfg(feature = "ga4")]
+        &sink::MixpanelSink,
+        #[cfg(feature = "ga4")]
+        &sink::WarehouseSink,
+    ]
+}
+
+/// Sends `event` to every sink, dropped if the user opted out of analytics
+/// or their details aren't loaded yet
+pub fn dispatch(ev_ctx: EventCtx, event: AnalyticsEvent) {
+    if !consent::analytics_allowed() {
+        return;
+    }
+    let Some(ctx) = AnalyticsContext::from_ev_ctx(ev_ctx) else {
+        return;
+    };
+    for sink in sinks() {
+        sink.send(&event, &ctx);
+    }
+}
+
+/// Like [`dispatch`], but video watch events are deduplicated per `session`
+/// and sent in batches
+#[cfg(feature = "ga4")]
+pub fn dispatch_watch(ev_ctx: EventCtx, session: batch::WatchSession, event: AnalyticsEvent) {
+    if !consent::analytics_allowed() {
+        return;
+    }
+    let Some(ctx) = AnalyticsContext::from_ev_ctx(ev_ctx) else {
+        return;
+    };
+    if batch::queue(session, &event, &ctx) {
+        return;
+    }
+    for sink in sinks() {
+        sink.send(&event, &ctx);
+    }
+}
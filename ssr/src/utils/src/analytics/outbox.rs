@@ -0,0 +1,573 @@
+//! Server-side queue for analytics events
+//!
+//! Events are stored as KV entries, one per enqueue call (a batch request
+//! is a single entry), listed in an index of due entries scored by their
+//! next attempt time. Every instance's background task claims due entries by
+//! removing them from that index, so whichever instance is running drains
+//! the shared queue. Failed deliveries are retried with exponential backoff,
+//! events the destination rejects outright (or that keep failing) are
+//! dead-lettered
+
+use std::time::Duration;
+
+use auth::server_impl::store::{KVStore, KVStoreImpl};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use super::OutboxDepth;
+
+pub const OUTBOX_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+/// Events claimed per delivery round of a flush, and sent per Mixpanel batch
+pub const OUTBOX_BATCH_SIZE: usize = 100;
+/// Retries before an entry is dead-lettered
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 8;
+const RETRY_BASE_SECS: u64 = 5;
+const RETRY_MAX_SECS: u64 = 60 * 60;
+/// Oldest entries are dropped beyond this, so a long outage can't grow the queue forever
+pub const MAX_PENDING_ENTRIES: usize = 10_000;
+pub const MAX_DEAD_LETTERS: usize = 1_000;
+/// Claimed entries are handed back to the queue if their instance hasn't
+/// settled them by then, e.g. because it was stopped mid delivery
+const CLAIM_TIMEOUT_SECS: u64 = 5 * 60;
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn entry_key(id: &str) -> String {
+    format!("analytics-outbox-entry:{id}")
+}
+
+/// Ids of queued entries, by enqueue time
+const QUEUED_INDEX: &str = "analytics-outbox-queued";
+/// Ids of queued entries not claimed by an instance, by next attempt time
+const DUE_INDEX: &str = "analytics-outbox-due";
+/// Ids of entries being delivered, by claim time
+const CLAIMED_INDEX: &str = "analytics-outbox-claimed";
+/// Ids of queued entries that failed at least once, by attempts
+const RETRYING_INDEX: &str = "analytics-outbox-retrying";
+/// Ids of dead-lettered entries, by the time they were given up on
+const DEAD_LETTER_INDEX: &str = "analytics-outbox-dead-letters";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OutboxDestination {
+    /// The offchain agent's warehouse stream
+    Warehouse { event: String },
+    /// Mixpanel, through QStash and the analytics server
+    Mixpanel,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OutboxEvent {
+    pub destination: OutboxDestination,
+    pub params: Value,
+}
+
+/// Events enqueued together, retried together until each is delivered
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OutboxEntry {
+    pub id: String,
+    pub events: Vec<OutboxEvent>,
+    pub enqueued_at: u64,
+    pub attempts: u32,
+    pub next_attempt_at: u64,
+    pub last_error: Option<String>,
+}
+
+impl OutboxEntry {
+    fn new(events: Vec<OutboxEvent>) -> Self {
+        let now = now_secs();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            events,
+            enqueued_at: now,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+        }
+    }
+
+    fn backoff_secs(&self) -> u64 {
+        RETRY_BASE_SECS
+            .saturating_mul(1 << self.attempts.min(16))
+            .min(RETRY_MAX_SECS)
+    }
+}
+
+/// Why a delivery failed
+#[derive(Debug)]
+pub enum DeliveryError {
+    Retryable(String),
+    /// Won't succeed on retry, the event is dead-lettered right away
+    Poison(String),
+}
+
+/// Clients the outbox delivers through, `None` when not configured
+#[derive(Clone, Default)]
+pub struct OutboxTargets {
+    #[cfg(feature = "ga4")]
+    pub warehouse: Option<tonic::transport::Channel>,
+    #[cfg(feature = "qstash")]
+    pub qstash: Option<(crate::qstash::QStashClient, String)>,
+}
+
+/// Delivery results are in the same order as the events passed in
+impl OutboxTargets {
+    #[cfg(feature = "ga4")]
+    async fn deliver_warehouse(&self, events: &[&OutboxEvent]) -> Vec<Result<(), DeliveryError>> {
+        use crate::event_streaming::stream_to_offchain_agent;
+        use tonic::Code;
+
+        let Some(channel) = self.warehouse.clone() else {
+            return not_configured(events);
+        };
+        let sends = events.iter().map(|e| {
+            let channel = channel.clone();
+            async move {
+                let OutboxDestination::Warehouse { event } = &e.destination else {
+                    return Err(DeliveryError::Poison("wrong destination".into()));
+                };
+                stream_to_offchain_agent(channel, event.clone(), &e.params)
+                    .await
+                    .map_err(|status| match status.code() {
+                        Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => {
+                            DeliveryError::Poison(status.to_string())
+                        }
+                        _ => DeliveryError::Retryable(status.to_string()),
+                    })
+            }
+        });
+        join_all(sends).await
+    }
+
+    #[cfg(not(feature = "ga4"))]
+    async fn deliver_warehouse(&self, events: &[&OutboxEvent]) -> Vec<Result<(), DeliveryError>> {
+        not_configured(events)
+    }
+
+    /// Sends batches of [`OUTBOX_BATCH_SIZE`], falls back to one request per
+    /// event when a batch is rejected so a single bad event can't hold back
+    /// the rest. Batches failing transiently are retried whole
+    #[cfg(feature = "qstash")]
+    async fn deliver_mixpanel(&self, events: &[&OutboxEvent]) -> Vec<Result<(), DeliveryError>> {
+        let Some((client, token)) = &self.qstash else {
+            return not_configured(events);
+        };
+        let mut results = Vec::with_capacity(events.len());
+        for chunk in events.chunks(OUTBOX_BATCH_SIZE) {
+            let params = chunk.iter().map(|e| e.params.clone()).collect::<Vec<_>>();
+            match client
+                .send_analytics_events_batch_to_qstash(&params, token)
+                .await
+            {
+                Ok(()) => {
+                    results.extend(chunk.iter().map(|_| Ok(())));
+                    continue;
+                }
+                Err(e) if e.is_transient() => {
+                    let e = e.to_string();
+                    results.extend(
+                        chunk
+                            .iter()
+                            .map(|_| Err(DeliveryError::Retryable(e.clone()))),
+                    );
+                    continue;
+                }
+                Err(e) => log::warn!(
+                    "analytics batch of {} rejected, retrying one by one: {e}",
+                    chunk.len()
+                ),
+            }
+
+            for event in chunk {
+                let res = client
+                    .send_analytics_event_to_qstash(event.params.clone(), token.clone())
+                    .await
+                    .map_err(|e| {
+                        if e.is_transient() {
+                            DeliveryError::Retryable(e.to_string())
+                        } else {
+                            DeliveryError::Poison(e.to_string())
+                        }
+                    });
+                results.push(res);
+            }
+        }
+        results
+    }
+
+    #[cfg(not(feature = "qstash"))]
+    async fn deliver_mixpanel(&self, events: &[&OutboxEvent]) -> Vec<Result<(), DeliveryError>> {
+        not_configured(events)
+    }
+}
+
+fn not_configured(events: &[&OutboxEvent]) -> Vec<Result<(), DeliveryError>> {
+    events
+        .iter()
+        .map(|_| Err(DeliveryError::Poison("destination not configured".into())))
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct AnalyticsOutbox {
+    kv: KVStoreImpl,
+}
+
+impl AnalyticsOutbox {
+    pub fn new(kv: KVStoreImpl) -> Self {
+        Self { kv }
+    }
+
+    async fn save(&self, entry: &OutboxEntry) -> Result<(), String> {
+        let raw = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+        self.kv
+            .write(entry_key(&entry.id), raw)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Stores `entry` and queues it for its next attempt
+    async fn push(&self, entry: &OutboxEntry) {
+        let res = async {
+            self.save(entry).await?;
+            self.kv
+                .index_add(
+                    QUEUED_INDEX.into(),
+                    entry.id.clone(),
+                    entry.enqueued_at as f64,
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+            self.kv
+                .index_add(
+                    DUE_INDEX.into(),
+                    entry.id.clone(),
+                    entry.next_attempt_at as f64,
+                )
+                .await
+                .map_err(|e| e.to_string())
+        }
+        .await;
+        if let Err(e) = res {
+            log::error!("failed to queue analytics entry {}: {e}", entry.id);
+        }
+    }
+
+    pub async fn enqueue(&self, destination: OutboxDestination, params: Value) {
+        self.enqueue_all(vec![OutboxEvent {
+            destination,
+            params,
+        }])
+        .await;
+    }
+
+    /// Queues `events` as a single entry, one write however many there are
+    pub async fn enqueue_all(&self, events: Vec<OutboxEvent>) {
+        if events.is_empty() {
+            return;
+        }
+        self.push(&OutboxEntry::new(events)).await;
+    }
+
+    /// Forgets `id` everywhere but the dead letter index
+    async fn unqueue(&self, id: &str) {
+        for index in [QUEUED_INDEX, DUE_INDEX, CLAIMED_INDEX, RETRYING_INDEX] {
+            if let Err(e) = self.kv.index_remove(index.into(), id.to_string()).await {
+                log::warn!("failed to unqueue analytics entry {id}: {e}");
+            }
+        }
+    }
+
+    async fn drop_entries(&self, ids: &[String]) {
+        for id in ids {
+            self.unqueue(id).await;
+            if let Err(e) = self.kv.delete(entry_key(id)).await {
+                log::warn!("failed to delete analytics entry {id}: {e}");
+            }
+        }
+    }
+
+    pub async fn depth(&self) -> OutboxDepth {
+        let len = |index: &'static str| async move {
+            self.kv.index_len(index.into()).await.unwrap_or_else(|e| {
+                log::warn!("failed to read analytics outbox {index}: {e}");
+                0
+            })
+        };
+        let oldest = self
+            .kv
+            .index_range_by_score(QUEUED_INDEX.into(), f64::INFINITY, 1)
+            .await
+            .ok()
+            .and_then(|oldest| oldest.first().map(|(_, enqueued_at)| *enqueued_at as u64));
+        OutboxDepth {
+            pending: len(QUEUED_INDEX).await,
+            retrying: len(RETRYING_INDEX).await,
+            dead_letters: len(DEAD_LETTER_INDEX).await,
+            oldest_pending_secs: oldest.map(|at| now_secs().saturating_sub(at)),
+        }
+    }
+
+    /// Hands entries claimed by an instance that went away back to the queue
+    async fn recover_stale_claims(&self, now: u64) -> Result<(), String> {
+        let stale = self
+            .kv
+            .index_range_by_score(
+                CLAIMED_INDEX.into(),
+                now.saturating_sub(CLAIM_TIMEOUT_SECS) as f64,
+                OUTBOX_BATCH_SIZE,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        for (id, _) in stale {
+            if self
+                .kv
+                .index_remove(CLAIMED_INDEX.into(), id.clone())
+                .await
+                .map_err(|e| e.to_string())?
+            {
+                self.kv
+                    .index_add(DUE_INDEX.into(), id, now as f64)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Takes due entries off the queue until about [`OUTBOX_BATCH_SIZE`]
+    /// events are claimed, removing an id from the due index succeeds on
+    /// one instance only
+    async fn claim_due(&self, now: u64) -> Result<Vec<OutboxEntry>, String> {
+        let due = self
+            .kv
+            .index_range_by_score(DUE_INDEX.into(), now as f64, OUTBOX_BATCH_SIZE)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut claimed = vec![];
+        let mut claimed_events = 0;
+        for (id, _) in due {
+            if claimed_events >= OUTBOX_BATCH_SIZE {
+                break;
+            }
+            if !self
+                .kv
+                .index_remove(DUE_INDEX.into(), id.clone())
+                .await
+                .map_err(|e| e.to_string())?
+            {
+                continue;
+            }
+            self.kv
+                .index_add(CLAIMED_INDEX.into(), id.clone(), now as f64)
+                .await
+                .map_err(|e| e.to_string())?;
+            let raw = self
+                .kv
+                .read(entry_key(&id))
+                .await
+                .map_err(|e| e.to_string())?;
+            match raw.as_deref().map(serde_json::from_str::<OutboxEntry>) {
+                Some(Ok(entry)) => {
+                    claimed_events += entry.events.len();
+                    claimed.push(entry);
+                }
+                Some(Err(e)) => {
+                    log::error!("discarding unreadable analytics entry {id}: {e}");
+                    self.drop_entries(&[id]).await;
+                }
+                None => self.unqueue(&id).await,
+            }
+        }
+        Ok(claimed)
+    }
+
+    async fn dead_letter(&self, entry: &OutboxEntry, now: u64) -> Result<(), String> {
+        log::warn!(
+            "dead-lettering {} analytics events of {} after {} attempts: {}",
+            entry.events.len(),
+            entry.id,
+            entry.attempts,
+            entry.last_error.as_deref().unwrap_or_default()
+        );
+        self.save(entry).await?;
+        self.unqueue(&entry.id).await;
+        self.kv
+            .index_add(DEAD_LETTER_INDEX.into(), entry.id.clone(), now as f64)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Drops delivered events from `entry`, dead-letters rejected ones and
+    /// requeues the rest
+    async fn settle(
+        &self,
+        mut entry: OutboxEntry,
+        results: Vec<Result<(), DeliveryError>>,
+        now: u64,
+    ) -> Result<(), String> {
+        let mut retry = vec![];
+        let mut rejected = vec![];
+        let mut last_error = None;
+        for (event, res) in entry.events.drain(..).zip(results) {
+            match res {
+                Ok(()) => {}
+                Err(DeliveryError::Poison(e)) => {
+                    rejected.push(event);
+                    last_error = Some(e);
+                }
+                Err(DeliveryError::Retryable(e)) => {
+                    retry.push(event);
+                    last_error = Some(e);
+                }
+            }
+        }
+        entry.attempts += 1;
+        entry.last_error = last_error;
+
+        if !rejected.is_empty() {
+            let mut dead = OutboxEntry::new(rejected);
+            dead.attempts = entry.attempts;
+            dead.last_error = entry.last_error.clone();
+            self.dead_letter(&dead, now).await?;
+        }
+        if retry.is_empty() {
+            self.drop_entries(&[entry.id]).await;
+            return Ok(());
+        }
+
+        entry.events = retry;
+        if entry.attempts >= MAX_DELIVERY_ATTEMPTS {
+            return self.dead_letter(&entry, now).await;
+        }
+        entry.next_attempt_at = now + entry.backoff_secs();
+        self.save(&entry).await?;
+        self.kv
+            .index_add(
+                RETRYING_INDEX.into(),
+                entry.id.clone(),
+                entry.attempts as f64,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        self.kv
+            .index_add(
+                DUE_INDEX.into(),
+                entry.id.clone(),
+                entry.next_attempt_at as f64,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        self.kv
+            .index_remove(CLAIMED_INDEX.into(), entry.id)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Drops the oldest queued entries and dead letters beyond their limits
+    async fn trim(&self) -> Result<(), String> {
+        let overflow = self
+            .kv
+            .index_trim(QUEUED_INDEX.into(), MAX_PENDING_ENTRIES)
+            .await
+            .map_err(|e| e.to_string())?;
+        if !overflow.is_empty() {
+            log::warn!(
+                "analytics outbox full, dropping {} oldest entries",
+                overflow.len()
+            );
+            self.drop_entries(&overflow).await;
+        }
+        let expired = self
+            .kv
+            .index_trim(DEAD_LETTER_INDEX.into(), MAX_DEAD_LETTERS)
+            .await
+            .map_err(|e| e.to_string())?;
+        for id in expired {
+            if let Err(e) = self.kv.delete(entry_key(&id)).await {
+                log::warn!("failed to delete analytics entry {id}: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Delivers every entry due by the start of the flush, a batch at a time
+    pub async fn flush(&self, targets: &OutboxTargets) -> Result<(), String> {
+        let now = now_secs();
+        self.trim().await?;
+        self.recover_stale_claims(now).await?;
+        loop {
+            // failed entries are requeued for later, so this runs dry
+            let claimed = self.claim_due(now).await?;
+            if claimed.is_empty() {
+                return Ok(());
+            }
+            self.deliver(targets, claimed, now).await?;
+        }
+    }
+
+    /// Delivers the claimed entries and settles each of them
+    async fn deliver(
+        &self,
+        targets: &OutboxTargets,
+        claimed: Vec<OutboxEntry>,
+        now: u64,
+    ) -> Result<(), String> {
+        // (entry, event) positions of every claimed event, by destination
+        let (warehouse, mixpanel): (Vec<_>, Vec<_>) = claimed
+            .iter()
+            .enumerate()
+            .flat_map(|(i, entry)| entry.events.iter().enumerate().map(move |(j, e)| (i, j, e)))
+            .partition(|(_, _, e)| matches!(e.destination, OutboxDestination::Warehouse { .. }));
+        let events = |batch: &[(usize, usize, &OutboxEvent)]| {
+            batch.iter().map(|(_, _, e)| *e).collect::<Vec<_>>()
+        };
+        let (warehouse_res, mixpanel_res) = futures::join!(
+            targets.deliver_warehouse(&events(&warehouse)),
+            targets.deliver_mixpanel(&events(&mixpanel)),
+        );
+
+        let mut results = claimed
+            .iter()
+            .map(|entry| {
+                entry
+                    .events
+                    .iter()
+                    .map(|_| Err(DeliveryError::Retryable("no delivery result".into())))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for ((i, j, _), res) in warehouse
+            .iter()
+            .chain(&mixpanel)
+            .zip(warehouse_res.into_iter().chain(mixpanel_res))
+        {
+            results[*i][*j] = res;
+        }
+
+        for (entry, results) in claimed.into_iter().zip(results) {
+            self.settle(entry, results, now).await?;
+        }
+        Ok(())
+    }
+
+    /// Background task delivering queued events, runs forever
+    pub async fn run(self, targets: OutboxTargets) {
+        let mut interval = tokio::time::interval(OUTBOX_FLUSH_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.flush(&targets).await {
+                log::warn!("failed to flush analytics outbox: {e}");
+            }
+        }
+    }
+}
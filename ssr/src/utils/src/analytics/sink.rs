@@ -0,0 +1,93 @@
+#[cfg(feature = "ga4")]
+use super::event::SinkEvent;
+use super::{context::AnalyticsContext, event::AnalyticsEvent};
+
+/// A destination for analytics events
+///
+/// Sinks ask the event for their own view of it and skip events they don't track
+pub trait AnalyticsSink: Send + Sync {
+    fn send(&self, event: &AnalyticsEvent, ctx: &AnalyticsContext);
+}
+
+/// Adds `extra`'s fields to `props`, keeping the event's own value on conflicts
+#[cfg(feature = "ga4")]
+fn merge(props: &mut serde_json::Value, extra: serde_json::Value) {
+    use serde_json::Value;
+
+    let (Value::Object(props), Value::Object(extra)) = (props, extra) else {
+        return;
+    };
+    for (key, value) in extra {
+        props.entry(key).or_insert(value);
+    }
+}
+
+#[cfg(feature = "ga4")]
+pub struct MixpanelSink;
+
+#[cfg(feature = "ga4")]
+impl MixpanelSink {
+    /// The event with the user's global props added
+    pub fn render(event: &AnalyticsEvent, ctx: &AnalyticsContext) -> Option<SinkEvent> {
+        let mut sink_event = event.mixpanel()?;
+        merge(
+            &mut sink_event.props,
+            serde_json::to_value(ctx.mixpanel_global()).unwrap_or_default(),
+        );
+        Some(sink_event)
+    }
+}
+
+#[cfg(feature = "ga4")]
+impl AnalyticsSink for MixpanelSink {
+    fn send(&self, event: &AnalyticsEvent, ctx: &AnalyticsContext) {
+        use crate::mixpanel::mixpanel_events::{send_event_to_server, track_event};
+
+        let Some(sink_event) = Self::render(event, ctx) else {
+            return;
+        };
+
+        if event.mixpanel_client_side() {
+            track_event(sink_event.name, sink_event.props);
+        } else {
+            send_event_to_server(sink_event.name, sink_event.props);
+        }
+    }
+}
+
+#[cfg(feature = "ga4")]
+pub struct WarehouseSink;
+
+#[cfg(feature = "ga4")]
+impl WarehouseSink {
+    /// The event with the user's details added
+    pub fn render(event: &AnalyticsEvent, ctx: &AnalyticsContext) -> Option<SinkEvent> {
+        use serde_json::json;
+
+        let mut sink_event = event.warehouse()?;
+        merge(
+            &mut sink_event.props,
+            json!({
+                "user_id": ctx.principal,
+                "is_loggedIn": ctx.is_logged_in,
+                "display_name": ctx.display_name,
+                "canister_id": ctx.canister_id,
+                "device_id": ctx.device_id,
+            }),
+        );
+        Some(sink_event)
+    }
+}
+
+#[cfg(feature = "ga4")]
+impl AnalyticsSink for WarehouseSink {
+    fn send(&self, event: &AnalyticsEvent, ctx: &AnalyticsContext) {
+        use crate::event_streaming::send_event_ssr_spawn;
+
+        let Some(sink_event) = Self::render(event, ctx) else {
+            return;
+        };
+
+        let _ = send_event_ssr_spawn(sink_event.name.to_string(), sink_event.props.to_string());
+    }
+}
@@ -0,0 +1,97 @@
+//! Error codes shown on the `/error` page
+//!
+//! Only the code and an opaque correlation id are put in the url,
+//! the full error is reported to Sentry tagged with the same id.
+
+use std::fmt::Display;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppErrorCode {
+    /// canister, agent or backend service failures
+    ServiceUnavailable,
+    NoPostsFound,
+    NotFound,
+    Unauthorized,
+    Unknown,
+}
+
+impl AppErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ServiceUnavailable => "service_unavailable",
+            Self::NoPostsFound => "no_posts_found",
+            Self::NotFound => "not_found",
+            Self::Unauthorized => "unauthorized",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "service_unavailable" => Self::ServiceUnavailable,
+            "no_posts_found" => Self::NoPostsFound,
+            "not_found" => Self::NotFound,
+            "unauthorized" => Self::Unauthorized,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Best effort classification of errors which don't carry a code
+    pub fn classify(err: &str) -> Self {
+        const SERVICE_ERRORS: &[&str] = &[
+            "IC agent error",
+            "error running server function",
+            "Canister error",
+            "CanisterError",
+            "http fetch error",
+            "ServerError",
+            "TypeError",
+            "Network error",
+        ];
+        if SERVICE_ERRORS.iter().any(|e| err.contains(e)) {
+            Self::ServiceUnavailable
+        } else if err.contains("Not authorized") || err.contains("Unauthorized") {
+            Self::Unauthorized
+        } else if err.contains("not found") || err.contains("Not found") {
+            Self::NotFound
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Copy shown to the user
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::ServiceUnavailable => "It looks like our system is taking a coffee break. Try again in a bit, and we'll have it back to work!",
+            Self::NoPostsFound => "We couldn't find any videos for you right now. Please try again later.",
+            Self::NotFound => "We couldn't find what you were looking for.",
+            Self::Unauthorized => "You don't have access to this. Try logging in again.",
+            Self::Unknown => "Something went wrong. Please try again.",
+        }
+    }
+}
+
+/// Reports `err` and returns the path of the error page for it
+pub fn report_error(code: AppErrorCode, err: impl Display) -> String {
+    let correlation_id = uuid::Uuid::new_v4().simple().to_string();
+    let message = err.to_string();
+
+    #[cfg(feature = "hydrate")]
+    crate::sentry::capture_app_error(&message, code.as_str(), &correlation_id);
+    #[cfg(feature = "ssr")]
+    tracing::error!(
+        error_code = code.as_str(),
+        correlation_id = correlation_id.as_str(),
+        "{message}"
+    );
+    #[cfg(not(any(feature = "hydrate", feature = "ssr")))]
+    log::error!("[{}] {correlation_id}: {message}", code.as_str());
+
+    format!("/error?code={}&id={correlation_id}", code.as_str())
+}
+
+/// [report_error] with the code classified from the error text
+pub fn error_path(err: impl Display) -> String {
+    let message = err.to_string();
+    report_error(AppErrorCode::classify(&message), message)
+}
@@ -0,0 +1,279 @@
+use std::{fmt::Display, str::FromStr};
+
+use codee::string::FromToStringCodec;
+use consts::{AUTH_UTIL_COOKIES_MAX_AGE_MS, CONSENT_COOKIE};
+use leptos::prelude::*;
+use leptos_use::{use_cookie_with_options, UseCookieOptions};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsentCategory {
+    /// Login, wallet and anything else the app can't work without
+    Essential,
+    /// Mixpanel, GA4 and warehouse events
+    Analytics,
+    /// Recommendations tailored to the user's history
+    Personalization,
+}
+
+impl ConsentCategory {
+    pub fn all() -> [Self; 3] {
+        [Self::Essential, Self::Analytics, Self::Personalization]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Essential => "Essential",
+            Self::Analytics => "Analytics",
+            Self::Personalization => "Personalization",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Essential => "Needed for login, wallet and uploads. Always on.",
+            Self::Analytics => "Usage events that help us understand how the app is used.",
+            Self::Personalization => "Use your watch history to tailor your feed.",
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Essential => "essential",
+            Self::Analytics => "analytics",
+            Self::Personalization => "personalization",
+        }
+    }
+}
+
+/// Categories the user agreed to, essential is always granted
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Consent {
+    pub analytics: bool,
+    pub personalization: bool,
+}
+
+/// Applies until the user makes a choice in the banner or settings, nothing
+/// optional runs without an explicit opt in
+pub const DEFAULT_CONSENT: Consent = Consent::ESSENTIAL_ONLY;
+
+impl Consent {
+    pub const ALL: Self = Self {
+        analytics: true,
+        personalization: true,
+    };
+
+    pub const ESSENTIAL_ONLY: Self = Self {
+        analytics: false,
+        personalization: false,
+    };
+
+    pub fn allows(&self, category: ConsentCategory) -> bool {
+        match category {
+            ConsentCategory::Essential => true,
+            ConsentCategory::Analytics => self.analytics,
+            ConsentCategory::Personalization => self.personalization,
+        }
+    }
+
+    pub fn set(&mut self, category: ConsentCategory, granted: bool) {
+        match category {
+            ConsentCategory::Essential => {}
+            ConsentCategory::Analytics => self.analytics = granted,
+            ConsentCategory::Personalization => self.personalization = granted,
+        }
+    }
+
+    /// Do Not Track and Global Privacy Control override whatever was stored
+    pub fn with_privacy_signal(self, signal: bool) -> Self {
+        if signal {
+            Self::ESSENTIAL_ONLY
+        } else {
+            self
+        }
+    }
+}
+
+/// Kept as the granted categories joined by `.` so the cookie needs no escaping
+impl Display for Consent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let granted = ConsentCategory::all()
+            .into_iter()
+            .filter(|c| self.allows(*c))
+            .map(ConsentCategory::key)
+            .collect::<Vec<_>>();
+        write!(f, "{}", granted.join("."))
+    }
+}
+
+impl FromStr for Consent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut consent = Self::ESSENTIAL_ONLY;
+        for key in s.split('.') {
+            let category = ConsentCategory::all()
+                .into_iter()
+                .find(|c| c.key() == key)
+                .ok_or_else(|| format!("unknown consent category {key}"))?;
+            consent.set(category, true);
+        }
+        Ok(consent)
+    }
+}
+
+/// The consent cookie, shared by the whole app through context
+#[derive(Clone, Copy)]
+pub struct ConsentCtx {
+    consent: Signal<Option<Consent>>,
+    set_consent: WriteSignal<Option<Consent>>,
+}
+
+impl ConsentCtx {
+    /// Provided once at the root, the cookie is read here and nowhere else
+    pub fn init() -> Self {
+        let (consent, set_consent) = use_cookie_with_options::<Consent, FromToStringCodec>(
+            CONSENT_COOKIE,
+            UseCookieOptions::default()
+                .path("/")
+                .max_age(AUTH_UTIL_COOKIES_MAX_AGE_MS),
+        );
+        let ctx = Self {
+            consent,
+            set_consent,
+        };
+        provide_context(ctx);
+        ctx
+    }
+}
+
+/// The stored choice, `None` until the user decides
+pub fn use_consent_cookie() -> (Signal<Option<Consent>>, WriteSignal<Option<Consent>>) {
+    let ctx: ConsentCtx = expect_context();
+    (ctx.consent, ctx.set_consent)
+}
+
+/// Whether the browser sends Do Not Track or Global Privacy Control
+pub fn browser_privacy_signal() -> bool {
+    #[cfg(feature = "hydrate")]
+    {
+        let navigator = window().navigator();
+        let dnt = navigator.do_not_track() == "1";
+        let gpc = js_sys::Reflect::get(&navigator, &"globalPrivacyControl".into())
+            .ok()
+            .and_then(|v| v.as_bool())
+            .unwrap_or_default();
+        dnt || gpc
+    }
+    #[cfg(not(feature = "hydrate"))]
+    {
+        false
+    }
+}
+
+/// Effective consent in the browser, checked before any event leaves it
+///
+/// Read from [`ConsentCtx`], events sent from outside the app's reactive tree
+/// get [`DEFAULT_CONSENT`]
+pub fn current() -> Consent {
+    use_context::<ConsentCtx>()
+        .and_then(|ctx| ctx.consent.get_untracked())
+        .unwrap_or(DEFAULT_CONSENT)
+        .with_privacy_signal(browser_privacy_signal())
+}
+
+/// Saves the user's choice and applies it to the Mixpanel SDK right away
+pub fn store(set_consent: WriteSignal<Option<Consent>>, consent: Consent) {
+    set_consent.set(Some(consent));
+    let effective = consent.with_privacy_signal(browser_privacy_signal());
+    crate::mixpanel::mixpanel_events::sync_tracking_consent(
+        effective.allows(ConsentCategory::Analytics),
+    );
+}
+
+pub fn analytics_allowed() -> bool {
+    current().allows(ConsentCategory::Analytics)
+}
+
+#[cfg(feature = "ssr")]
+pub mod server {
+    use axum::http::HeaderMap;
+    use axum_extra::extract::CookieJar;
+    use consts::CONSENT_COOKIE;
+    use leptos_axum::extract;
+
+    use super::{Consent, ConsentCategory, DEFAULT_CONSENT};
+
+    fn privacy_signal(headers: &HeaderMap) -> bool {
+        ["dnt", "sec-gpc"]
+            .into_iter()
+            .any(|name| headers.get(name).is_some_and(|v| v.as_bytes() == b"1"))
+    }
+
+    /// Effective consent of the request being served
+    ///
+    /// Requests without a stored choice, and events produced outside a request
+    /// (background jobs), get [`DEFAULT_CONSENT`]
+    pub async fn request_consent() -> Consent {
+        let Ok((headers, jar)) = extract::<(HeaderMap, CookieJar)>().await else {
+            return DEFAULT_CONSENT;
+        };
+        jar.get(CONSENT_COOKIE)
+            .and_then(|c| c.value().parse().ok())
+            .unwrap_or(DEFAULT_CONSENT)
+            .with_privacy_signal(privacy_signal(&headers))
+    }
+
+    pub async fn analytics_allowed() -> bool {
+        request_consent().await.allows(ConsentCategory::Analytics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cookie_round_trip() {
+        for analytics in [false, true] {
+            for personalization in [false, true] {
+                let consent = Consent {
+                    analytics,
+                    personalization,
+                };
+                assert_eq!(consent.to_string().parse::<Consent>(), Ok(consent));
+            }
+        }
+    }
+
+    #[test]
+    fn cookie_format() {
+        assert_eq!(Consent::ESSENTIAL_ONLY.to_string(), "essential");
+        assert_eq!(
+            Consent::ALL.to_string(),
+            "essential.analytics.personalization"
+        );
+        assert_eq!(
+            "analytics".parse::<Consent>(),
+            Ok(Consent {
+                analytics: true,
+                personalization: false,
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_category_is_rejected() {
+        assert!("essential.marketing".parse::<Consent>().is_err());
+        assert!("".parse::<Consent>().is_err());
+    }
+
+    #[test]
+    fn defaults_to_essential_only() {
+        assert_eq!(DEFAULT_CONSENT, Consent::ESSENTIAL_ONLY);
+        assert_eq!(
+            Consent::ALL.with_privacy_signal(true),
+            Consent::ESSENTIAL_ONLY
+        );
+    }
+}
@@ -106,32 +106,6 @@ use yral_canisters_common::{
     Canisters,
 };
 
-pub enum AnalyticsEvent {
-    VideoWatched(VideoWatched),
-    LikeVideo(LikeVideo),
-    ShareVideo(ShareVideo),
-    VideoUploadInitiated(VideoUploadInitiated),
-    VideoUploadUploadButtonClicked(VideoUploadUploadButtonClicked),
-    VideoUploadVideoSelected(VideoUploadVideoSelected),
-    VideoUploadUnsuccessful(VideoUploadUnsuccessful),
-    VideoUploadSuccessful(VideoUploadSuccessful),
-    Refer(Refer),
-    ReferShareLink(ReferShareLink),
-    LoginSuccessful(LoginSuccessful),
-    LoginMethodSelected(LoginMethodSelected),
-    LoginJoinOverlayViewed(LoginJoinOverlayViewed),
-    LoginCta(LoginCta),
-    LogoutClicked(LogoutClicked),
-    LogoutConfirmation(LogoutConfirmation),
-    ErrorEvent(ErrorEvent),
-    ProfileViewVideo(ProfileViewVideo),
-    TokenCreationStarted(TokenCreationStarted),
-    TokensTransferred(TokensTransferred),
-    PageVisit(PageVisit),
-    CentsAdded(CentsAdded),
-    CentsWithdrawn(CentsWithdrawn),
-}
-
 #[derive(Clone)]
 pub struct EventUserDetails {
     pub details: ProfileDetails,
@@ -155,9 +129,6 @@ impl EventCtx {
     }
 }
 
-// VideoEventData is now exported from video_analytics module
-pub use crate::event_streaming::video_analytics::VideoEventData;
-
 #[derive(Default)]
 pub struct VideoWatched;
 
@@ -196,104 +167,6 @@ impl VideoWatched {
     }
 }
 
-#[derive(Default)]
-pub struct LikeVideo;
-
-impl LikeVideo {
-    pub fn send_event(&self, ctx: EventCtx, post_details: PostDetails, likes: RwSignal<u64>) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
-        {
-            let publisher_user_id = post_details.poster_principal;
-            let video_id = post_details.uid.clone();
-            let hastag_count = post_details.hastags.len();
-            let is_nsfw = post_details.is_nsfw;
-            let is_hotornot = post_details.hot_or_not_feed_ranking_score.is_some();
-            let view_count = post_details.views;
-            let post_id = post_details.post_id;
-            let publisher_canister_id = post_details.canister_id;
-            let nsfw_probability = post_details.nsfw_probability;
-
-            // like_video - analytics
-
-            let Some(user) = ctx.user_details() else {
-                return;
-            };
-
-            let _ = send_event_ssr_spawn(
-                "like_video".to_string(),
-                json!({
-                    "publisher_user_id":publisher_user_id,
-                    "user_id": user.details.principal,
-                    "is_loggedIn": ctx.is_connected(),
-                    "display_name": user.details.display_name,
-                    "canister_id": user.canister_id,
-                    "video_id": video_id,
-                    "video_category": "NA",
-                    "creator_category": "NA",
-                    "hashtag_count": hastag_count,
-                    "is_NSFW": is_nsfw,
-                    "is_hotorNot": is_hotornot,
-                    "feed_type": "NA",
-                    "view_count": view_count,
-                    "like_count": likes.get(),
-                    "share_count": 0,
-                    "post_id": post_id,
-                    "publisher_canister_id": publisher_canister_id,
-                    "nsfw_probability": nsfw_probability,
-                })
-                .to_string(),
-            );
-        }
-    }
-}
-
-#[derive(Default)]
-pub struct ShareVideo;
-
-impl ShareVideo {
-    pub fn send_event(&self, ctx: EventCtx, post_details: PostDetails) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
-        {
-            let publisher_user_id = post_details.poster_principal;
-            let video_id = post_details.uid.clone();
-            let hastag_count = post_details.hastags.len();
-            let is_nsfw = post_details.is_nsfw;
-            let is_hotornot = post_details.hot_or_not_feed_ranking_score.is_some();
-            let view_count = post_details.views;
-            let like_count = post_details.likes;
-            let nsfw_probability = post_details.nsfw_probability;
-
-            let Some(user) = ctx.user_details() else {
-                return;
-            };
-
-            // share_video - analytics
-            let _ = send_event_ssr_spawn(
-                "share_video".to_string(),
-                json!({
-                    "publisher_user_id":publisher_user_id,
-                    "user_id": user.details.principal,
-                    "is_loggedIn": ctx.is_connected(),
-                    "display_name": user.details.display_name,
-                    "canister_id": user.canister_id,
-                    "video_id": video_id,
-                    "video_category": "NA",
-                    "creator_category": "NA",
-                    "hashtag_count": hastag_count,
-                    "is_NSFW": is_nsfw,
-                    "is_hotorNot": is_hotornot,
-                    "feed_type": "NA",
-                    "view_count": view_count,
-                    "like_count": like_count,
-                    "share_count": 0,
-                    "nsfw_probability": nsfw_probability,
-                })
-                .to_string(),
-            );
-        }
-    }
-}
-
 #[derive(Default)]
 pub struct VideoUploadInitiated;
 
@@ -742,37 +615,6 @@ impl ErrorEvent {
     }
 }
 
-#[derive(Default)]
-pub struct ProfileViewVideo;
-
-impl ProfileViewVideo {
-    pub fn send_event(&self, ctx: EventCtx, post_details: PostDetails) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
-        {
-            let publisher_user_id = post_details.poster_principal;
-            let video_id = post_details.uid.clone();
-
-            let Some(user) = ctx.user_details() else {
-                return;
-            };
-
-            let _ = send_event_ssr_spawn(
-                "profile_view_video".to_string(),
-                json!({
-                    "publisher_user_id":publisher_user_id,
-                    "user_id": user.details.principal,
-                    "is_loggedIn": ctx.is_connected(),
-                    "display_name": user.details.display_name,
-                    "canister_id": user.canister_id,
-                    "video_id": video_id,
-                    "profile_feed": "main",
-                })
-                .to_string(),
-            );
-        }
-    }
-}
-
 #[derive(Default)]
 pub struct TokenCreationStarted;
 
@@ -47,6 +47,10 @@ pub async fn send_event_ssr(event_name: String, params: String) -> Result<(), Se
 pub fn send_event_ssr_spawn(event_name: String, params: String) -> Result<(), ServerFnError> {
     use leptos::task::spawn_local;
 
+    if !crate::consent::analytics_allowed() {
+        return Ok(());
+    }
+
     let mut params = serde_json::from_str::<serde_json::Value>(&params).map_err(|e| {
         log::error!("Error parsing params: {e:?}");
         ServerFnError::new(e.to_string())
@@ -68,20 +72,39 @@ pub fn send_event_ssr_spawn(event_name: String, params: String) -> Result<(), Se
     Ok(())
 }
 
+/// Fills in the host serving the request unless the event already has one
 #[cfg(all(feature = "ga4", feature = "ssr"))]
-#[instrument]
-pub async fn send_event_warehouse(event_name: &str, params: &serde_json::Value) {
+pub(crate) fn with_host(mut params: serde_json::Value) -> serde_json::Value {
     use super::host::get_host;
 
-    let event_name = event_name.to_string();
-
-    let mut params = params.clone();
     if params["host"].is_null() {
         let host_str = get_host();
         params["host"] = json!(host_str);
     }
+    params
+}
 
-    let res = stream_to_offchain_agent(event_name, &params).await;
+#[cfg(all(feature = "ga4", feature = "ssr"))]
+#[instrument]
+pub async fn send_event_warehouse(event_name: &str, params: &serde_json::Value) {
+    if !crate::consent::server::analytics_allowed().await {
+        return;
+    }
+
+    let event_name = event_name.to_string();
+    let params = with_host(params.clone());
+
+    if let Some(outbox) = use_context::<crate::analytics::outbox::AnalyticsOutbox>() {
+        use crate::analytics::outbox::OutboxDestination;
+
+        outbox
+            .enqueue(OutboxDestination::Warehouse { event: event_name }, params)
+            .await;
+        return;
+    }
+
+    let channel: tonic::transport::Channel = expect_context();
+    let res = stream_to_offchain_agent(channel, event_name, &params).await;
     if let Err(e) = res {
         log::error!("Error sending event to warehouse: {e:?}");
     }
@@ -106,28 +129,32 @@ pub async fn send_event_warehouse_ssr(
 pub fn send_event_warehouse_ssr_spawn(event_name: String, params: String) {
     use leptos::task::spawn_local;
 
+    if !crate::consent::analytics_allowed() {
+        return;
+    }
+
     spawn_local(async move {
         let _ = send_event_warehouse_ssr(event_name, params).await;
     });
 }
 
 #[cfg(all(feature = "ga4", feature = "ssr"))]
-#[instrument]
+#[instrument(skip(channel))]
 pub async fn stream_to_offchain_agent(
+    channel: tonic::transport::Channel,
     event: String,
     params: &serde_json::Value,
-) -> Result<(), ServerFnError> {
+) -> Result<(), tonic::Status> {
     use tonic::metadata::MetadataValue;
-    use tonic::transport::Channel;
     use tonic::Request;
 
-    let channel: Channel = expect_context();
-
     let mut off_chain_agent_grpc_auth_token = env::var("GRPC_AUTH_TOKEN").expect("GRPC_AUTH_TOKEN");
     // removing whitespaces and new lines for proper parsing
     off_chain_agent_grpc_auth_token.retain(|c| !c.is_whitespace());
 
-    let token: MetadataValue<_> = format!("Bearer {off_chain_agent_grpc_auth_token}").parse()?;
+    let token: MetadataValue<_> = format!("Bearer {off_chain_agent_grpc_auth_token}")
+        .parse()
+        .map_err(|e| tonic::Status::unauthenticated(format!("invalid GRPC_AUTH_TOKEN: {e}")))?;
 
     let mut client =
         warehouse_events::warehouse_events_client::WarehouseEventsClient::with_interceptor(
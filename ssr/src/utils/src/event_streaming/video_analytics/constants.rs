@@ -15,7 +15,3 @@ pub const MIN_PAUSE_TIME_SECONDS: f64 = 0.1;
 
 /// Progress check interval in milliseconds
 pub const PROGRESS_CHECK_INTERVAL_MS: u64 = (VIDEO_PAUSE_ERROR_THRESHOLD_SECONDS * 1000.0) as u64;
-
-/// Event names
-pub const EVENT_VIDEO_VIEWED: &str = "video_viewed";
-pub const EVENT_VIDEO_DURATION_WATCHED: &str = "video_duration_watched";
@@ -1,11 +1,7 @@
-pub mod analytics_provider;
 pub mod constants;
-pub mod event_builder;
 pub mod progress_tracker;
 pub mod video_watched;
 
-pub use analytics_provider::{VideoAnalyticsEvent, VideoAnalyticsProvider};
 pub use constants::*;
-pub use event_builder::{VideoEventData, VideoEventDataBuilder};
 pub use progress_tracker::VideoProgressTracker;
 pub use video_watched::VideoWatchedHandler;
@@ -4,19 +4,14 @@ use leptos::{ev, logging};
 use leptos_use::use_event_listener;
 use wasm_bindgen::JsCast;
 
+#[cfg(feature = "ga4")]
+use crate::analytics::{batch::WatchSession, dispatch_watch};
+use crate::analytics::{dispatch, AnalyticsEvent, VideoProps, WatchProgress};
 use crate::event_streaming::events::EventCtx;
+use crate::mixpanel::mixpanel_events::MixpanelVideoClickedCTAType;
 use yral_canisters_common::utils::posts::PostDetails;
 
-use super::{
-    constants::*, progress_tracker::ProgressLogInfo, VideoAnalyticsEvent, VideoAnalyticsProvider,
-    VideoEventDataBuilder, VideoProgressTracker,
-};
-
-#[cfg(feature = "ga4")]
-use crate::event_streaming::{send_event_ssr_spawn, send_event_warehouse_ssr_spawn};
-
-#[cfg(feature = "ga4")]
-use super::analytics_provider::MixpanelProvider;
+use super::{constants::*, progress_tracker::ProgressLogInfo, VideoProgressTracker};
 
 pub struct VideoWatchedHandler {
     progress_tracker: VideoProgressTracker,
@@ -29,6 +24,7 @@ struct TimeUpdateListenerParams {
     full_video_watched: Signal<bool>,
     set_full_video_watched: WriteSignal<bool>,
     playing_started: RwSignal<bool>,
+    session: StoredValue<WatchSession>,
 }
 
 impl Default for VideoWatchedHandler {
@@ -68,12 +64,26 @@ impl VideoWatchedHandler {
             let (full_video_watched, set_full_video_watched) = signal(false);
             let playing_started = RwSignal::new(false);
 
+            // a new session whenever another post is loaded or this one comes back into view
+            let session = StoredValue::new(WatchSession::next());
+            let post_key = Memo::new(move |_| {
+                vid_details.with(|post| post.as_ref().map(|p| (p.canister_id, p.post_id)))
+            });
+            Effect::new(move |_| {
+                post_key.track();
+                if is_current.is_some_and(|is_current| !is_current.get()) {
+                    return;
+                }
+                session.set_value(WatchSession::next());
+            });
+
             self.setup_playing_listener(
                 ctx,
                 vid_details,
                 container_ref,
                 playing_started,
                 self.progress_tracker,
+                session,
             );
 
             let params = TimeUpdateListenerParams {
@@ -82,10 +92,17 @@ impl VideoWatchedHandler {
                 full_video_watched: full_video_watched.into(),
                 set_full_video_watched,
                 playing_started,
+                session,
             };
             self.setup_timeupdate_listener(ctx, vid_details, container_ref, params);
 
-            self.setup_pause_listener(ctx, vid_details, container_ref, self.progress_tracker);
+            self.setup_pause_listener(
+                ctx,
+                vid_details,
+                container_ref,
+                self.progress_tracker,
+                session,
+            );
 
             self.setup_mute_listener(ctx, vid_details, muted, is_current);
         }
@@ -99,6 +116,7 @@ impl VideoWatchedHandler {
         container_ref: NodeRef<Video>,
         playing_started: RwSignal<bool>,
         progress_tracker: VideoProgressTracker,
+        session: StoredValue<WatchSession>,
     ) {
         let _ = use_event_listener(container_ref, ev::playing, move |_evt| {
             let Some(_) = container_ref.get() else {
@@ -121,13 +139,10 @@ impl VideoWatchedHandler {
             progress_tracker.start_tracking(container_ref, log_info);
 
             if let Some(post) = vid_details() {
-                #[cfg(feature = "ga4")]
-                MixpanelProvider.track_event(
-                    VideoAnalyticsEvent::VideoStarted {
-                        post,
-                        is_logged_in: ctx.is_connected(),
-                    },
+                dispatch_watch(
                     ctx,
+                    session.get_value(),
+                    AnalyticsEvent::VideoStarted(VideoProps::from(&post)),
                 );
             }
         });
@@ -142,11 +157,9 @@ impl VideoWatchedHandler {
         params: TimeUpdateListenerParams,
     ) {
         let _ = use_event_listener(container_ref, ev::timeupdate, move |evt| {
-            let Some(user) = ctx.user_details() else {
+            let Some(post) = vid_details() else {
                 return;
             };
-            let post_o = vid_details();
-            let post = post_o.as_ref();
 
             let Some(target) = evt.target() else {
                 logging::error!("video_log: No target found for video timeupdate event");
@@ -164,13 +177,13 @@ impl VideoWatchedHandler {
             if current_time >= VIDEO_COMPLETION_PERCENTAGE * duration
                 && !params.full_video_watched.get()
             {
-                let event_data = VideoEventDataBuilder::from_context(&user, post, &ctx)
-                    .with_completion(duration)
-                    .build();
-
-                send_event_warehouse_ssr_spawn(
-                    EVENT_VIDEO_DURATION_WATCHED.to_string(),
-                    serde_json::to_string(&event_data).unwrap_or_default(),
+                dispatch_watch(
+                    ctx,
+                    params.session.get_value(),
+                    AnalyticsEvent::VideoDurationWatched {
+                        video: VideoProps::from(&post),
+                        progress: WatchProgress::completed(duration),
+                    },
                 );
 
                 params.set_full_video_watched.set(true);
@@ -182,24 +195,12 @@ impl VideoWatchedHandler {
 
             // Track 3 second view
             if current_time >= VIDEO_VIEWED_THRESHOLD_SECONDS && params.playing_started.get() {
-                let event_data = VideoEventDataBuilder::from_context(&user, post, &ctx).build();
-
-                let _ = send_event_ssr_spawn(
-                    EVENT_VIDEO_VIEWED.to_string(),
-                    serde_json::to_string(&event_data).unwrap_or_default(),
+                dispatch_watch(
+                    ctx,
+                    params.session.get_value(),
+                    AnalyticsEvent::VideoViewed(VideoProps::from(&post)),
                 );
 
-                if let Some(post) = post {
-                    #[cfg(feature = "ga4")]
-                    MixpanelProvider.track_event(
-                        VideoAnalyticsEvent::VideoViewed {
-                            post: post.clone(),
-                            is_logged_in: ctx.is_connected(),
-                        },
-                        ctx,
-                    );
-                }
-
                 params.playing_started.set(false);
                 params.set_video_watched.set(true);
             }
@@ -213,15 +214,14 @@ impl VideoWatchedHandler {
         vid_details: Signal<Option<PostDetails>>,
         container_ref: NodeRef<Video>,
         progress_tracker: VideoProgressTracker,
+        session: StoredValue<WatchSession>,
     ) {
         let _ = use_event_listener(container_ref, ev::pause, move |evt| {
             progress_tracker.stop_tracking();
 
-            let Some(user) = ctx.user_details() else {
+            let Some(post) = vid_details() else {
                 return;
             };
-            let post_o = vid_details();
-            let post = post_o.as_ref();
 
             let Some(target) = evt.target() else {
                 logging::error!("No target found for video pause event");
@@ -235,13 +235,13 @@ impl VideoWatchedHandler {
                 return;
             }
 
-            let event_data = VideoEventDataBuilder::from_context(&user, post, &ctx)
-                .with_pause_progress(current_time, duration)
-                .build();
-
-            send_event_warehouse_ssr_spawn(
-                EVENT_VIDEO_DURATION_WATCHED.to_string(),
-                serde_json::to_string(&event_data).unwrap_or_default(),
+            dispatch_watch(
+                ctx,
+                session.get_value(),
+                AnalyticsEvent::VideoDurationWatched {
+                    video: VideoProps::from(&post),
+                    progress: WatchProgress::paused(current_time, duration),
+                },
             );
         });
     }
@@ -270,15 +270,18 @@ impl VideoWatchedHandler {
             }
             mixpanel_video_muted.set(current_muted);
 
-            let post_o = vid_details();
-            if let Some(post) = post_o {
-                #[cfg(feature = "ga4")]
-                MixpanelProvider.track_event(
-                    VideoAnalyticsEvent::VideoMuted {
-                        post,
-                        muted: current_muted,
-                    },
+            if let Some(post) = vid_details() {
+                let cta_type = if current_muted {
+                    MixpanelVideoClickedCTAType::Mute
+                } else {
+                    MixpanelVideoClickedCTAType::Unmute
+                };
+                dispatch(
                     ctx,
+                    AnalyticsEvent::VideoClicked {
+                        video: VideoProps::from(&post),
+                        cta_type,
+                    },
                 );
             }
         });
@@ -0,0 +1,78 @@
+//! Errors shared by the clients for outbound HTTP services
+//!
+//! [HttpClientError] implements [std::error::Error], so `?` converts it
+//! into a `ServerFnError` carrying the message
+
+use reqwest::{Response, StatusCode};
+use serde::{Deserialize, Serialize};
+
+/// Error bodies are cut to this many chars before they're logged or shown
+const MAX_BODY_LEN: usize = 512;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, thiserror::Error)]
+pub enum HttpClientError {
+    #[error("Network error: {0}")]
+    Network(String),
+    #[error("Request failed with status {status}: {body}")]
+    Status { status: u16, body: String },
+    #[error("Failed to decode response: {0}")]
+    Decode(String),
+    #[error("Not authorized: {0}")]
+    Auth(String),
+}
+
+impl From<reqwest::Error> for HttpClientError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_decode() {
+            Self::Decode(e.to_string())
+        } else {
+            Self::Network(e.to_string())
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: String,
+}
+
+impl HttpClientError {
+    /// Builds the error for a non-success response, keeping its body
+    /// services responding with `{"error": ".."}` only keep the message
+    pub async fn from_response(res: Response) -> Self {
+        let status = res.status();
+        let body = match res.text().await {
+            Ok(text) => serde_json::from_str::<ErrorBody>(&text)
+                .map(|b| b.error)
+                .unwrap_or(text),
+            Err(e) => format!("<failed to read body: {e}>"),
+        };
+        let body = body.chars().take(MAX_BODY_LEN).collect();
+
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Auth(body),
+            status => Self::Status {
+                status: status.as_u16(),
+                body,
+            },
+        }
+    }
+
+    /// Passes through successful responses
+    pub async fn check(res: Response) -> Result<Response, Self> {
+        if res.status().is_success() {
+            Ok(res)
+        } else {
+            Err(Self::from_response(res).await)
+        }
+    }
+
+    /// Whether retrying the same request may succeed
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Network(_) => true,
+            Self::Status { status, .. } => *status == 429 || *status >= 500,
+            Self::Decode(_) | Self::Auth(_) => false,
+        }
+    }
+}
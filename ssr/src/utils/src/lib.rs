@@ -2,8 +2,12 @@ use futures::Future;
 use serde::{Deserialize, Serialize};
 
 pub mod ab_testing;
+pub mod analytics;
+pub mod app_error;
+pub mod consent;
 pub mod event_streaming;
 pub mod host;
+pub mod http_client;
 pub mod icon;
 pub mod local_storage;
 pub mod mixpanel;
@@ -16,6 +20,7 @@ pub mod qstash;
 pub mod report;
 pub mod route;
 pub mod sentry;
+pub mod text_entities;
 pub mod time;
 pub mod types;
 pub mod web;
@@ -15,6 +15,7 @@ use wasm_bindgen::JsValue;
 use yral_canisters_common::utils::vote::VoteKind;
 use yral_canisters_common::Canisters;
 
+use crate::consent;
 use crate::event_streaming::events::EventCtx;
 use crate::event_streaming::events::HistoryCtx;
 
@@ -26,26 +27,54 @@ extern "C" {
     /// mixpanel.identify(user_id)
     #[wasm_bindgen(js_namespace = mixpanel, catch)]
     fn identify(user_id: &str) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_namespace = mixpanel, catch)]
+    fn opt_in_tracking() -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_namespace = mixpanel, catch)]
+    fn opt_out_tracking() -> Result<(), JsValue>;
 }
 
 /// Call once you know the logged-in user's ID
 pub fn identify_user(user_id: &str) {
+    if !consent::analytics_allowed() {
+        return;
+    }
     let _ = identify(user_id);
 }
 
+/// Keeps the Mixpanel SDK's own autocapture in line with the user's analytics consent
+pub fn sync_tracking_consent(allowed: bool) {
+    let res = if allowed {
+        opt_in_tracking()
+    } else {
+        opt_out_tracking()
+    };
+    if let Err(e) = res {
+        logging::error!("Error updating Mixpanel tracking consent: {:?}", e);
+    }
+}
+
 #[server]
 async fn track_event_server_fn(props: Value) -> Result<(), ServerFnError> {
+    if !crate::consent::server::analytics_allowed().await {
+        return Ok(());
+    }
+    forward_to_analytics_server(props).await
+}
+
+/// The caller's ip and user agent, `None` outside a request
+#[cfg(feature = "ssr")]
+pub(crate) async fn request_client_info() -> (Option<String>, Option<String>) {
     use axum::http::HeaderMap;
     use axum_extra::headers::UserAgent;
     use axum_extra::TypedHeader;
     use leptos_axum::extract;
 
-    let mut props = props;
-
     // Attempt to extract headers and User-Agent
     let result: Result<(HeaderMap, TypedHeader<UserAgent>), _> = extract().await;
 
-    let (ip, ua) = match result {
+    match result {
         Ok((headers, TypedHeader(user_agent))) => {
             let ip = headers
                 .get("x-forwarded-for")
@@ -58,12 +87,32 @@ async fn track_event_server_fn(props: Value) -> Result<(), ServerFnError> {
             (Some(ip), Some(ua))
         }
         Err(_) => (None, None),
-    };
+    }
+}
 
-    // Inject metadata into props
+/// Injects the metadata from [`request_client_info`] into `props`
+#[cfg(feature = "ssr")]
+pub(crate) fn with_client_info(
+    mut props: Value,
+    (ip, ua): &(Option<String>, Option<String>),
+) -> Value {
     props["ip"] = ip.clone().into();
     props["ip_addr"] = ip.clone().into();
     props["user_agent"] = ua.clone().into();
+    props
+}
+
+/// Adds the caller's ip and user agent and hands the event to the analytics server
+#[cfg(feature = "ssr")]
+pub(crate) async fn forward_to_analytics_server(props: Value) -> Result<(), ServerFnError> {
+    let props = with_client_info(props, &request_client_info().await);
+
+    if let Some(outbox) = use_context::<crate::analytics::outbox::AnalyticsOutbox>() {
+        use crate::analytics::outbox::OutboxDestination;
+
+        outbox.enqueue(OutboxDestination::Mixpanel, props).await;
+        return Ok(());
+    }
 
     #[cfg(feature = "qstash")]
     {
@@ -74,7 +123,7 @@ async fn track_event_server_fn(props: Value) -> Result<(), ServerFnError> {
             qstash_client
                 .send_analytics_event_to_qstash(props, token)
                 .await
-                .map_err(|e| ServerFnError::new(format!("Mixpanel track error: {e:?}")))?;
+                .map_err(|e| ServerFnError::new(format!("Mixpanel track error: {e}")))?;
         } else {
             logging::error!("QStash client not found. Gracefully continuing");
         }
@@ -110,7 +159,19 @@ pub fn track_event<T>(event_name: &str, props: T)
 where
     T: Serialize,
 {
-    let track_props = serde_wasm_bindgen::to_value(&props);
+    if !consent::analytics_allowed() {
+        return;
+    }
+    track_in_sdk(event_name, &props);
+    send_event_to_server(event_name, props);
+}
+
+/// Tracks through the Mixpanel browser SDK only
+pub(crate) fn track_in_sdk<T>(event_name: &str, props: &T)
+where
+    T: Serialize,
+{
+    let track_props = serde_wasm_bindgen::to_value(props);
     match track_props {
         Ok(props) => {
             if let Err(e) = track(event_name, props) {
@@ -121,11 +182,27 @@ where
             logging::error!("Error serializing Mixpanel event properties: {:?}", e);
         }
     }
+}
 
-    send_event_to_server(event_name, props);
+pub(crate) fn send_event_to_server<T>(event_name: &str, props: T)
+where
+    T: Serialize,
+{
+    if !consent::analytics_allowed() {
+        return;
+    }
+    let props = server_event_props(event_name, props);
+    spawn_local(async {
+        let res = track_event_server_fn(props).await;
+        match res {
+            Ok(_) => {}
+            Err(e) => logging::error!("Error tracking Mixpanel event: {}", e),
+        }
+    });
 }
 
-fn send_event_to_server<T>(event_name: &str, props: T)
+/// Adds the device, page and UTM details the analytics server expects
+pub(crate) fn server_event_props<T>(event_name: &str, props: T) -> Value
 where
     T: Serialize,
 {
@@ -167,13 +244,7 @@ where
     } else {
         logging::error!("HistoryCtx not found. Gracefully continuing");
     }
-    spawn_local(async {
-        let res = track_event_server_fn(props).await;
-        match res {
-            Ok(_) => {}
-            Err(e) => logging::error!("Error tracking Mixpanel event: {}", e),
-        }
-    });
+    props
 }
 
 /// Global properties for Mixpanel events
@@ -531,6 +602,7 @@ impl TryFrom<String> for BottomNavigationCategory {
             "/upload" => Ok(BottomNavigationCategory::UploadVideo),
             "/profile" => Ok(BottomNavigationCategory::Profile),
             "/menu" => Ok(BottomNavigationCategory::Menu),
+            "/leaderboard" => Ok(BottomNavigationCategory::Leaderboard),
             _ => Err(()),
         }
     }
@@ -584,81 +656,6 @@ pub struct MixpanelNsfwToggleProps {
     pub is_nsfw: bool,
 }
 
-#[derive(Serialize)]
-pub struct MixpanelVideoClickedProps {
-    // #[serde(flatten)]
-    pub user_id: Option<String>,
-    pub visitor_id: Option<String>,
-    pub is_logged_in: bool,
-    pub canister_id: String,
-    pub is_nsfw_enabled: bool,
-    pub publisher_user_id: String,
-    pub like_count: u64,
-    pub view_count: u64,
-    pub is_game_enabled: bool,
-    pub video_id: String,
-    pub game_type: MixpanelPostGameType,
-    pub cta_type: MixpanelVideoClickedCTAType,
-    pub is_nsfw: bool,
-}
-
-#[derive(Serialize)]
-pub struct MixpanelVideoReportedProps {
-    // #[serde(flatten)]
-    pub user_id: Option<String>,
-    pub visitor_id: Option<String>,
-    pub is_logged_in: bool,
-    pub canister_id: String,
-    pub is_nsfw_enabled: bool,
-    pub publisher_user_id: String,
-    pub is_game_enabled: bool,
-    pub video_id: String,
-    pub game_type: MixpanelPostGameType,
-    pub is_nsfw: bool,
-    pub report_reason: String,
-}
-
-#[derive(Serialize)]
-pub struct MixpanelVideoClickedProfileProps {
-    // #[serde(flatten)]
-    pub user_id: Option<String>,
-    pub visitor_id: Option<String>,
-    pub is_logged_in: bool,
-    pub canister_id: String,
-    pub is_nsfw_enabled: bool,
-    pub publisher_user_id: String,
-    pub like_count: u64,
-    pub view_count: u64,
-    pub is_game_enabled: bool,
-    pub video_id: String,
-    pub game_type: MixpanelPostGameType,
-    pub cta_type: MixpanelVideoClickedCTAType,
-    pub position: Option<u64>,
-    pub is_own_profile: bool,
-    pub is_nsfw: bool,
-    pub page_name: String,
-}
-
-#[derive(Serialize)]
-pub struct MixpanelGameClickedProps {
-    // #[serde(flatten)]
-    pub user_id: Option<String>,
-    pub visitor_id: Option<String>,
-    pub is_logged_in: bool,
-    pub canister_id: String,
-    pub is_nsfw_enabled: bool,
-    pub publisher_user_id: String,
-    pub like_count: u64,
-    pub view_count: u64,
-    pub is_game_enabled: bool,
-    pub video_id: String,
-    pub game_type: MixpanelPostGameType,
-    pub option_chosen: ChosenGameOption,
-    pub stake_amount: u64,
-    pub stake_type: StakeType,
-    pub is_nsfw: bool,
-}
-
 #[derive(Serialize)]
 pub struct MixpanelReferAndEarnProps {
     // #[serde(flatten)]
@@ -685,14 +682,14 @@ impl From<VoteKind> for ChosenGameOption {
     }
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ChosenGameOption {
     Hot,
     Not,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum MixpanelVideoClickedCTAType {
     Like,
@@ -730,80 +727,7 @@ pub enum MixpanelProfileClickedCTAType {
     MemeCoin,
 }
 
-#[derive(Serialize)]
-pub struct MixpanelVideoViewedProps {
-    // #[serde(flatten)]
-    pub user_id: Option<String>,
-    pub visitor_id: Option<String>,
-    pub is_logged_in: bool,
-    pub canister_id: String,
-    pub is_nsfw_enabled: bool,
-    pub video_id: String,
-    pub publisher_user_id: String,
-    pub game_type: MixpanelPostGameType,
-    pub like_count: u64,
-    pub view_count: u64,
-    pub is_nsfw: bool,
-    pub is_game_enabled: bool,
-}
-
-#[derive(Serialize)]
-pub struct MixpanelVideoStartedProps {
-    // #[serde(flatten)]
-    pub user_id: Option<String>,
-    pub visitor_id: Option<String>,
-    pub is_logged_in: bool,
-    pub canister_id: String,
-    pub is_nsfw_enabled: bool,
-    pub video_id: String,
-    pub publisher_user_id: String,
-    pub game_type: MixpanelPostGameType,
-    pub like_count: u64,
-    pub view_count: u64,
-    pub is_nsfw: bool,
-    pub is_game_enabled: bool,
-}
-
-#[derive(Serialize)]
-pub struct MixpanelGamePlayedProps {
-    // #[serde(flatten)]
-    pub user_id: Option<String>,
-    pub visitor_id: Option<String>,
-    pub is_logged_in: bool,
-    pub canister_id: String,
-    pub is_nsfw_enabled: bool,
-    pub video_id: String,
-    pub publisher_user_id: String,
-    pub game_type: MixpanelPostGameType,
-    pub stake_amount: u64,
-    pub stake_type: StakeType,
-    pub option_chosen: ChosenGameOption,
-    pub like_count: u64,
-    pub view_count: u64,
-    pub is_game_enabled: bool,
-    pub conclusion: GameConclusion,
-    pub won_loss_amount: String,
-    pub creator_commision_percentage: u64,
-    pub is_nsfw: bool,
-}
-
-#[derive(Serialize)]
-pub struct MixpanelHowToPlayGameClickedProps {
-    // #[serde(flatten)]
-    pub user_id: Option<String>,
-    pub visitor_id: Option<String>,
-    pub is_logged_in: bool,
-    pub canister_id: String,
-    pub is_nsfw_enabled: bool,
-    pub video_id: String,
-    pub game_type: MixpanelPostGameType,
-    pub stake_amount: u64,
-    pub stake_type: StakeType,
-    pub option_chosen: ChosenGameOption,
-    pub conclusion: GameConclusion,
-}
-
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum GameConclusion {
     Pending,
@@ -811,7 +735,7 @@ pub enum GameConclusion {
     Loss,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum StakeType {
     Sats,
@@ -829,6 +753,7 @@ pub enum BottomNavigationCategory {
     Menu,
     Home,
     Wallet,
+    Leaderboard,
 }
 
 #[derive(Serialize)]
@@ -878,7 +803,7 @@ pub struct MixPanelEvent;
 impl MixPanelEvent {
     /// Call once you know the logged-in user's ID
     pub fn identify_user(user_id: &str) {
-        let _ = identify(user_id);
+        identify_user(user_id);
     }
     pub fn track_home_page_viewed(p: MixpanelBottomBarPageViewedProps) {
         track_event("home_page_viewed", p);
@@ -1076,49 +1001,14 @@ impl MixPanelEvent {
         track_event("sats_to_btc_converted", p);
     }
 
-    pub fn track_nsfw_true(p: MixpanelNsfwToggleProps) {
-        track_event("nsfw_enabled", p);
-    }
-
     pub fn track_nsfw_false(p: MixpanelNsfwToggleProps) {
         track_event("NSFW_False", p);
     }
 
-    pub fn track_video_clicked(p: MixpanelVideoClickedProps) {
-        track_event("video_clicked", p);
-    }
-
-    pub fn track_video_reported(p: MixpanelVideoReportedProps) {
-        send_event_to_server("video_reported", p);
-    }
-
-    pub fn track_video_clicked_profile(p: MixpanelVideoClickedProfileProps) {
-        track_event("video_clicked", p);
-    }
-
     pub fn track_refer_and_earn(p: MixpanelReferAndEarnProps) {
         track_event("refer_and_earn", p);
     }
 
-    pub fn track_video_viewed(p: MixpanelVideoViewedProps) {
-        track_event("video_viewed", p);
-    }
-
-    pub fn track_video_impression(p: MixpanelVideoViewedProps) {
-        send_event_to_server("video_impression", p);
-    }
-
-    pub fn track_video_started(p: MixpanelVideoStartedProps) {
-        track_event("video_started", p);
-    }
-
-    pub fn track_game_played(p: MixpanelGamePlayedProps) {
-        track_event("game_played", p);
-    }
-    pub fn track_game_clicked(p: MixpanelGameClickedProps) {
-        track_event("game_voted", p);
-    }
-
     pub fn track_video_upload_success(p: MixpanelVideoUploadSuccessProps) {
         track_event("video_upload_success", p);
     }
@@ -1130,8 +1020,4 @@ impl MixPanelEvent {
     pub fn track_third_party_wallet_transferred(p: MixpanelThirdPartyWalletTransferredProps) {
         track_event("third_party_wallet_transferred", p);
     }
-
-    pub fn track_how_to_play_clicked(p: MixpanelHowToPlayGameClickedProps) {
-        track_event("how_to_play_clicked", p);
-    }
 }
@@ -10,14 +10,14 @@ use yral_types::post::PostItem;
 pub async fn get_ml_feed_coldstart_clean(
     canister_id: Principal,
     num_results: u32,
-    filter_results: Vec<PostDetails>,
+    filter_results: Vec<PostItem>,
 ) -> Result<Vec<PostItem>, anyhow::Error> {
     let client = reqwest::Client::new();
     let ml_feed_url = ML_FEED_URL.join("api/v1/feed/coldstart/clean").unwrap();
 
     let req = FeedRequest {
         canister_id,
-        filter_results: post_details_to_post_item(filter_results),
+        filter_results,
         num_results,
     };
 
@@ -36,14 +36,14 @@ pub async fn get_ml_feed_coldstart_clean(
 pub async fn get_ml_feed_coldstart_nsfw(
     canister_id: Principal,
     num_results: u32,
-    filter_results: Vec<PostDetails>,
+    filter_results: Vec<PostItem>,
 ) -> Result<Vec<PostItem>, anyhow::Error> {
     let client = reqwest::Client::new();
     let ml_feed_url = ML_FEED_URL.join("api/v1/feed/coldstart/nsfw").unwrap();
 
     let req = FeedRequest {
         canister_id,
-        filter_results: post_details_to_post_item(filter_results),
+        filter_results,
         num_results,
     };
 
@@ -62,14 +62,14 @@ pub async fn get_ml_feed_coldstart_nsfw(
 pub async fn get_ml_feed_coldstart_mixed(
     canister_id: Principal,
     num_results: u32,
-    filter_results: Vec<PostDetails>,
+    filter_results: Vec<PostItem>,
 ) -> Result<Vec<PostItem>, anyhow::Error> {
     let client = reqwest::Client::new();
     let ml_feed_url = ML_FEED_URL.join("api/v1/feed/coldstart/mixed").unwrap();
 
     let req = FeedRequest {
         canister_id,
-        filter_results: post_details_to_post_item(filter_results),
+        filter_results,
         num_results,
     };
 
@@ -88,14 +88,14 @@ pub async fn get_ml_feed_coldstart_mixed(
 pub async fn get_ml_feed_clean(
     canister_id: Principal,
     num_results: u32,
-    filter_results: Vec<PostDetails>,
+    filter_results: Vec<PostItem>,
 ) -> Result<Vec<PostItem>, anyhow::Error> {
     let client = reqwest::Client::new();
     let ml_feed_url = ML_FEED_URL.join("api/v1/feed/clean").unwrap();
 
     let req = FeedRequest {
         canister_id,
-        filter_results: post_details_to_post_item(filter_results),
+        filter_results,
         num_results,
     };
 
@@ -114,14 +114,14 @@ pub async fn get_ml_feed_clean(
 pub async fn get_ml_feed_nsfw(
     canister_id: Principal,
     num_results: u32,
-    filter_results: Vec<PostDetails>,
+    filter_results: Vec<PostItem>,
 ) -> Result<Vec<PostItem>, anyhow::Error> {
     let client = reqwest::Client::new();
     let ml_feed_url = ML_FEED_URL.join("api/v1/feed/nsfw").unwrap();
 
     let req = FeedRequest {
         canister_id,
-        filter_results: post_details_to_post_item(filter_results),
+        filter_results,
         num_results,
     };
 
@@ -140,14 +140,14 @@ pub async fn get_ml_feed_nsfw(
 pub async fn get_ml_feed_mixed(
     canister_id: Principal,
     num_results: u32,
-    filter_results: Vec<PostDetails>,
+    filter_results: Vec<PostItem>,
 ) -> Result<Vec<PostItem>, anyhow::Error> {
     let client = reqwest::Client::new();
     let ml_feed_url = ML_FEED_URL.join("api/v1/feed/mixed").unwrap();
 
     let req = FeedRequest {
         canister_id,
-        filter_results: post_details_to_post_item(filter_results),
+        filter_results,
         num_results,
     };
 
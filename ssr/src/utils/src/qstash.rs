@@ -5,10 +5,12 @@ use http::{
     HeaderMap, HeaderValue,
 };
 use reqwest::{Client, Url};
-use serde_json::Value;
+use serde_json::{json, Value};
 
 use consts::ANALYTICS_SERVER_URL;
 
+use crate::http_client::HttpClientError;
+
 #[derive(Clone, Debug)]
 pub struct QStashClient {
     client: Client,
@@ -40,7 +42,7 @@ impl QStashClient {
         &self,
         req: Value,
         token: String,
-    ) -> Result<(), reqwest::Error> {
+    ) -> Result<(), HttpClientError> {
         let off_chain_ep = ANALYTICS_SERVER_URL.join("api/send_event").unwrap();
         let path = format!("publish/{off_chain_ep}");
         let ep = self.base_url.join(&path).unwrap();
@@ -54,10 +56,36 @@ impl QStashClient {
             .header("Upstash-Forward-Authorization", format!("Bearer {token}"))
             .send()
             .await?;
-        if res.status() != 200 {
-            let e = res.text().await?;
-            log::error!("Error sending analytics to qstash: {e:?}");
-        }
+        HttpClientError::check(res).await?;
+        Ok(())
+    }
+
+    /// Publishes all events in one request through QStash's batch API
+    pub async fn send_analytics_events_batch_to_qstash(
+        &self,
+        events: &[Value],
+        token: &str,
+    ) -> Result<(), HttpClientError> {
+        let off_chain_ep = ANALYTICS_SERVER_URL.join("api/send_event").unwrap();
+        let ep = self.base_url.join("batch").unwrap();
+
+        let messages = events
+            .iter()
+            .map(|event| {
+                json!({
+                    "destination": off_chain_ep.as_str(),
+                    "headers": {
+                        "Content-Type": "application/json",
+                        "Upstash-Method": "POST",
+                        "Upstash-Forward-Authorization": format!("Bearer {token}"),
+                    },
+                    "body": event.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let res = self.client.post(ep).json(&messages).send().await?;
+        HttpClientError::check(res).await?;
         Ok(())
     }
 }
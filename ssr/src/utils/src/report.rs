@@ -11,6 +11,16 @@ pub enum ReportOption {
 }
 
 impl ReportOption {
+    pub fn all() -> [ReportOption; 5] {
+        [
+            ReportOption::Nudity,
+            ReportOption::Violence,
+            ReportOption::Offensive,
+            ReportOption::Spam,
+            ReportOption::Other,
+        ]
+    }
+
     pub fn as_str(&self) -> impl Display {
         match self {
             ReportOption::Nudity => "Nudity/Porn",
@@ -29,7 +29,7 @@ macro_rules! try_or_redirect_opt {
 }
 
 pub fn failure_redirect<E: Display>(err: E) {
-    let path = format!("/error?err={err}");
+    let path = crate::app_error::error_path(err);
     #[cfg(feature = "hydrate")]
     {
         let nav = leptos_router::hooks::use_navigate();
@@ -4,4 +4,5 @@ use wasm_bindgen::prelude::wasm_bindgen;
 extern "C" {
     pub fn set_sentry_user(user_principal: Option<String>);
     pub fn set_sentry_user_canister(user_canister: Option<String>);
+    pub fn capture_app_error(message: &str, error_code: &str, correlation_id: &str);
 }
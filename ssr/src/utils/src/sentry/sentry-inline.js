@@ -18,4 +18,18 @@ export function set_sentry_user_canister(user_canister) {
     Sentry.onLoad(function () {
         Sentry.setTag("user_canister", user_canister ? user_canister : null)
     })
-}
\ No newline at end of file
+}
+
+export function capture_app_error(message, error_code, correlation_id) {
+    if (!window.Sentry) {
+        return;
+    }
+
+    Sentry.onLoad(function () {
+        Sentry.withScope(function (scope) {
+            scope.setTag("error_code", error_code);
+            scope.setTag("correlation_id", correlation_id);
+            Sentry.captureException(new Error(message));
+        });
+    })
+}
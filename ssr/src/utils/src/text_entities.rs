@@ -0,0 +1,320 @@
+//! Parsing of #hashtags, @mentions and URLs in post descriptions
+
+pub const MAX_HASHTAGS: usize = 8;
+pub const MAX_HASHTAG_LEN: usize = 32;
+pub const MAX_MENTIONS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EntityError {
+    #[error("Hashtags are required")]
+    NoHashtags,
+    #[error("Only a maximum of {MAX_HASHTAGS} hashtags are allowed")]
+    TooManyHashtags,
+    #[error("#{0} is too long, hashtags can be at most {MAX_HASHTAG_LEN} characters")]
+    HashtagTooLong(String),
+    #[error("#{0} is not a valid hashtag, only letters, numbers and _ are allowed")]
+    InvalidHashtag(String),
+    #[error("Only a maximum of {MAX_MENTIONS} mentions are allowed")]
+    TooManyMentions,
+}
+
+/// A piece of text, either plain or an entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextSegment<'a> {
+    Text(&'a str),
+    /// without the leading `#`, as written
+    Hashtag(&'a str),
+    /// without the leading `@`
+    Mention(&'a str),
+    Url(&'a str),
+}
+
+fn is_hashtag_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_mention_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')
+}
+
+/// Entities must not be glued to a preceding word, e.g `C#` or `me@mail.com`
+fn at_word_boundary(text: &str, idx: usize) -> bool {
+    text[..idx]
+        .chars()
+        .next_back()
+        .is_none_or(|c| !(c.is_alphanumeric() || c == '_'))
+}
+
+/// Length in bytes of the prefix of `rest` made of `pred` chars
+fn prefix_len(rest: &str, pred: impl Fn(char) -> bool) -> usize {
+    rest.char_indices()
+        .find(|(_, c)| !pred(*c))
+        .map(|(idx, _)| idx)
+        .unwrap_or(rest.len())
+}
+
+fn url_len(rest: &str) -> Option<usize> {
+    let lower = rest.get(..8).unwrap_or(rest).to_ascii_lowercase();
+    let prefix = ["https://", "http://", "www."]
+        .into_iter()
+        .find(|p| lower.starts_with(p))?;
+    let mut len = prefix_len(rest, |c| !c.is_whitespace());
+    // trailing punctuation is part of the sentence, not the url
+    while let Some(last) = rest[..len].chars().next_back() {
+        let unbalanced_paren = last == ')' && !rest[..len].contains('(');
+        if matches!(last, '.' | ',' | '!' | '?' | ';' | ':' | '"' | '\'') || unbalanced_paren {
+            len -= last.len_utf8();
+        } else {
+            break;
+        }
+    }
+    // just the scheme
+    (len > prefix.len()).then_some(len)
+}
+
+/// Splits `text` into plain text and entities
+pub fn parse(text: &str) -> Vec<TextSegment<'_>> {
+    let mut segments = vec![];
+    let mut plain_start = 0;
+    let mut idx = 0;
+
+    while let Some(c) = text[idx..].chars().next() {
+        let entity = if !at_word_boundary(text, idx) {
+            None
+        } else if c == '#' {
+            let len = prefix_len(&text[idx + 1..], is_hashtag_char);
+            (len > 0).then(|| (TextSegment::Hashtag(&text[idx + 1..idx + 1 + len]), len + 1))
+        } else if c == '@' {
+            let name = &text[idx + 1..];
+            let len = prefix_len(name, is_mention_char);
+            // dots and dashes can't end a mention, e.g "thanks @yral."
+            let len = name[..len].trim_end_matches(['.', '-']).len();
+            (len > 0).then(|| (TextSegment::Mention(&name[..len]), len + 1))
+        } else {
+            url_len(&text[idx..]).map(|len| (TextSegment::Url(&text[idx..idx + len]), len))
+        };
+
+        match entity {
+            Some((segment, len)) => {
+                if plain_start < idx {
+                    segments.push(TextSegment::Text(&text[plain_start..idx]));
+                }
+                segments.push(segment);
+                idx += len;
+                plain_start = idx;
+            }
+            None => idx += c.len_utf8(),
+        }
+    }
+    if plain_start < text.len() {
+        segments.push(TextSegment::Text(&text[plain_start..]));
+    }
+
+    segments
+}
+
+/// Lowercases and validates a single hashtag, the leading `#` is optional
+pub fn normalize_hashtag(raw: &str) -> Result<String, EntityError> {
+    let tag = raw.trim().trim_start_matches('#');
+    if tag.is_empty() || !tag.chars().all(is_hashtag_char) {
+        return Err(EntityError::InvalidHashtag(tag.to_string()));
+    }
+    if tag.chars().count() > MAX_HASHTAG_LEN {
+        return Err(EntityError::HashtagTooLong(tag.to_string()));
+    }
+    Ok(tag.to_lowercase())
+}
+
+fn dedup_preserving_order(items: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| seen.insert(item.clone()))
+        .collect()
+}
+
+/// Parses a comma or space separated list of hashtags,
+/// as entered in the upload form
+pub fn parse_hashtag_list(input: &str) -> Result<Vec<String>, EntityError> {
+    let tags = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|t| t.trim_start_matches('#'))
+        .filter(|t| !t.is_empty())
+        .map(normalize_hashtag)
+        .collect::<Result<Vec<_>, _>>()?;
+    let tags = dedup_preserving_order(tags);
+
+    if tags.is_empty() {
+        return Err(EntityError::NoHashtags);
+    }
+    if tags.len() > MAX_HASHTAGS {
+        return Err(EntityError::TooManyHashtags);
+    }
+    Ok(tags)
+}
+
+/// Normalized hashtags in `text`, without duplicates
+/// hashtags which are too long are skipped
+pub fn extract_hashtags(text: &str) -> Vec<String> {
+    dedup_preserving_order(parse(text).into_iter().filter_map(|s| match s {
+        TextSegment::Hashtag(tag) => normalize_hashtag(tag).ok(),
+        _ => None,
+    }))
+}
+
+/// Mentioned usernames in `text`, without duplicates
+pub fn extract_mentions(text: &str) -> Vec<String> {
+    dedup_preserving_order(parse(text).into_iter().filter_map(|s| match s {
+        TextSegment::Mention(name) => Some(name.to_lowercase()),
+        _ => None,
+    }))
+}
+
+/// Checks the entities used in a description
+pub fn validate_description_entities(text: &str) -> Result<(), EntityError> {
+    if extract_mentions(text).len() > MAX_MENTIONS {
+        return Err(EntityError::TooManyMentions);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TextSegment::*;
+
+    #[test]
+    fn parses_mixed_entities() {
+        assert_eq!(
+            parse("hey @alice check #Rust at https://yral.com."),
+            vec![
+                Text("hey "),
+                Mention("alice"),
+                Text(" check "),
+                Hashtag("Rust"),
+                Text(" at "),
+                Url("https://yral.com"),
+                Text("."),
+            ]
+        );
+    }
+
+    #[test]
+    fn plain_text_is_untouched() {
+        assert_eq!(parse("nothing to see"), vec![Text("nothing to see")]);
+        assert_eq!(parse(""), vec![]);
+        assert_eq!(parse("# @ lone symbols"), vec![Text("# @ lone symbols")]);
+    }
+
+    #[test]
+    fn entities_need_a_word_boundary() {
+        assert_eq!(
+            parse("C# and me@mail.com"),
+            vec![Text("C# and me@mail.com")]
+        );
+        assert_eq!(parse("(#tag)"), vec![Text("("), Hashtag("tag"), Text(")")]);
+    }
+
+    #[test]
+    fn unicode_hashtags() {
+        assert_eq!(
+            parse("#café #日本 #привет"),
+            vec![
+                Hashtag("café"),
+                Text(" "),
+                Hashtag("日本"),
+                Text(" "),
+                Hashtag("привет"),
+            ]
+        );
+        assert_eq!(normalize_hashtag("#CAFÉ"), Ok("café".to_string()));
+    }
+
+    #[test]
+    fn emoji_terminates_entities() {
+        assert_eq!(
+            parse("#fun🎉 @bob🔥"),
+            vec![Hashtag("fun"), Text("🎉 "), Mention("bob"), Text("🔥")]
+        );
+        assert_eq!(parse("🎉#party"), vec![Text("🎉"), Hashtag("party")]);
+        assert!(matches!(
+            normalize_hashtag("fun🎉"),
+            Err(EntityError::InvalidHashtag(_))
+        ));
+    }
+
+    #[test]
+    fn mentions_drop_trailing_punctuation() {
+        assert_eq!(
+            parse("thanks @yral.dev."),
+            vec![Text("thanks "), Mention("yral.dev"), Text(".")]
+        );
+    }
+
+    #[test]
+    fn urls_drop_trailing_punctuation() {
+        assert_eq!(
+            parse("(see www.yral.com/a_b)"),
+            vec![Text("(see "), Url("www.yral.com/a_b"), Text(")")]
+        );
+        assert_eq!(
+            parse("https://en.wikipedia.org/wiki/Rust_(language)!"),
+            vec![
+                Url("https://en.wikipedia.org/wiki/Rust_(language)"),
+                Text("!")
+            ]
+        );
+        assert_eq!(parse("https:// nope"), vec![Text("https:// nope")]);
+        assert_eq!(parse("www.a.co"), vec![Url("www.a.co")]);
+    }
+
+    #[test]
+    fn hashtag_list_normalizes_and_dedups() {
+        assert_eq!(
+            parse_hashtag_list("#Rust, rust,#RUST web3 ,, #日本"),
+            Ok(vec![
+                "rust".to_string(),
+                "web3".to_string(),
+                "日本".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn hashtag_list_errors() {
+        assert_eq!(parse_hashtag_list(" , #"), Err(EntityError::NoHashtags));
+        assert_eq!(
+            parse_hashtag_list("a,b,c,d,e,f,g,h,i"),
+            Err(EntityError::TooManyHashtags)
+        );
+        // duplicates don't count towards the limit
+        assert!(parse_hashtag_list("a,b,c,d,e,f,g,h,A").is_ok());
+        assert!(matches!(
+            parse_hashtag_list("good, bad-tag"),
+            Err(EntityError::InvalidHashtag(_))
+        ));
+        assert!(matches!(
+            parse_hashtag_list(&"a".repeat(MAX_HASHTAG_LEN + 1)),
+            Err(EntityError::HashtagTooLong(_))
+        ));
+    }
+
+    #[test]
+    fn extracts_unique_entities() {
+        let text = "#Fun #fun #FUN @Alice @alice https://yral.com";
+        assert_eq!(extract_hashtags(text), vec!["fun".to_string()]);
+        assert_eq!(extract_mentions(text), vec!["alice".to_string()]);
+    }
+
+    #[test]
+    fn too_many_mentions() {
+        let text = (0..=MAX_MENTIONS)
+            .map(|i| format!("@user{i}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(
+            validate_description_entities(&text),
+            Err(EntityError::TooManyMentions)
+        );
+    }
+}
@@ -25,6 +25,18 @@ pub fn get_day_month(epoch_secs: u64) -> String {
     format!("{:02} {month}", ts.day)
 }
 
+/// Short relative time like `5m` or `3d`, older dates fall back to [get_day_month]
+pub fn time_ago(epoch_secs: u64, now_secs: u64) -> String {
+    let elapsed = now_secs.saturating_sub(epoch_secs);
+    match elapsed {
+        0..60 => "now".to_string(),
+        60..3600 => format!("{}m", elapsed / 60),
+        3600..86400 => format!("{}h", elapsed / 3600),
+        86400..604800 => format!("{}d", elapsed / 86400),
+        _ => get_day_month(epoch_secs),
+    }
+}
+
 pub fn to_hh_mm_ss(duration: Duration) -> String {
     let secs = duration.as_secs();
     let hh = secs / 3600;
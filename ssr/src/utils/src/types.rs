@@ -1,4 +1,5 @@
 use candid::Principal;
+use serde::{Deserialize, Serialize};
 use yral_canisters_client::individual_user_template::PostStatus as PostStatusCandid;
 
 pub type PostId = (Principal, u64);
@@ -9,7 +10,7 @@ pub struct PostParams {
     pub post_id: u64,
 }
 
-#[derive(PartialEq, Debug, Eq)]
+#[derive(PartialEq, Debug, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum PostStatus {
     BannedForExplicitness,
     BannedDueToUserReporting,
@@ -33,3 +34,50 @@ impl From<&PostStatusCandid> for PostStatus {
         }
     }
 }
+
+impl PostStatus {
+    /// Stages a post goes through after being uploaded, in order
+    pub const STAGES: [PostStatus; 4] = [
+        PostStatus::Uploaded,
+        PostStatus::Transcoding,
+        PostStatus::CheckingExplicitness,
+        PostStatus::ReadyToView,
+    ];
+
+    /// Index of the status in [PostStatus::STAGES]
+    /// None for banned or deleted posts
+    pub fn stage(&self) -> Option<usize> {
+        Self::STAGES.iter().position(|s| s == self)
+    }
+
+    /// The post is still being processed and its status will change
+    pub fn is_processing(&self) -> bool {
+        matches!(
+            self,
+            PostStatus::Uploaded | PostStatus::Transcoding | PostStatus::CheckingExplicitness
+        )
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PostStatus::Uploaded => "Uploaded",
+            PostStatus::Transcoding => "Processing video",
+            PostStatus::CheckingExplicitness => "Reviewing content",
+            PostStatus::ReadyToView => "Live",
+            PostStatus::BannedForExplicitness | PostStatus::BannedDueToUserReporting => "Removed",
+            PostStatus::Deleted => "Deleted",
+        }
+    }
+
+    pub fn ban_reason(&self) -> Option<&'static str> {
+        match self {
+            PostStatus::BannedForExplicitness => {
+                Some("This video was flagged as explicit and violates our community guidelines")
+            }
+            PostStatus::BannedDueToUserReporting => {
+                Some("This video was removed after being reported by other users")
+            }
+            _ => None,
+        }
+    }
+}